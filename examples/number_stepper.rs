@@ -0,0 +1,13 @@
+use dialoguer::{theme::ColorfulTheme, NumberStepper};
+
+fn main() {
+    let workers: u32 = NumberStepper::with_theme(&ColorfulTheme::default())
+        .with_prompt("Number of workers")
+        .default(4)
+        .min(1)
+        .max(16)
+        .interact()
+        .unwrap();
+
+    println!("Starting {} workers", workers);
+}
@@ -0,0 +1,20 @@
+use dialoguer::{theme::ColorfulTheme, QuantitySelect};
+
+fn main() {
+    let items = &["Ice Cream", "Vanilla Cupcake", "Chocolate Muffin"];
+    let cart = QuantitySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Fill your cart")
+        .items(&items[..])
+        .max_quantity(10)
+        .interact()
+        .unwrap();
+
+    if cart.is_empty() {
+        println!("Your cart is empty :(");
+    } else {
+        println!("Your cart:");
+        for (idx, quantity) in cart {
+            println!("  {} x {}", quantity, items[idx]);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Provides awaiting prompts on an async runtime
+//!
+//! Reading keys blocks the thread, so the prompts run on tokio's blocking
+//! pool while the other tasks keep going. The prompts borrow their theme,
+//! validators and history, which keeps them from being sent there, so the
+//! prompt is built and run inside a closure instead.
+use std::{io, panic};
+
+use crate::error::Result;
+
+/// Runs a prompt on tokio's blocking pool and awaits its answer.
+///
+/// `f` builds the prompt and interacts with it. This works on both the
+/// current thread and the multi-threaded runtime. A panic in `f` resumes on
+/// the awaiting task and a runtime that shuts down before the prompt
+/// finished results in an IO error of kind `Interrupted`.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dialoguer::{interact_async, Confirm};
+///
+/// # async fn test() -> dialoguer::Result<()> {
+/// let proceed = interact_async(|| {
+///     Confirm::new()
+///         .with_prompt("Do you want to continue?")
+///         .interact()
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn interact_async<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(rv) => rv,
+        Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+        Err(err) => Err(io::Error::new(io::ErrorKind::Interrupted, err).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::Key, Select};
+
+    #[test]
+    fn test_interact_async() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (selection, other) = runtime.block_on(async {
+            let task = tokio::spawn(async { 1 });
+            let selection = interact_async(|| {
+                Select::new()
+                    .items(&["Apple", "Banana"])
+                    .interact_with_keys(&[Key::ArrowDown, Key::Enter])
+            })
+            .await;
+
+            (selection, task.await.unwrap())
+        });
+
+        assert_eq!(selection.unwrap(), Some(1));
+        assert_eq!(other, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "prompt panicked")]
+    fn test_interact_async_panic() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _ = runtime.block_on(interact_async(|| -> Result<()> {
+            panic!("prompt panicked")
+        }));
+    }
+}
@@ -0,0 +1,75 @@
+//! Provides capturing the rendered output of prompts
+use std::cell::RefCell;
+
+thread_local! {
+    static CAPTURED: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Runs `f` and returns its result along with the output prompts rendered.
+///
+/// Everything the themes render on the current thread while `f` runs is
+/// recorded in the order it is written, including redraws and the final
+/// selection. Cursor movements and clearing are not part of the output.
+/// The output is still written to the terminal as well.
+///
+/// This is meant for snapshot testing the appearance of prompts together
+/// with [Select::interact_with_keys](struct.Select.html#method.interact_with_keys).
+/// Styling is included as escape codes if colors are enabled for the
+/// terminal, which can be forced with
+/// [console::set_colors_enabled_stderr](https://docs.rs/console/0.13/console/fn.set_colors_enabled_stderr.html).
+/// Otherwise prompts render with the
+/// [PlainTheme](theme/struct.PlainTheme.html) regardless of their theme.
+///
+/// ## Example
+///
+/// ```rust
+/// use dialoguer::{capture_output, console::Key, Select};
+///
+/// let (selection, output) = capture_output(|| {
+///     Select::new()
+///         .with_prompt("Fruit")
+///         .items(&["Apple", "Banana"])
+///         .default(0)
+///         .interact_with_keys(&[Key::Enter])
+/// });
+///
+/// assert_eq!(selection.unwrap(), Some(0));
+/// assert!(output.ends_with("Fruit: Apple\n"));
+/// ```
+pub fn capture_output<T, F: FnOnce() -> T>(f: F) -> (T, String) {
+    let outer = CAPTURED.with(|captured| captured.replace(Some(String::new())));
+    let rv = f();
+    let output = CAPTURED.with(|captured| captured.replace(outer));
+
+    (rv, output.unwrap_or_default())
+}
+
+/// Records `text` if the output is being captured.
+pub(crate) fn record(text: &str) {
+    CAPTURED.with(|captured| {
+        if let Some(ref mut output) = *captured.borrow_mut() {
+            output.push_str(text);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_output() {
+        record("ignored");
+
+        let ((), output) = capture_output(|| {
+            record("outer ");
+
+            let ((), inner) = capture_output(|| record("inner"));
+            assert_eq!(inner, "inner");
+
+            record("again");
+        });
+
+        assert_eq!(output, "outer again");
+    }
+}
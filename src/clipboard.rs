@@ -0,0 +1,77 @@
+//! Provides clipboard access for input prompts
+
+/// A source of text to paste into input prompts.
+pub(crate) trait ClipboardSource {
+    /// Returns the current text contents, if there are any.
+    fn text(&self) -> Option<String>;
+}
+
+/// The clipboard of the system.
+///
+/// An unavailable clipboard, e.g. on a machine without a display server,
+/// is treated like an empty one.
+pub(crate) struct SystemClipboard;
+
+impl ClipboardSource for SystemClipboard {
+    fn text(&self) -> Option<String> {
+        arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .ok()
+    }
+}
+
+/// Inserts the contents of `source` into `chars` at `position`.
+///
+/// Newlines are stripped as well as any other control character the prompt
+/// would not accept when typed. Returns the number of inserted characters.
+pub(crate) fn paste(source: &dyn ClipboardSource, chars: &mut Vec<char>, position: usize) -> usize {
+    let text = source.text().unwrap_or_default();
+    let pasted: Vec<char> = text.chars().filter(|chr| !chr.is_control()).collect();
+    let count = pasted.len();
+
+    chars.splice(position..position, pasted);
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockClipboard(Option<&'static str>);
+
+    impl ClipboardSource for MockClipboard {
+        fn text(&self) -> Option<String> {
+            self.0.map(String::from)
+        }
+    }
+
+    #[test]
+    fn test_paste_inserts_at_position() {
+        let mut chars: Vec<char> = "https://".chars().collect();
+        chars.push('/');
+
+        let count = paste(&MockClipboard(Some("example.com")), &mut chars, 8);
+
+        assert_eq!(count, 11);
+        assert_eq!(chars.iter().collect::<String>(), "https://example.com/");
+    }
+
+    #[test]
+    fn test_paste_strips_newlines() {
+        let mut chars = vec![];
+
+        let count = paste(&MockClipboard(Some("abc\r\ndef\n")), &mut chars, 0);
+
+        assert_eq!(count, 6);
+        assert_eq!(chars.iter().collect::<String>(), "abcdef");
+    }
+
+    #[test]
+    fn test_paste_empty_clipboard() {
+        let mut chars = vec!['a'];
+
+        assert_eq!(paste(&MockClipboard(None), &mut chars, 1), 0);
+        assert_eq!(chars, vec!['a']);
+    }
+}
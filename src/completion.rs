@@ -0,0 +1,20 @@
+//! Provides completion for text inputs
+
+/// Trait for input completions.
+///
+/// A generic implementation for `Fn(&str) -> Option<String>` is provided
+/// to facilitate development.
+pub trait Completion {
+    /// Invoked with the current input when the user presses tab.
+    ///
+    /// The returned text replaces the whole input, so to extend it the
+    /// completion has to start with the input. Returning `None` leaves the
+    /// input unchanged.
+    fn get(&self, input: &str) -> Option<String>;
+}
+
+impl<F: Fn(&str) -> Option<String>> Completion for F {
+    fn get(&self, input: &str) -> Option<String> {
+        self(input)
+    }
+}
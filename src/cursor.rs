@@ -0,0 +1,226 @@
+use std::{cell::Cell, io, thread};
+
+use console::{Term, TermTarget};
+
+thread_local! {
+    /// Number of prompts currently keeping the cursor hidden, for stdout
+    /// and stderr.
+    static HIDDEN_DEPTH: [Cell<usize>; 2] = [Cell::new(0), Cell::new(0)];
+}
+
+/// Returns the entry of `HIDDEN_DEPTH` for `target`.
+fn depth_slot(target: TermTarget) -> usize {
+    match target {
+        TermTarget::Stdout => 0,
+        TermTarget::Stderr => 1,
+    }
+}
+
+/// Keeps the cursor hidden while a prompt is active.
+///
+/// Guards on the same target nest: only the outermost guard hides the
+/// cursor and shows it again when dropped, so a prompt running inside
+/// another one does not make the cursor flicker or leave it visible for the
+/// rest of the outer prompt. Guards on stdout and stderr are independent.
+/// The cursor is also shown again if the prompt returns early with an error
+/// or unwinds because a callback like a validator panicked. In the latter
+/// case the current line is cleared as well. The panic hook has already
+/// printed its message by then, so this doesn't keep the message off the
+/// prompt, but output that follows a caught panic starts on a clean line.
+pub(crate) struct HiddenCursor<'a, T: CursorTerm + ?Sized = Term> {
+    term: &'a T,
+}
+
+/// The terminal operations `HiddenCursor` needs.
+pub(crate) trait CursorTerm {
+    fn target(&self) -> TermTarget;
+    fn hide_cursor(&self) -> io::Result<()>;
+    fn show_cursor(&self) -> io::Result<()>;
+    fn clear_line(&self) -> io::Result<()>;
+    fn flush(&self) -> io::Result<()>;
+}
+
+impl CursorTerm for Term {
+    fn target(&self) -> TermTarget {
+        Term::target(self)
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        Term::hide_cursor(self)
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        Term::show_cursor(self)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        Term::clear_line(self)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Term::flush(self)
+    }
+}
+
+impl<'a, T: CursorTerm + ?Sized> HiddenCursor<'a, T> {
+    pub fn new(term: &'a T) -> io::Result<HiddenCursor<'a, T>> {
+        let slot = depth_slot(term.target());
+
+        if HIDDEN_DEPTH.with(|depth| depth[slot].get()) == 0 {
+            term.hide_cursor()?;
+        }
+
+        HIDDEN_DEPTH.with(|depth| depth[slot].set(depth[slot].get() + 1));
+
+        Ok(HiddenCursor { term })
+    }
+}
+
+impl<'a, T: CursorTerm + ?Sized> Drop for HiddenCursor<'a, T> {
+    fn drop(&mut self) {
+        let slot = depth_slot(self.term.target());
+        let depth = HIDDEN_DEPTH.with(|depth| {
+            depth[slot].set(depth[slot].get() - 1);
+            depth[slot].get()
+        });
+
+        if depth == 0 {
+            if thread::panicking() {
+                let _ = self.term.clear_line();
+            }
+            let _ = self.term.show_cursor();
+            let _ = self.term.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, panic, time::Duration};
+
+    use console::Key;
+
+    use super::*;
+    use crate::{Confirm, Select};
+
+    fn depth() -> usize {
+        HIDDEN_DEPTH.with(|depth| depth[depth_slot(TermTarget::Stderr)].get())
+    }
+
+    /// Records the escape sequences console writes for the operations.
+    #[derive(Default)]
+    struct RecordingTerm(RefCell<String>);
+
+    impl CursorTerm for RecordingTerm {
+        fn target(&self) -> TermTarget {
+            TermTarget::Stderr
+        }
+
+        fn hide_cursor(&self) -> io::Result<()> {
+            self.0.borrow_mut().push_str("\x1b[?25l");
+            Ok(())
+        }
+
+        fn show_cursor(&self) -> io::Result<()> {
+            self.0.borrow_mut().push_str("\x1b[?25h");
+            Ok(())
+        }
+
+        fn clear_line(&self) -> io::Result<()> {
+            self.0.borrow_mut().push_str("\r\x1b[2K");
+            Ok(())
+        }
+
+        fn flush(&self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_nested_guards_are_balanced() {
+        let term = Term::buffered_stderr();
+
+        {
+            let _outer = HiddenCursor::new(&term).unwrap();
+            assert_eq!(depth(), 1);
+
+            {
+                let _inner = HiddenCursor::new(&term).unwrap();
+                assert_eq!(depth(), 2);
+            }
+
+            assert_eq!(depth(), 1);
+        }
+
+        assert_eq!(depth(), 0);
+    }
+
+    #[test]
+    fn test_targets_are_independent() {
+        let stderr = Term::buffered_stderr();
+        let stdout = Term::buffered_stdout();
+
+        let _outer = HiddenCursor::new(&stderr).unwrap();
+
+        {
+            let _inner = HiddenCursor::new(&stdout).unwrap();
+            assert_eq!(HIDDEN_DEPTH.with(|depth| depth[0].get()), 1);
+            assert_eq!(depth(), 1);
+        }
+
+        assert_eq!(HIDDEN_DEPTH.with(|depth| depth[0].get()), 0);
+        assert_eq!(depth(), 1);
+    }
+
+    #[test]
+    fn test_nested_prompt_keeps_cursor_hidden() {
+        let term = Term::buffered_stderr();
+        let _outer = HiddenCursor::new(&term).unwrap();
+
+        Confirm::new()
+            .default(true)
+            .timeout(Duration::from_millis(10))
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(depth(), 1);
+
+        Confirm::new()
+            .timeout(Duration::from_millis(10))
+            .interact_on(&term)
+            .unwrap_err();
+        assert_eq!(depth(), 1);
+    }
+
+    #[test]
+    fn test_panicking_validator_shows_cursor() {
+        let result = panic::catch_unwind(|| {
+            Select::new()
+                .items(&["Apple", "Banana"])
+                .default(0)
+                .validate_with(|_: &usize| -> Result<(), String> { panic!("validator failed") })
+                .interact_with_keys(&[Key::Enter])
+        });
+
+        assert!(result.is_err());
+        assert_eq!(depth(), 0);
+    }
+
+    #[test]
+    fn test_panic_clears_line_and_shows_cursor() {
+        let term = RecordingTerm::default();
+
+        {
+            let _cursor = HiddenCursor::new(&term).unwrap();
+        }
+        assert_eq!(*term.0.borrow(), "\x1b[?25l\x1b[?25h");
+
+        term.0.borrow_mut().clear();
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let _cursor = HiddenCursor::new(&term).unwrap();
+            panic!("prompt failed");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(*term.0.borrow(), "\x1b[?25l\r\x1b[2K\x1b[?25h");
+    }
+}
@@ -0,0 +1,83 @@
+//! Provides the error type of the prompts
+use std::{error::Error as StdError, fmt, io, result};
+
+/// Possible errors returned by prompts.
+#[derive(Debug)]
+pub enum Error {
+    /// Error while reading from or writing to the terminal.
+    IO(io::Error),
+    /// The prompt was cancelled where that can't be reported as `None`.
+    Cancelled,
+    /// The answer was rejected and can't be corrected, e.g. because it was
+    /// piped in or because no attempts are left.
+    ValidationFailed(String),
+}
+
+/// Result type of the prompts.
+pub type Result<T = ()> = result::Result<T, Error>;
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::IO(err)
+    }
+}
+
+/// Converts back for callers that still work with `io::Result`.
+///
+/// A cancelled prompt becomes an error of kind `Interrupted` and a failed
+/// validation one of kind `InvalidInput` carrying the message.
+impl From<Error> for io::Error {
+    fn from(err: Error) -> io::Error {
+        match err {
+            Error::IO(err) => err,
+            Error::Cancelled => io::Error::new(io::ErrorKind::Interrupted, "Prompt was cancelled"),
+            Error::ValidationFailed(message) => {
+                io::Error::new(io::ErrorKind::InvalidInput, message)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::IO(ref err) => write!(f, "{}", err),
+            Error::Cancelled => write!(f, "Prompt was cancelled"),
+            Error::ValidationFailed(ref message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            Error::IO(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_roundtrip() {
+        let err = Error::from(io::Error::new(io::ErrorKind::TimedOut, "late"));
+        assert_eq!(err.to_string(), "late");
+        assert!(err.source().is_some());
+
+        let err = io::Error::from(err);
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_into_io_error() {
+        let err = io::Error::from(Error::Cancelled);
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+
+        let err = io::Error::from(Error::ValidationFailed("Too short".into()));
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "Too short");
+    }
+}
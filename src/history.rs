@@ -0,0 +1,209 @@
+//! Provides history for input prompts
+#[cfg(feature = "file-history")]
+use std::{
+    collections::VecDeque,
+    fs, io,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Trait for input histories.
+///
+/// Entries are addressed by their distance from the most recent one, so
+/// `read(0)` returns the last written entry. An implementation for
+/// `Vec<String>` is provided for in-process use.
+pub trait History {
+    /// Returns the entry at `pos`, counting back from the most recent one.
+    fn read(&self, pos: usize) -> Option<String>;
+
+    /// Adds a submitted value to the history.
+    fn write(&mut self, val: &str);
+}
+
+impl History for Vec<String> {
+    fn read(&self, pos: usize) -> Option<String> {
+        if pos < self.len() {
+            Some(self[self.len() - pos - 1].clone())
+        } else {
+            None
+        }
+    }
+
+    fn write(&mut self, val: &str) {
+        self.push(val.into());
+    }
+}
+
+/// A history backed by a file.
+///
+/// Every entry is stored on its own line. The file is read on construction
+/// and new entries are appended to it. An entry equal to the most recent one
+/// is not stored again and only the latest
+/// [max_entries](#method.max_entries) are kept. Failing to write the file is
+/// silently ignored.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{FileHistory, Input};
+///
+/// let mut history = FileHistory::open(".history")?;
+/// let command: String = Input::new()
+///     .with_prompt("Command")
+///     .history_with(&mut history)
+///     .interact_text()?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "file-history")]
+pub struct FileHistory {
+    path: PathBuf,
+    entries: VecDeque<String>,
+    max_entries: usize,
+}
+
+#[cfg(feature = "file-history")]
+impl FileHistory {
+    /// Opens the history stored at `path`.
+    ///
+    /// A missing file is treated as an empty history and created on the
+    /// first write.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<FileHistory> {
+        let path = path.as_ref().to_path_buf();
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(FileHistory {
+            path,
+            entries: contents.lines().map(|line| line.to_string()).collect(),
+            max_entries: 1000,
+        })
+    }
+
+    /// Sets the maximum number of stored entries.
+    ///
+    /// Older entries are dropped first. The default is 1000.
+    pub fn max_entries(&mut self, val: usize) -> &mut FileHistory {
+        self.max_entries = val;
+
+        if self.truncate() {
+            let _ = self.save();
+        }
+
+        self
+    }
+
+    /// Drops the oldest entries above the limit and reports if any were dropped.
+    fn truncate(&mut self) -> bool {
+        let excess = self.entries.len().saturating_sub(self.max_entries);
+        self.entries.drain(..excess);
+        excess > 0
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let mut contents = String::new();
+
+        for entry in &self.entries {
+            contents.push_str(entry);
+            contents.push('\n');
+        }
+
+        fs::write(&self.path, contents)
+    }
+
+    fn append(&self, val: &str) -> io::Result<()> {
+        let mut f = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        writeln!(f, "{}", val)
+    }
+}
+
+#[cfg(feature = "file-history")]
+impl History for FileHistory {
+    fn read(&self, pos: usize) -> Option<String> {
+        if pos < self.entries.len() {
+            self.entries.get(self.entries.len() - pos - 1).cloned()
+        } else {
+            None
+        }
+    }
+
+    fn write(&mut self, val: &str) {
+        if val.is_empty() || self.entries.back().map(String::as_str) == Some(val) {
+            return;
+        }
+
+        self.entries.push_back(val.into());
+
+        let _ = if self.truncate() {
+            self.save()
+        } else {
+            self.append(val)
+        };
+    }
+}
+
+#[cfg(all(test, feature = "file-history"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_history_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history");
+
+        let mut history = FileHistory::open(&path).unwrap();
+        assert_eq!(history.read(0), None);
+
+        history.write("ls");
+        history.write("cd");
+
+        let history = FileHistory::open(&path).unwrap();
+        assert_eq!(history.read(0), Some("cd".to_string()));
+        assert_eq!(history.read(1), Some("ls".to_string()));
+        assert_eq!(history.read(2), None);
+    }
+
+    #[test]
+    fn test_file_history_dedup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history");
+
+        let mut history = FileHistory::open(&path).unwrap();
+        history.write("ls");
+        history.write("ls");
+        history.write("cd");
+        history.write("ls");
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "ls\ncd\nls\n");
+    }
+
+    #[test]
+    fn test_file_history_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history");
+
+        let mut history = FileHistory::open(&path).unwrap();
+        history.max_entries(2);
+        history.write("a");
+        history.write("b");
+        history.write("c");
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "b\nc\n");
+
+        let mut history = FileHistory::open(&path).unwrap();
+        history.max_entries(1);
+
+        assert_eq!(history.read(0), Some("c".to_string()));
+        assert_eq!(history.read(1), None);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "c\n");
+    }
+}
@@ -0,0 +1,307 @@
+//! Provides the key presses prompts react to
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    io,
+    time::Duration,
+};
+
+use console::{Key, Term};
+
+use crate::timeout::{key_pending, read_key_within};
+
+/// Actions of a select prompt that keys can be bound to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAction {
+    /// Moves the selection up.
+    Up,
+    /// Moves the selection down.
+    Down,
+    /// Picks the selected item.
+    Confirm,
+    /// Quits the prompt where that is allowed.
+    Cancel,
+    /// Moves to the next page.
+    NextPage,
+    /// Moves to the previous page.
+    PrevPage,
+}
+
+/// Maps keys to the actions of a select prompt.
+///
+/// Every action can be bound to any number of keys. Keys that aren't bound
+/// keep their fixed meaning, e.g. `Home` and `End` jump to the first and
+/// last item and printable characters jump to the next item starting with
+/// them.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dialoguer::{console::Key, KeyAction, KeyBindings, Select};
+///
+/// # fn test() -> std::io::Result<()> {
+/// let mut bindings = KeyBindings::arrows_only();
+/// bindings
+///     .bind(KeyAction::Up, Key::Char('w'))
+///     .bind(KeyAction::Down, Key::Char('s'));
+///
+/// let selection = Select::new()
+///     .items(&["Apple", "Banana"])
+///     .key_bindings(bindings)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeyBindings {
+    bindings: Vec<(Key, KeyAction)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> KeyBindings {
+        KeyBindings::vim()
+    }
+}
+
+impl KeyBindings {
+    /// Creates key bindings without any keys bound.
+    pub fn new() -> KeyBindings {
+        KeyBindings { bindings: vec![] }
+    }
+
+    /// Creates key bindings for the arrow keys.
+    ///
+    /// The arrows move the selection and the pages, enter and space confirm
+    /// and escape cancels.
+    pub fn arrows_only() -> KeyBindings {
+        let mut rv = KeyBindings::new();
+        rv.bind(KeyAction::Up, Key::ArrowUp)
+            .bind(KeyAction::Down, Key::ArrowDown)
+            .bind(KeyAction::PrevPage, Key::ArrowLeft)
+            .bind(KeyAction::NextPage, Key::ArrowRight)
+            .bind(KeyAction::Confirm, Key::Enter)
+            .bind(KeyAction::Confirm, Key::Char(' '))
+            .bind(KeyAction::Cancel, Key::Escape);
+        rv
+    }
+
+    /// Creates key bindings for the arrow keys and the vim-style keys.
+    ///
+    /// In addition to [arrows_only](#method.arrows_only) `j`, `k`, `h` and
+    /// `l` move the selection and the pages and `q` cancels. These are the
+    /// default bindings.
+    pub fn vim() -> KeyBindings {
+        let mut rv = KeyBindings::arrows_only();
+        rv.bind(KeyAction::Down, Key::Char('j'))
+            .bind(KeyAction::Up, Key::Char('k'))
+            .bind(KeyAction::PrevPage, Key::Char('h'))
+            .bind(KeyAction::NextPage, Key::Char('l'))
+            .bind(KeyAction::Cancel, Key::Char('q'));
+        rv
+    }
+
+    /// Binds `key` to `action`, replacing what it was bound to before.
+    pub fn bind(&mut self, action: KeyAction, key: Key) -> &mut KeyBindings {
+        self.unbind(&key);
+        self.bindings.push((key, action));
+        self
+    }
+
+    /// Removes the binding of `key`.
+    pub fn unbind(&mut self, key: &Key) -> &mut KeyBindings {
+        self.bindings.retain(|(bound, _)| bound != key);
+        self
+    }
+
+    /// Returns the action `key` is bound to.
+    pub fn action(&self, key: &Key) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| bound == key)
+            .map(|&(_, action)| action)
+    }
+}
+
+/// A source of key presses.
+pub(crate) trait KeySource {
+    /// Reads the next key, giving up after `timeout` if set.
+    fn read_key(&self, term: &Term, timeout: Option<Duration>) -> io::Result<Option<Key>>;
+
+    /// Whether another key can be read right away.
+    fn has_pending_key(&self, term: &Term) -> io::Result<bool>;
+
+    /// Reads a line of text up to Enter.
+    fn read_line(&self, term: &Term) -> io::Result<String> {
+        term.read_line()
+    }
+
+    /// Whether the keys are replayed instead of read from a terminal.
+    fn is_scripted(&self) -> bool {
+        false
+    }
+}
+
+/// Reads keys from the terminal.
+pub(crate) struct TermKeys;
+
+impl KeySource for TermKeys {
+    fn read_key(&self, term: &Term, timeout: Option<Duration>) -> io::Result<Option<Key>> {
+        read_key_within(term, timeout)
+    }
+
+    fn has_pending_key(&self, term: &Term) -> io::Result<bool> {
+        key_pending(term)
+    }
+}
+
+/// Replays a fixed sequence of keys.
+///
+/// Timeouts don't apply. Reading past the last key is an error of kind
+/// `UnexpectedEof`, so a script that doesn't finish the prompt fails
+/// instead of blocking.
+pub(crate) struct ScriptedKeys(RefCell<VecDeque<Key>>);
+
+impl ScriptedKeys {
+    pub fn new(keys: &[Key]) -> ScriptedKeys {
+        ScriptedKeys(RefCell::new(keys.iter().cloned().collect()))
+    }
+}
+
+impl KeySource for ScriptedKeys {
+    fn read_key(&self, _term: &Term, _timeout: Option<Duration>) -> io::Result<Option<Key>> {
+        match self.0.borrow_mut().pop_front() {
+            Some(key) => Ok(Some(key)),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Ran out of scripted keys",
+            )),
+        }
+    }
+
+    fn has_pending_key(&self, _term: &Term) -> io::Result<bool> {
+        Ok(!self.0.borrow().is_empty())
+    }
+
+    fn read_line(&self, term: &Term) -> io::Result<String> {
+        let mut line = String::new();
+
+        loop {
+            match self.read_key(term, None)? {
+                Some(Key::Enter) => return Ok(line),
+                Some(Key::Backspace) => {
+                    line.pop();
+                }
+                Some(Key::Char(chr)) => line.push(chr),
+                _ => {}
+            }
+        }
+    }
+
+    fn is_scripted(&self) -> bool {
+        true
+    }
+}
+
+/// Counts the keys read from another source.
+pub(crate) struct CountingKeys<'a> {
+    inner: &'a dyn KeySource,
+    count: Cell<usize>,
+}
+
+impl<'a> CountingKeys<'a> {
+    pub fn new(inner: &'a dyn KeySource) -> CountingKeys<'a> {
+        CountingKeys {
+            inner,
+            count: Cell::new(0),
+        }
+    }
+
+    /// Returns the number of keys read so far.
+    pub fn count(&self) -> usize {
+        self.count.get()
+    }
+}
+
+impl<'a> KeySource for CountingKeys<'a> {
+    fn read_key(&self, term: &Term, timeout: Option<Duration>) -> io::Result<Option<Key>> {
+        let key = self.inner.read_key(term, timeout)?;
+
+        if key.is_some() {
+            self.count.set(self.count.get() + 1);
+        }
+
+        Ok(key)
+    }
+
+    fn has_pending_key(&self, term: &Term) -> io::Result<bool> {
+        self.inner.has_pending_key(term)
+    }
+
+    fn read_line(&self, term: &Term) -> io::Result<String> {
+        self.inner.read_line(term)
+    }
+
+    fn is_scripted(&self) -> bool {
+        self.inner.is_scripted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_bindings() {
+        let vim = KeyBindings::vim();
+        assert_eq!(vim.action(&Key::Char('j')), Some(KeyAction::Down));
+        assert_eq!(vim.action(&Key::ArrowUp), Some(KeyAction::Up));
+        assert_eq!(vim.action(&Key::Char('x')), None);
+
+        let mut arrows = KeyBindings::arrows_only();
+        assert_eq!(arrows.action(&Key::Char('q')), None);
+        assert_eq!(arrows.action(&Key::Char(' ')), Some(KeyAction::Confirm));
+
+        arrows
+            .bind(KeyAction::Cancel, Key::Enter)
+            .unbind(&Key::Escape);
+        assert_eq!(arrows.action(&Key::Enter), Some(KeyAction::Cancel));
+        assert_eq!(arrows.action(&Key::Escape), None);
+    }
+
+    #[test]
+    fn test_scripted_keys() {
+        let term = Term::buffered_stderr();
+        let keys = ScriptedKeys::new(&[Key::ArrowDown, Key::Enter]);
+
+        assert_eq!(keys.read_key(&term, None).unwrap(), Some(Key::ArrowDown));
+        assert!(keys.has_pending_key(&term).unwrap());
+        assert_eq!(keys.read_key(&term, None).unwrap(), Some(Key::Enter));
+        assert!(!keys.has_pending_key(&term).unwrap());
+
+        let err = keys.read_key(&term, None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let keys = ScriptedKeys::new(&[
+            Key::Char('a'),
+            Key::Char('x'),
+            Key::Backspace,
+            Key::Char('b'),
+            Key::Enter,
+        ]);
+        assert_eq!(keys.read_line(&term).unwrap(), "ab");
+    }
+
+    #[test]
+    fn test_counting_keys() {
+        let term = Term::buffered_stderr();
+        let scripted = ScriptedKeys::new(&[Key::ArrowDown, Key::Enter]);
+        let keys = CountingKeys::new(&scripted);
+
+        keys.read_key(&term, None).unwrap();
+        keys.read_key(&term, None).unwrap();
+        keys.read_key(&term, None).unwrap_err();
+
+        assert_eq!(keys.count(), 2);
+        assert!(keys.is_scripted());
+    }
+}
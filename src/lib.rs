@@ -16,16 +16,73 @@
 //! * Selections prompts (single and multi)
 //! * Other kind of prompts
 //! * Editor launching
+//!
+//! # Piped Input
+//!
+//! If stdin is piped in and the terminal is not user attended, the prompts
+//! read one line per answer instead of keys:
+//!
+//! * `Confirm` takes `yes`, `no` or one of its answer keys.
+//! * `Select` and `TypedSelect` take an item index or item text.
+//! * `MultiSelect` takes a comma separated list of item indices or texts.
+//! * `Sort` takes a comma separated list of all item indices or texts in
+//!   their new order.
+//! * `QuantitySelect` takes a comma separated list of item indices or texts,
+//!   each optionally followed by `=` and a quantity, e.g. `Apples=2, Pears`.
+//! * `NumberStepper` takes a number within its bounds.
+//! * `Input` and `Password` take the text itself, and `Password` takes a
+//!   second line if confirmation is enabled.
+//! * `Input::interact_lines` takes one value per line until a blank line or
+//!   the end of the input.
+//! * `Input::interact_separated` takes all values on one line.
+//!
+//! An empty line or the end of the input picks the default. An invalid
+//! answer is an `Error::ValidationFailed` as it can't be corrected, and a
+//! missing answer without a default an IO error of kind `UnexpectedEof`.
+//! Prompts with a timeout keep waiting it out instead.
 
+#[cfg(feature = "tokio")]
+pub use blocking::interact_async;
+pub use capture::capture_output;
+pub use completion::Completion;
 pub use console;
 pub use edit::Editor;
+pub use error::{Error, Result};
+#[cfg(feature = "file-history")]
+pub use history::FileHistory;
+pub use history::History;
+pub use keys::{KeyAction, KeyBindings};
+#[cfg(feature = "file-memory")]
+pub use memory::FileMemory;
+pub use memory::SelectionMemory;
 pub use prompts::{
-    confirm::Confirm, input::Input, multi_select::MultiSelect, password::Password, select::Select,
+    confirm::{Confirm, ConfirmAction},
+    input::{Input, InputSource},
+    multi_select::{MultiSelect, SelectionResult},
+    number_stepper::{NumberStepper, Steppable},
+    password::Password,
+    quantity_select::QuantitySelect,
+    recap::{Recap, RecapOutcome},
+    select::{DescriptionLayout, InteractionReport, Select, SelectionOutcome},
     sort::Sort,
+    typed_select::TypedSelect,
 };
-pub use validate::Validator;
+pub use validate::{NotIn, Validator};
 
+#[cfg(feature = "tokio")]
+mod blocking;
+mod capture;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod completion;
+mod cursor;
 mod edit;
+mod error;
+mod history;
+mod keys;
+mod memory;
+mod piped;
 mod prompts;
 pub mod theme;
+mod timeout;
 mod validate;
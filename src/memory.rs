@@ -0,0 +1,200 @@
+//! Provides storage for remembering selections across runs
+use std::collections::HashMap;
+
+#[cfg(feature = "file-memory")]
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Trait for selection memories.
+///
+/// A memory maps a caller supplied key to the index that was last chosen
+/// for it. An implementation for `HashMap<String, usize>` is provided for
+/// in-process use.
+pub trait SelectionMemory {
+    /// Returns the index previously stored for `key`, if any.
+    fn load(&self, key: &str) -> Option<usize>;
+
+    /// Remembers `index` as the latest choice for `key`.
+    fn store(&mut self, key: &str, index: usize);
+}
+
+impl SelectionMemory for HashMap<String, usize> {
+    fn load(&self, key: &str) -> Option<usize> {
+        self.get(key).cloned()
+    }
+
+    fn store(&mut self, key: &str, index: usize) {
+        self.insert(key.into(), index);
+    }
+}
+
+/// A selection memory backed by a file.
+///
+/// Every entry is stored on its own line as the key followed by a tab and
+/// the index. Backslashes, tabs and line breaks in keys are escaped with a
+/// backslash, so any key can be stored. The file is read on construction and rewritten on every
+/// store. Failing to write the file is silently ignored, so a read-only
+/// location only means choices are not remembered.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{FileMemory, Select};
+///
+/// let mut memory = FileMemory::open(".selections")?;
+/// let selection = Select::new()
+///     .items(&["Debug", "Release"])
+///     .with_memory("build-profile", &mut memory)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "file-memory")]
+pub struct FileMemory {
+    path: PathBuf,
+    entries: HashMap<String, usize>,
+}
+
+#[cfg(feature = "file-memory")]
+impl FileMemory {
+    /// Opens the memory stored at `path`.
+    ///
+    /// A missing file is treated as an empty memory and created on the
+    /// first store. Malformed lines are skipped.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<FileMemory> {
+        let path = path.as_ref().to_path_buf();
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        let entries = contents
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.rsplitn(2, '\t');
+                let index = parts.next()?.parse().ok()?;
+                let key = unescape_key(parts.next()?)?;
+                Some((key, index))
+            })
+            .collect();
+
+        Ok(FileMemory { path, entries })
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let mut contents = String::new();
+
+        for (key, index) in &self.entries {
+            contents.push_str(&format!("{}\t{}\n", escape_key(key), index));
+        }
+
+        fs::write(&self.path, contents)
+    }
+}
+
+#[cfg(feature = "file-memory")]
+impl SelectionMemory for FileMemory {
+    fn load(&self, key: &str) -> Option<usize> {
+        self.entries.load(key)
+    }
+
+    fn store(&mut self, key: &str, index: usize) {
+        self.entries.store(key, index);
+        let _ = self.save();
+    }
+}
+
+/// Escapes the characters of `key` that would break up its line.
+#[cfg(feature = "file-memory")]
+fn escape_key(key: &str) -> String {
+    let mut rv = String::with_capacity(key.len());
+
+    for chr in key.chars() {
+        match chr {
+            '\\' => rv.push_str("\\\\"),
+            '\t' => rv.push_str("\\t"),
+            '\n' => rv.push_str("\\n"),
+            '\r' => rv.push_str("\\r"),
+            chr => rv.push(chr),
+        }
+    }
+
+    rv
+}
+
+/// Reverses `escape_key`, returning `None` for unknown escapes.
+#[cfg(feature = "file-memory")]
+fn unescape_key(escaped: &str) -> Option<String> {
+    let mut rv = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+
+    while let Some(chr) = chars.next() {
+        rv.push(match chr {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            chr => chr,
+        });
+    }
+
+    Some(rv)
+}
+
+#[cfg(all(test, feature = "file-memory"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_memory_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory");
+
+        let mut memory = FileMemory::open(&path).unwrap();
+        assert_eq!(memory.load("flavor"), None);
+
+        memory.store("flavor", 2);
+        memory.store("size", 0);
+        memory.store("flavor", 1);
+
+        let memory = FileMemory::open(&path).unwrap();
+        assert_eq!(memory.load("flavor"), Some(1));
+        assert_eq!(memory.load("size"), Some(0));
+    }
+
+    #[test]
+    fn test_file_memory_escapes_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory");
+
+        let mut memory = FileMemory::open(&path).unwrap();
+        memory.store("multi\nline\tkey\\", 4);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "multi\\nline\\tkey\\\\\t4\n"
+        );
+
+        let memory = FileMemory::open(&path).unwrap();
+        assert_eq!(memory.load("multi\nline\tkey\\"), Some(4));
+    }
+
+    #[test]
+    fn test_file_memory_skips_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory");
+        fs::write(&path, "garbage\nflavor\tx\nbad\\q\t1\nsize\t3\n").unwrap();
+
+        let memory = FileMemory::open(&path).unwrap();
+        assert_eq!(memory.load("flavor"), None);
+        assert_eq!(memory.load("bad\\q"), None);
+        assert_eq!(memory.load("size"), Some(3));
+    }
+}
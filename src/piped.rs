@@ -0,0 +1,212 @@
+//! Provides reading answers from piped stdin
+//!
+//! When neither the terminal is user attended nor stdin is a terminal, e.g.
+//! in a script that pipes the answers in, prompts read one plain line per
+//! answer instead of keys. An empty line or the end of the input picks the
+//! default. Prompts with a timeout keep waiting it out instead.
+use std::io::{self, BufRead};
+
+use console::{strip_ansi_codes, Term};
+
+use crate::error::Error;
+
+/// A source of answer lines.
+pub(crate) trait LineSource {
+    /// Returns the next line without the line ending, or `None` at the end.
+    fn read_line(&self) -> io::Result<Option<String>>;
+}
+
+/// The standard input of the process.
+pub(crate) struct Stdin;
+
+impl LineSource for Stdin {
+    fn read_line(&self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+
+        if io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let len = line.trim_end_matches(&['\r', '\n'][..]).len();
+        line.truncate(len);
+
+        Ok(Some(line))
+    }
+}
+
+/// The lines typed on a terminal.
+pub(crate) struct TermLines<'a>(pub &'a Term);
+
+impl<'a> LineSource for TermLines<'a> {
+    fn read_line(&self) -> io::Result<Option<String>> {
+        self.0.read_line().map(Some)
+    }
+}
+
+/// Returns whether answers are read from piped stdin instead of `term`.
+pub(crate) fn is_piped(term: &Term) -> bool {
+    !term.features().is_attended() && !stdin_is_terminal()
+}
+
+#[cfg(unix)]
+fn stdin_is_terminal() -> bool {
+    unsafe { libc::isatty(libc::STDIN_FILENO) == 1 }
+}
+
+/// Piped stdin can't be detected, so prompts behave as before.
+#[cfg(not(unix))]
+fn stdin_is_terminal() -> bool {
+    true
+}
+
+/// Reads the next answer.
+///
+/// Returns `None` if the line is blank or the input has ended.
+pub(crate) fn read_answer(source: &dyn LineSource) -> io::Result<Option<String>> {
+    Ok(source
+        .read_line()?
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty()))
+}
+
+/// Returns the error for a prompt without an answer nor a default.
+pub(crate) fn no_answer_error() -> Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "No answer was given on stdin").into()
+}
+
+/// Returns the error for an answer that doesn't fit the prompt.
+pub(crate) fn invalid_answer_error(message: &str) -> Error {
+    Error::ValidationFailed(message.into())
+}
+
+/// Parses a confirm answer.
+///
+/// Accepts `yes` and `no` as well as the single answer keys.
+pub(crate) fn parse_bool(answer: &str, yes_keys: &[char], no_keys: &[char]) -> Option<bool> {
+    let mut chars = answer.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if yes_keys.contains(&c) => Some(true),
+        (Some(c), None) if no_keys.contains(&c) => Some(false),
+        _ if answer.eq_ignore_ascii_case("yes") => Some(true),
+        _ if answer.eq_ignore_ascii_case("no") => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses an item index or item text.
+///
+/// Texts are matched without styling, exactly if possible and ignoring case
+/// otherwise.
+pub(crate) fn parse_index<S: AsRef<str>>(answer: &str, items: &[S]) -> Option<usize> {
+    if let Ok(index) = answer.parse::<usize>() {
+        return if index < items.len() {
+            Some(index)
+        } else {
+            None
+        };
+    }
+
+    let texts: Vec<_> = items
+        .iter()
+        .map(|item| strip_ansi_codes(item.as_ref()))
+        .collect();
+
+    texts.iter().position(|text| text == answer).or_else(|| {
+        let answer = answer.to_lowercase();
+        texts.iter().position(|text| text.to_lowercase() == answer)
+    })
+}
+
+/// Parses a comma separated list of item indices or item texts.
+pub(crate) fn parse_indices(answer: &str, items: &[String]) -> Option<Vec<usize>> {
+    let mut indices = vec![];
+
+    for part in answer
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let index = parse_index(part, items)?;
+
+        if !indices.contains(&index) {
+            indices.push(index);
+        }
+    }
+
+    Some(indices)
+}
+
+/// Answer lines for tests.
+#[cfg(test)]
+pub(crate) struct MockLines(pub std::cell::RefCell<Vec<&'static str>>);
+
+#[cfg(test)]
+impl MockLines {
+    pub fn new(lines: &[&'static str]) -> MockLines {
+        MockLines(std::cell::RefCell::new(
+            lines.iter().rev().cloned().collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+impl LineSource for MockLines {
+    fn read_line(&self) -> io::Result<Option<String>> {
+        Ok(self.0.borrow_mut().pop().map(String::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<String> {
+        vec![
+            "Apple".into(),
+            "Banana".into(),
+            "\u{1b}[1mCherry\u{1b}[0m".into(),
+        ]
+    }
+
+    #[test]
+    fn test_read_answer() {
+        let lines = MockLines::new(&["  yes ", "   "]);
+
+        assert_eq!(read_answer(&lines).unwrap(), Some("yes".into()));
+        assert_eq!(read_answer(&lines).unwrap(), None);
+        assert_eq!(read_answer(&lines).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        let (yes, no) = (['y', 'Y'], ['n', 'N']);
+
+        assert_eq!(parse_bool("y", &yes, &no), Some(true));
+        assert_eq!(parse_bool("YES", &yes, &no), Some(true));
+        assert_eq!(parse_bool("N", &yes, &no), Some(false));
+        assert_eq!(parse_bool("no", &yes, &no), Some(false));
+        assert_eq!(parse_bool("nope", &yes, &no), None);
+    }
+
+    #[test]
+    fn test_parse_index() {
+        let items = items();
+
+        assert_eq!(parse_index("1", &items), Some(1));
+        assert_eq!(parse_index("3", &items), None);
+        assert_eq!(parse_index("Apple", &items), Some(0));
+        assert_eq!(parse_index("banana", &items), Some(1));
+        assert_eq!(parse_index("Cherry", &items), Some(2));
+        assert_eq!(parse_index("Date", &items), None);
+    }
+
+    #[test]
+    fn test_parse_indices() {
+        let items = items();
+
+        assert_eq!(parse_indices("2, apple,0", &items), Some(vec![2, 0]));
+        assert_eq!(parse_indices("", &items), Some(vec![]));
+        assert_eq!(parse_indices("1,Date", &items), None);
+    }
+}
@@ -1,8 +1,43 @@
-use std::io;
+use std::{
+    io,
+    time::{Duration, Instant},
+};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::{
+    cursor::HiddenCursor,
+    error::{Error, Result},
+    keys::{KeySource, TermKeys},
+    piped::{self, LineSource},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+    timeout::timed_out_error,
+};
 
-use console::Term;
+use console::{Key, Term};
+
+/// How often the countdown of a confirm prompt with a timeout is redrawn.
+const COUNTDOWN_TICK: Duration = Duration::from_millis(250);
+
+/// The answer given to a confirm prompt.
+///
+/// Returned by [Confirm::interact_action](struct.Confirm.html#method.interact_action).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfirmAction {
+    /// The user confirmed.
+    Yes,
+    /// The user declined.
+    No,
+    /// The user cancelled the prompt with escape.
+    Cancel,
+}
+
+/// What a key press does in a confirm prompt with buttons.
+#[derive(Debug, PartialEq, Eq)]
+enum ButtonInput {
+    Highlight(bool),
+    Answer(bool),
+    Cancel,
+    Ignore,
+}
 
 /// Renders a confirm prompt.
 ///
@@ -24,7 +59,22 @@ pub struct Confirm<'a> {
     default: Option<bool>,
     show_default: bool,
     wait_for_newline: bool,
+    require_explicit: bool,
+    timeout: Option<Duration>,
+    require_text: Option<String>,
+    yes_keys: Vec<char>,
+    no_keys: Vec<char>,
+    yes_words: Vec<String>,
+    no_words: Vec<String>,
+    labels: Option<(String, String)>,
+    buttons: bool,
     theme: &'a dyn Theme,
+    indent: usize,
+    prompt_icon: Option<String>,
+    wizard_step: Option<(usize, usize)>,
+    term: Option<Term>,
+    prompt_suffix: Option<String>,
+    truncate_echo: bool,
 }
 
 impl<'a> Default for Confirm<'a> {
@@ -61,7 +111,22 @@ impl<'a> Confirm<'a> {
             default: None,
             show_default: true,
             wait_for_newline: false,
+            require_explicit: false,
+            timeout: None,
+            require_text: None,
+            yes_keys: vec!['y', 'Y'],
+            no_keys: vec!['n', 'N'],
+            yes_words: vec![],
+            no_words: vec![],
+            labels: None,
+            buttons: false,
             theme,
+            indent: 0,
+            prompt_icon: None,
+            wizard_step: None,
+            term: None,
+            prompt_suffix: None,
+            truncate_echo: false,
         }
     }
 
@@ -101,24 +166,218 @@ impl<'a> Confirm<'a> {
         self
     }
 
+    /// Enables or disables requiring an explicit answer.
+    ///
+    /// When enabled a bare enter doesn't accept the [default](#method.default),
+    /// the user has to press one of the answer keys or type an answer
+    /// instead. This is a safeguard for destructive actions. The default is
+    /// still shown and still picked when the [timeout](#method.timeout) runs
+    /// out, while a blank piped answer is an error. In button mode no
+    /// answer is highlighted initially.
+    ///
+    /// Explicit answers are not required by default.
+    pub fn require_explicit(&mut self, val: bool) -> &mut Confirm<'a> {
+        self.require_explicit = val;
+        self
+    }
+
+    /// Sets a timeout after which the default is picked.
+    ///
+    /// While waiting the prompt shows a countdown of the remaining seconds.
+    /// If no key is pressed in time the [default](#method.default) is used,
+    /// or an error of kind `TimedOut` is returned when there is none.
+    ///
+    /// Waiting for a key with a timeout is only supported on unix. Elsewhere
+    /// the countdown is shown once but doesn't tick, and the prompt waits
+    /// for a key as if there was no timeout.
+    pub fn timeout(&mut self, val: Duration) -> &mut Confirm<'a> {
+        self.timeout = Some(val);
+        self
+    }
+
+    /// Requires the user to type `expected` to confirm.
+    ///
+    /// Instead of reacting to single keys the prompt reads a full line and
+    /// only confirms if it matches `expected` exactly, which is useful as a
+    /// safeguard before destructive actions. Any other input shows an error
+    /// and asks again, while an empty line declines. The
+    /// [timeout](#method.timeout) does not apply in this mode.
+    pub fn require_text(&mut self, expected: &str) -> &mut Confirm<'a> {
+        self.require_text = Some(expected.into());
+        self
+    }
+
+    /// Sets the keys that confirm the prompt.
+    ///
+    /// The default is `'y'` and `'Y'`. This is useful for prompts in other
+    /// languages together with [labels](#method.labels).
+    pub fn yes_keys(&mut self, keys: &[char]) -> &mut Confirm<'a> {
+        self.yes_keys = keys.to_vec();
+        self
+    }
+
+    /// Sets the keys that decline the prompt.
+    ///
+    /// The default is `'n'` and `'N'`.
+    pub fn no_keys(&mut self, keys: &[char]) -> &mut Confirm<'a> {
+        self.no_keys = keys.to_vec();
+        self
+    }
+
+    /// Sets whole words that confirm the prompt.
+    ///
+    /// With words set the prompt reads a full line instead of a single key
+    /// and matches it against the words ignoring case. The
+    /// [answer keys](#method.yes_keys) as well as `yes` and `no` are still
+    /// understood. Unrecognized answers show an error and ask again, while
+    /// an empty line picks the [default](#method.default) if there is one.
+    /// The [timeout](#method.timeout) does not apply in this mode.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Confirm;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let proceed = Confirm::new()
+    ///     .with_prompt("¿Quieres continuar?")
+    ///     .yes_keys(&['s', 'S'])
+    ///     .yes_words(&["sí", "si"])
+    ///     .labels("sí", "no")
+    ///     .interact()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn yes_words(&mut self, words: &[&str]) -> &mut Confirm<'a> {
+        self.yes_words = words.iter().map(|word| word.to_lowercase()).collect();
+        self
+    }
+
+    /// Sets whole words that decline the prompt.
+    ///
+    /// See [yes_words](#method.yes_words).
+    pub fn no_words(&mut self, words: &[&str]) -> &mut Confirm<'a> {
+        self.no_words = words.iter().map(|word| word.to_lowercase()).collect();
+        self
+    }
+
+    /// Sets the labels shown for the answers.
+    ///
+    /// These replace the `y` and `n` in the `[y/n]` hint, with the label of
+    /// the default answer rendered in uppercase.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Confirm;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let proceed = Confirm::new()
+    ///     .with_prompt("Voulez-vous continuer ?")
+    ///     .yes_keys(&['o', 'O'])
+    ///     .labels("o", "n")
+    ///     .default(true)
+    ///     .interact()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn labels(&mut self, yes: &str, no: &str) -> &mut Confirm<'a> {
+        self.labels = Some((yes.into(), no.into()));
+        self
+    }
+
+    /// Renders the answers as buttons that can be chosen with the arrows.
+    ///
+    /// The left and right arrows or tab highlight an answer, which is
+    /// chosen with enter. The [default](#method.default) is highlighted
+    /// initially. The answer keys still answer right away, so both habits
+    /// work in the same prompt. [wait_for_newline](#method.wait_for_newline)
+    /// doesn't apply in this mode.
+    ///
+    /// Disabled by default.
+    pub fn buttons(&mut self, val: bool) -> &mut Confirm<'a> {
+        self.buttons = val;
+        self
+    }
+
     /// Disables or enables the default value display.
     ///
-    /// The default is to append `[y/n]` to the prompt to tell the
-    /// user which keys to press. This also renders the default choice
+    /// The default is to append `[y/n]`, or the configured
+    /// [labels](#method.labels), to the prompt to tell the user which keys
+    /// to press. This also renders the default choice
     /// in uppercase. The default is selected on enter.
     pub fn show_default(&mut self, val: bool) -> &mut Confirm<'a> {
         self.show_default = val;
         self
     }
 
+    /// Indents every rendered line by `val` spaces, see
+    /// [Select::indent](struct.Select.html#method.indent).
+    pub fn indent(&mut self, val: usize) -> &mut Confirm<'a> {
+        self.indent = val;
+        self
+    }
+
+    /// Sets an icon rendered in front of the prompt, see
+    /// [Select::prompt_icon](struct.Select.html#method.prompt_icon).
+    pub fn prompt_icon<S: Into<String>>(&mut self, val: S) -> &mut Confirm<'a> {
+        self.prompt_icon = Some(val.into());
+        self
+    }
+
+    /// Shows the position of the prompt in a sequence of prompts.
+    ///
+    /// The theme renders e.g. `Step 3/7` in front of the prompt and the
+    /// final answer, see
+    /// [Theme::format_step](../theme/trait.Theme.html#method.format_step).
+    pub fn wizard_step(&mut self, current: usize, total: usize) -> &mut Confirm<'a> {
+        self.wizard_step = Some((current, total));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    ///
+    /// The `interact` methods that don't take a terminal use this one
+    /// instead of stderr.
+    pub fn term(&mut self, term: Term) -> &mut Confirm<'a> {
+        self.term = Some(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    ///
+    /// This is the terminal set with [term](#method.term), or stderr.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.target_term().size()
+    }
+
+    /// Sets the separator between the prompt and the answer, e.g. `" › "`.
+    ///
+    /// This replaces the theme's own separator, including any spacing.
+    pub fn prompt_suffix<S: Into<String>>(&mut self, val: S) -> &mut Confirm<'a> {
+        self.prompt_suffix = Some(val.into());
+        self
+    }
+
+    /// Truncates the echoed answer to the width of the terminal.
+    ///
+    /// Only matters for very long prompts, which are cut off with an
+    /// ellipsis instead of wrapping.
+    ///
+    /// Disabled by default.
+    pub fn truncate_echo(&mut self, val: bool) -> &mut Confirm<'a> {
+        self.truncate_echo = val;
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// If the user confirms the result is `true`, `false` if declines or default (configured in [default](#method.default)) if pushes enter.
     /// Otherwise function discards input waiting for valid one.
     ///
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<bool> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact(&self) -> Result<bool> {
+        self.interact_on(&self.target_term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
@@ -136,39 +395,183 @@ impl<'a> Confirm<'a> {
     /// #   Ok(())
     /// # }
     /// ```
-    pub fn interact_on(&self, term: &Term) -> io::Result<bool> {
+    #[inline]
+    pub fn interact_on(&self, term: &Term) -> Result<bool> {
+        self._interact_on(term, false, &TermKeys)?
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// This method is similar to [interact_on_opt](#method.interact_on_opt) except for the fact that it does not allow selection of the terminal.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    /// Result contains `Some(answer)` if the user answered, possibly by accepting the default with enter, or `None` if user cancelled with 'Esc' or 'q'.
+    pub fn interact_opt(&self) -> Result<Option<bool>> {
+        self.interact_on_opt(&self.target_term())
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &Term) -> Result<Option<bool>> {
+        self._interact_on(term, true, &TermKeys)
+    }
+
+    /// Enables user interaction and returns the answer as an action.
+    ///
+    /// Unlike [interact](#method.interact) the user can also cancel the
+    /// prompt with escape or 'q', which results in [ConfirmAction::Cancel](enum.ConfirmAction.html).
+    /// This is useful for flows that branch three ways.
+    ///
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_action(&self) -> Result<ConfirmAction> {
+        self.interact_action_on(&self.target_term())
+    }
+
+    /// Like [interact_action](#method.interact_action) but allows a specific terminal to be set.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::{Confirm, ConfirmAction};
+    /// use console::Term;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// match Confirm::new()
+    ///     .with_prompt("Save changes?")
+    ///     .interact_action_on(&Term::stderr())?
+    /// {
+    ///     ConfirmAction::Yes => println!("Saving"),
+    ///     ConfirmAction::No => println!("Discarding"),
+    ///     ConfirmAction::Cancel => println!("Back to editing"),
+    /// }
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn interact_action_on(&self, term: &Term) -> Result<ConfirmAction> {
+        self.interact_action_with(term, &TermKeys)
+    }
+
+    fn interact_action_with(&self, term: &Term, keys: &dyn KeySource) -> Result<ConfirmAction> {
+        Ok(match self._interact_on(term, true, keys)? {
+            Some(true) => ConfirmAction::Yes,
+            Some(false) => ConfirmAction::No,
+            None => ConfirmAction::Cancel,
+        })
+    }
+
+    /// Returns the terminal set with [term](#method.term), or stderr.
+    fn target_term(&self) -> Term {
+        self.term.clone().unwrap_or_else(Term::stderr)
+    }
+
+    /// Like `interact_on` but allows the user to cancel with escape.
+    pub(crate) fn _interact_on(
+        &self,
+        term: &Term,
+        allow_quit: bool,
+        keys: &dyn KeySource,
+    ) -> Result<Option<bool>> {
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_prompt_suffix(self.prompt_suffix.as_deref());
+        render.set_truncate_echo(self.truncate_echo);
 
-        let default_if_show = if self.show_default {
+        if !keys.is_scripted() && self.timeout.is_none() && piped::is_piped(term) {
+            return self
+                .interact_piped(&mut render, term, &piped::Stdin)
+                .map(Some);
+        }
+
+        if let Some(ref expected) = self.require_text {
+            return self
+                .interact_text_match(&mut render, term, keys, expected)
+                .map(Some);
+        }
+
+        if !self.yes_words.is_empty() || !self.no_words.is_empty() {
+            return self.interact_words(&mut render, term, keys).map(Some);
+        }
+
+        let default_if_show = if self.show_default || self.buttons {
             self.default
         } else {
             None
         };
 
-        render.confirm_prompt(&self.prompt, default_if_show)?;
+        self.render_prompt(&mut render, default_if_show)?;
 
-        term.hide_cursor()?;
+        let _cursor = HiddenCursor::new(term)?;
         term.flush()?;
 
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
         let rv;
 
-        if self.wait_for_newline {
+        if self.buttons {
+            // Moves the highlight with the arrows while still answering
+            // right away on the answer keys.
+            let mut highlighted = if self.require_explicit {
+                None
+            } else {
+                default_if_show
+            };
+
+            loop {
+                let key = match self.read_key(&mut render, term, keys, deadline, highlighted)? {
+                    Some(key) => key,
+                    None => {
+                        rv = self.timed_out(term)?;
+                        break;
+                    }
+                };
+
+                match self.button_input(highlighted, &key, allow_quit) {
+                    ButtonInput::Highlight(val) => {
+                        highlighted = Some(val);
+                        term.clear_line()?;
+                        self.render_prompt(&mut render, highlighted)?;
+                        term.flush()?;
+                    }
+                    ButtonInput::Answer(val) => {
+                        rv = val;
+                        break;
+                    }
+                    ButtonInput::Cancel => return self.cancel(&mut render, term),
+                    ButtonInput::Ignore => {}
+                }
+            }
+        } else if self.wait_for_newline {
             // Waits for user input and for the user to hit the Enter key
             // before validation.
-            let mut value = default_if_show;
+            let mut value = if self.require_explicit {
+                None
+            } else {
+                default_if_show
+            };
 
             loop {
-                let input = term.read_char()?;
+                let input = match self.read_char(&mut render, term, keys, deadline, value)? {
+                    Some(input) => input,
+                    None => {
+                        rv = self.timed_out(term)?;
+                        break;
+                    }
+                };
 
                 match input {
-                    'y' | 'Y' => {
+                    c if self.yes_keys.contains(&c) => {
                         value = Some(true);
                     }
-                    'n' | 'N' => {
+                    c if self.no_keys.contains(&c) => {
                         value = Some(false);
                     }
+                    '\x1b' | 'q' | 'Q' if allow_quit => {
+                        return self.cancel(&mut render, term);
+                    }
                     '\n' | '\r' => {
-                        value = value.or(self.default);
+                        value = value.or(self.enter_default());
 
                         if let Some(val) = value {
                             rv = val;
@@ -183,17 +586,27 @@ impl<'a> Confirm<'a> {
                 };
 
                 term.clear_line()?;
-                render.confirm_prompt(&self.prompt, value)?;
+                self.render_prompt(&mut render, value)?;
             }
         } else {
             // Default behavior: matches continuously on every keystroke,
             // and does not wait for user to hit the Enter key.
             loop {
-                let input = term.read_char()?;
+                let input =
+                    match self.read_char(&mut render, term, keys, deadline, default_if_show)? {
+                        Some(input) => input,
+                        None => {
+                            rv = self.timed_out(term)?;
+                            break;
+                        }
+                    };
                 let value = match input {
-                    'y' | 'Y' => true,
-                    'n' | 'N' => false,
-                    '\n' | '\r' if self.default.is_some() => self.default.unwrap(),
+                    c if self.yes_keys.contains(&c) => true,
+                    c if self.no_keys.contains(&c) => false,
+                    '\n' | '\r' if self.enter_default().is_some() => self.default.unwrap(),
+                    '\x1b' | 'q' | 'Q' if allow_quit => {
+                        return self.cancel(&mut render, term);
+                    }
                     _ => {
                         continue;
                     }
@@ -206,9 +619,533 @@ impl<'a> Confirm<'a> {
 
         term.clear_line()?;
         render.confirm_prompt_selection(&self.prompt, rv)?;
-        term.show_cursor()?;
         term.flush()?;
 
-        return Ok(rv);
+        Ok(Some(rv))
+    }
+
+    /// Renders the prompt with the configured labels.
+    fn render_prompt(
+        &self,
+        render: &mut TermThemeRenderer,
+        default: Option<bool>,
+    ) -> io::Result<()> {
+        if self.buttons {
+            let labels = self
+                .labels
+                .as_ref()
+                .map(|(yes, no)| (yes.as_str(), no.as_str()));
+            return render.confirm_prompt_buttons(&self.prompt, default, labels);
+        }
+
+        match self.labels {
+            Some((ref yes, ref no)) => render.confirm_prompt_labels(&self.prompt, default, yes, no),
+            None => render.confirm_prompt(&self.prompt, default),
+        }
+    }
+
+    /// Reads lines until one is empty or matches `expected`.
+    fn interact_text_match(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        keys: &dyn KeySource,
+        expected: &str,
+    ) -> Result<bool> {
+        loop {
+            render.confirm_prompt_text(&self.prompt, expected)?;
+            term.flush()?;
+
+            let input = keys.read_line(term)?;
+
+            render.add_line();
+            term.clear_line()?;
+            render.clear()?;
+
+            let rv = if input == expected {
+                true
+            } else if input.is_empty() {
+                false
+            } else {
+                render.error(&format!("Type \"{}\" exactly to confirm", expected))?;
+                continue;
+            };
+
+            render.confirm_prompt_selection(&self.prompt, rv)?;
+            term.flush()?;
+
+            return Ok(rv);
+        }
+    }
+
+    /// Reads lines until one is a recognized answer or empty with a default.
+    fn interact_words(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        keys: &dyn KeySource,
+    ) -> Result<bool> {
+        let default_if_show = if self.show_default {
+            self.default
+        } else {
+            None
+        };
+
+        loop {
+            self.render_prompt(render, default_if_show)?;
+            term.flush()?;
+
+            let input = keys.read_line(term)?;
+            let input = input.trim();
+
+            render.add_line();
+            term.clear_line()?;
+            render.clear()?;
+
+            let rv = match (self.parse_answer(input), self.enter_default()) {
+                (Some(rv), _) => rv,
+                (None, Some(default)) if input.is_empty() => default,
+                (None, _) if input.is_empty() => continue,
+                (None, _) => {
+                    render.error(&format!("Unrecognized answer: {}", input))?;
+                    continue;
+                }
+            };
+
+            render.confirm_prompt_selection(&self.prompt, rv)?;
+            term.flush()?;
+
+            return Ok(rv);
+        }
+    }
+
+    /// Returns the answer a bare enter picks, if any.
+    fn enter_default(&self) -> Option<bool> {
+        if self.require_explicit {
+            None
+        } else {
+            self.default
+        }
+    }
+
+    /// Parses a typed answer, preferring the configured words.
+    fn parse_answer(&self, answer: &str) -> Option<bool> {
+        let word = answer.to_lowercase();
+
+        if self.yes_words.contains(&word) {
+            Some(true)
+        } else if self.no_words.contains(&word) {
+            Some(false)
+        } else {
+            piped::parse_bool(answer, &self.yes_keys, &self.no_keys)
+        }
+    }
+
+    /// Reads the answer as a line from `source`.
+    fn interact_piped(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        source: &dyn LineSource,
+    ) -> Result<bool> {
+        let answer = piped::read_answer(source)?;
+
+        let rv = match (answer, &self.require_text) {
+            (Some(answer), Some(expected)) if answer == *expected => true,
+            (Some(answer), Some(_)) => {
+                return Err(piped::invalid_answer_error(&format!(
+                    "Invalid confirmation: {}",
+                    answer
+                )))
+            }
+            (None, Some(_)) => false,
+            (Some(answer), None) => match self.parse_answer(&answer) {
+                Some(rv) => rv,
+                None => {
+                    return Err(piped::invalid_answer_error(&format!(
+                        "Invalid answer: {}",
+                        answer
+                    )))
+                }
+            },
+            (None, None) => self.enter_default().ok_or_else(piped::no_answer_error)?,
+        };
+
+        render.confirm_prompt_selection(&self.prompt, rv)?;
+        term.flush()?;
+
+        Ok(rv)
+    }
+
+    /// Marks the prompt as cancelled.
+    fn cancel(&self, render: &mut TermThemeRenderer, term: &Term) -> Result<Option<bool>> {
+        term.clear_line()?;
+        render.confirm_prompt_cancelled(&self.prompt)?;
+        term.flush()?;
+
+        Ok(None)
+    }
+
+    /// Returns what `key` does in button mode with `highlighted` answer.
+    fn button_input(&self, highlighted: Option<bool>, key: &Key, allow_quit: bool) -> ButtonInput {
+        match *key {
+            Key::Char(c) if self.yes_keys.contains(&c) => ButtonInput::Answer(true),
+            Key::Char(c) if self.no_keys.contains(&c) => ButtonInput::Answer(false),
+            Key::ArrowLeft => ButtonInput::Highlight(true),
+            Key::ArrowRight => ButtonInput::Highlight(false),
+            Key::Tab | Key::BackTab => ButtonInput::Highlight(!highlighted.unwrap_or(false)),
+            Key::Enter => highlighted.map_or(ButtonInput::Ignore, ButtonInput::Answer),
+            Key::Escape | Key::Char('q') | Key::Char('Q') if allow_quit => ButtonInput::Cancel,
+            _ => ButtonInput::Ignore,
+        }
+    }
+
+    /// Reads a character, redrawing the countdown until the deadline.
+    ///
+    /// Enter is read as a newline and escape as `'\x1b'`. Returns `None`
+    /// once the deadline has passed.
+    fn read_char(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        keys: &dyn KeySource,
+        deadline: Option<Instant>,
+        shown: Option<bool>,
+    ) -> Result<Option<char>> {
+        loop {
+            match self.read_key(render, term, keys, deadline, shown)? {
+                Some(Key::Char(c)) => return Ok(Some(c)),
+                Some(Key::Enter) => return Ok(Some('\n')),
+                Some(Key::Escape) => return Ok(Some('\x1b')),
+                Some(_) => {}
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Reads a key, redrawing the countdown until the deadline.
+    ///
+    /// Returns `None` once the deadline has passed.
+    fn read_key(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        keys: &dyn KeySource,
+        deadline: Option<Instant>,
+        shown: Option<bool>,
+    ) -> Result<Option<Key>> {
+        loop {
+            let key = match (deadline, self.timeout) {
+                (Some(deadline), Some(timeout)) => {
+                    let now = Instant::now();
+
+                    if now >= deadline {
+                        return Ok(None);
+                    }
+
+                    let remaining = deadline - now;
+                    let fraction = remaining.as_secs_f32() / timeout.as_secs_f32();
+
+                    term.clear_line()?;
+                    self.render_prompt(render, shown)?;
+                    render.countdown(remaining, fraction)?;
+                    term.flush()?;
+
+                    match keys.read_key(term, Some(remaining.min(COUNTDOWN_TICK)))? {
+                        Some(key) => key,
+                        None => continue,
+                    }
+                }
+                _ => match keys.read_key(term, None)? {
+                    Some(key) => key,
+                    None => return Ok(None),
+                },
+            };
+
+            return match key {
+                Key::Unknown => {
+                    Err(io::Error::new(io::ErrorKind::NotConnected, "Not a terminal").into())
+                }
+                key => Ok(Some(key)),
+            };
+        }
+    }
+
+    /// Resolves a prompt whose timeout ran out to the default.
+    fn timed_out(&self, term: &Term) -> Result<bool> {
+        match self.default {
+            Some(default) => Ok(default),
+            None => {
+                term.clear_line()?;
+                term.flush()?;
+
+                Err(timed_out_error())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::ScriptedKeys;
+
+    #[test]
+    fn test_term() {
+        let term = Term::buffered_stderr();
+        let mut confirm = Confirm::new();
+        assert!(confirm.term.is_none());
+
+        confirm.term(term.clone());
+        assert_eq!(confirm.term_size(), term.size());
+        assert_eq!(confirm.target_term().target(), term.target());
+    }
+
+    #[test]
+    fn test_piped() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        let lines = piped::MockLines::new(&["yes", "n", "", "maybe"]);
+
+        let mut confirm = Confirm::new();
+        assert!(confirm.interact_piped(&mut render, &term, &lines).unwrap());
+        assert!(!confirm.interact_piped(&mut render, &term, &lines).unwrap());
+
+        confirm.default(true);
+        assert!(confirm.interact_piped(&mut render, &term, &lines).unwrap());
+
+        let err = confirm
+            .interact_piped(&mut render, &term, &lines)
+            .unwrap_err();
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_require_explicit() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        let lines = piped::MockLines::new(&["", "y"]);
+
+        let mut confirm = Confirm::new();
+        confirm.default(false).require_explicit(true);
+        assert_eq!(confirm.enter_default(), None);
+
+        let err = confirm
+            .interact_piped(&mut render, &term, &lines)
+            .unwrap_err();
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::UnexpectedEof);
+        assert!(confirm.interact_piped(&mut render, &term, &lines).unwrap());
+    }
+
+    #[test]
+    fn test_keys_require_explicit() {
+        let term = Term::buffered_stderr();
+        let mut confirm = Confirm::new();
+        confirm.default(true).require_explicit(true);
+
+        let run = |confirm: &Confirm, keys: &[Key]| {
+            confirm
+                ._interact_on(&term, false, &ScriptedKeys::new(keys))
+                .unwrap()
+        };
+
+        assert_eq!(run(&confirm, &[Key::Enter, Key::Char('n')]), Some(false));
+
+        confirm.wait_for_newline(true);
+        let keys = [Key::Enter, Key::Char('n'), Key::Enter];
+        assert_eq!(run(&confirm, &keys), Some(false));
+
+        confirm.wait_for_newline(false).buttons(true);
+        let keys = [Key::Enter, Key::ArrowRight, Key::Enter];
+        assert_eq!(run(&confirm, &keys), Some(false));
+
+        confirm.require_explicit(false);
+        assert_eq!(run(&confirm, &[Key::Enter]), Some(true));
+    }
+
+    #[test]
+    fn test_keys_button_labels() {
+        let term = Term::buffered_stderr();
+        let mut confirm = Confirm::new();
+        confirm
+            .with_prompt("Continuer ?")
+            .yes_keys(&['o'])
+            .labels("oui", "non")
+            .default(true)
+            .buttons(true);
+
+        let keys = ScriptedKeys::new(&[Key::Tab, Key::Enter]);
+        let (rv, output) = crate::capture_output(|| confirm._interact_on(&term, false, &keys));
+        assert_eq!(rv.unwrap(), Some(false));
+        assert!(output.contains("[oui]  non "));
+        assert!(output.contains(" oui  [non]"));
+        assert!(!output.contains("yes"));
+    }
+
+    #[test]
+    fn test_words() {
+        let mut confirm = Confirm::new();
+        confirm
+            .yes_keys(&['o', 'O'])
+            .yes_words(&["Oui"])
+            .no_words(&["non"]);
+
+        assert_eq!(confirm.parse_answer("OUI"), Some(true));
+        assert_eq!(confirm.parse_answer("o"), Some(true));
+        assert_eq!(confirm.parse_answer("Non"), Some(false));
+        assert_eq!(confirm.parse_answer("peut-être"), None);
+
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        let lines = piped::MockLines::new(&["oui"]);
+        assert!(confirm.interact_piped(&mut render, &term, &lines).unwrap());
+    }
+
+    #[test]
+    fn test_timeout_resolves_to_default() {
+        let rv = Confirm::new()
+            .default(true)
+            .timeout(Duration::from_millis(10))
+            .interact_on(&Term::buffered_stderr())
+            .unwrap();
+
+        assert!(rv);
+    }
+
+    #[test]
+    fn test_timeout_without_default() {
+        let err = Confirm::new()
+            .timeout(Duration::from_millis(10))
+            .interact_on(&Term::buffered_stderr())
+            .unwrap_err();
+
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_timeout_action() {
+        let term = Term::buffered_stderr();
+        let mut confirm = Confirm::new();
+        confirm.timeout(Duration::from_millis(10));
+
+        confirm.default(true);
+        assert_eq!(
+            confirm.interact_action_on(&term).unwrap(),
+            ConfirmAction::Yes
+        );
+
+        confirm.default(false);
+        assert_eq!(
+            confirm.interact_action_on(&term).unwrap(),
+            ConfirmAction::No
+        );
+    }
+
+    #[test]
+    fn test_keys_prompt_suffix() {
+        let term = Term::buffered_stderr();
+        let mut confirm = Confirm::new();
+        confirm
+            .with_prompt("Continue")
+            .default(true)
+            .prompt_suffix(": ");
+
+        let keys = ScriptedKeys::new(&[Key::Char('n')]);
+        let (rv, output) = crate::capture_output(|| confirm._interact_on(&term, false, &keys));
+        assert_eq!(rv.unwrap(), Some(false));
+        assert!(output.starts_with("Continue [Y/n]: "));
+    }
+
+    #[test]
+    fn test_keys_require_text() {
+        let term = Term::buffered_stderr();
+        let mut confirm = Confirm::new();
+        confirm
+            .with_prompt("Delete the repository?")
+            .require_text("acme/api");
+
+        let run = |text: &str| {
+            let mut keys: Vec<Key> = text.chars().map(Key::Char).collect();
+            keys.push(Key::Enter);
+            keys
+        };
+
+        let mut keys = run("acme");
+        keys.extend(run("acme/api"));
+        let (rv, output) =
+            crate::capture_output(|| confirm._interact_on(&term, false, &ScriptedKeys::new(&keys)));
+        assert_eq!(rv.unwrap(), Some(true));
+        assert!(output.contains("Type \"acme/api\" exactly to confirm"));
+
+        let keys = ScriptedKeys::new(&run(""));
+        assert_eq!(
+            confirm._interact_on(&term, false, &keys).unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_keys_action() {
+        let term = Term::buffered_stderr();
+        let mut confirm = Confirm::new();
+        confirm.with_prompt("Save changes?");
+
+        let run = |confirm: &Confirm, keys: &[Key]| {
+            confirm
+                .interact_action_with(&term, &ScriptedKeys::new(keys))
+                .unwrap()
+        };
+
+        assert_eq!(run(&confirm, &[Key::Char('y')]), ConfirmAction::Yes);
+        assert_eq!(run(&confirm, &[Key::Char('n')]), ConfirmAction::No);
+        assert_eq!(run(&confirm, &[Key::Escape]), ConfirmAction::Cancel);
+        assert_eq!(run(&confirm, &[Key::Char('q')]), ConfirmAction::Cancel);
+
+        confirm.wait_for_newline(true);
+        let keys = [Key::Char('y'), Key::Escape];
+        assert_eq!(run(&confirm, &keys), ConfirmAction::Cancel);
+    }
+
+    #[test]
+    fn test_button_input() {
+        let mut confirm = Confirm::new();
+        confirm.buttons(true);
+
+        // Answering with a letter.
+        assert_eq!(
+            confirm.button_input(None, &Key::Char('y'), false),
+            ButtonInput::Answer(true)
+        );
+        assert_eq!(
+            confirm.button_input(Some(true), &Key::Char('n'), false),
+            ButtonInput::Answer(false)
+        );
+
+        // Answering with the arrows and enter.
+        assert_eq!(
+            confirm.button_input(None, &Key::Enter, false),
+            ButtonInput::Ignore
+        );
+        assert_eq!(
+            confirm.button_input(Some(true), &Key::ArrowRight, false),
+            ButtonInput::Highlight(false)
+        );
+        assert_eq!(
+            confirm.button_input(Some(false), &Key::Tab, false),
+            ButtonInput::Highlight(true)
+        );
+        assert_eq!(
+            confirm.button_input(Some(false), &Key::Enter, false),
+            ButtonInput::Answer(false)
+        );
+
+        assert_eq!(
+            confirm.button_input(Some(false), &Key::Escape, false),
+            ButtonInput::Ignore
+        );
+        assert_eq!(
+            confirm.button_input(Some(false), &Key::Escape, true),
+            ButtonInput::Cancel
+        );
     }
 }
@@ -1,16 +1,41 @@
 use std::{
+    cell::RefCell,
     fmt::{Debug, Display},
     io, iter,
     str::FromStr,
+    time::Duration,
 };
 
+#[cfg(feature = "clipboard")]
+use crate::clipboard::{self, SystemClipboard};
 use crate::{
+    completion::Completion,
+    error::Result,
+    history::History,
+    keys::{KeySource, TermKeys},
+    piped::{self, LineSource},
     theme::{SimpleTheme, TermThemeRenderer, Theme},
-    validate::Validator,
+    timeout::timed_out_error,
+    validate::{NotIn, Validator},
 };
 
 use console::{Key, Term};
 
+type ValidatorCallback<'a, T> = Box<dyn Fn(&T) -> Option<String> + 'a>;
+type PostProcessingCallback<'a, T> = Box<dyn Fn(T) -> T + 'a>;
+
+/// Where the value returned by an input prompt came from.
+///
+/// Returned by [Input::interact_with_source](struct.Input.html#method.interact_with_source).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// The user entered the value, including initial text they accepted
+    /// and answers piped in.
+    Typed,
+    /// The default was used because the input was empty or timed out.
+    Default,
+}
+
 /// Renders an input prompt.
 ///
 /// ## Example usage
@@ -43,8 +68,29 @@ pub struct Input<'a, T> {
     show_default: bool,
     initial_text: Option<String>,
     theme: &'a dyn Theme,
+    indent: usize,
+    prompt_icon: Option<String>,
+    wizard_step: Option<(usize, usize)>,
+    term: Option<Term>,
+    prompt_suffix: Option<String>,
+    truncate_echo: bool,
     permit_empty: bool,
-    validator: Option<Box<dyn Fn(&T) -> Option<String> + 'a>>,
+    empty_error_message: Option<String>,
+    trim: bool,
+    mask: Option<char>,
+    allowed_chars: Option<String>,
+    forbidden_chars: Option<String>,
+    live_validate: bool,
+    max_length: Option<usize>,
+    show_length: bool,
+    paste_safe: bool,
+    validator: Option<ValidatorCallback<'a, T>>,
+    post_processing: Option<PostProcessingCallback<'a, T>>,
+    history: Option<RefCell<&'a mut dyn History>>,
+    completion: Option<&'a dyn Completion>,
+    timeout: Option<Duration>,
+    #[cfg(feature = "clipboard")]
+    paste_key: Option<Key>,
 }
 
 impl<'a, T> Default for Input<'a, T>
@@ -75,8 +121,29 @@ where
             show_default: true,
             initial_text: None,
             theme,
+            indent: 0,
+            prompt_icon: None,
+            wizard_step: None,
+            term: None,
+            prompt_suffix: None,
+            truncate_echo: false,
             permit_empty: false,
+            empty_error_message: None,
+            trim: false,
+            mask: None,
+            allowed_chars: None,
+            forbidden_chars: None,
+            live_validate: false,
+            max_length: None,
+            show_length: false,
+            paste_safe: false,
             validator: None,
+            post_processing: None,
+            history: None,
+            completion: None,
+            timeout: None,
+            #[cfg(feature = "clipboard")]
+            paste_key: None,
         }
     }
 
@@ -87,6 +154,11 @@ where
     }
 
     /// Sets initial text that user can accept or erase.
+    ///
+    /// The text prefills the editable line, so hitting enter right away
+    /// submits it instead of the [default](#method.default). The default is
+    /// only used if the line is empty on enter, e.g. because the initial
+    /// text was erased completely.
     pub fn with_initial_text<S: Into<String>>(&mut self, val: S) -> &mut Input<'a, T> {
         self.initial_text = Some(val.into());
         self
@@ -96,12 +168,29 @@ where
     ///
     /// Out of the box the prompt does not have a default and will continue
     /// to display until the user inputs something and hits enter. If a default is set the user
-    /// can instead accept the default with enter.
+    /// can instead accept the default with enter. With
+    /// [initial text](#method.with_initial_text) the line has to be erased
+    /// first, as the initial text takes precedence.
     pub fn default(&mut self, value: T) -> &mut Input<'a, T> {
         self.default = Some(value);
         self
     }
 
+    /// Sets a timeout after which the default is picked.
+    ///
+    /// The timeout restarts with every key press. If no key is pressed in
+    /// time the input typed so far is discarded and the
+    /// [default](#method.default) is used, or an error of kind `TimedOut`
+    /// is returned when there is none.
+    ///
+    /// Setting a timeout makes [interact](#method.interact) read the input
+    /// like [interact_text](#method.interact_text). It does not apply to
+    /// [interact_lines](#method.interact_lines).
+    pub fn timeout(&mut self, val: Duration) -> &mut Input<'a, T> {
+        self.timeout = Some(val);
+        self
+    }
+
     /// Enables or disables an empty input
     ///
     /// By default, if there is no default value set for the input, the user must input a non-empty string.
@@ -110,6 +199,137 @@ where
         self
     }
 
+    /// Sets the error shown when an empty input isn't allowed.
+    ///
+    /// By default, submitting an empty input without a default value just
+    /// keeps prompting without any message.
+    pub fn empty_error_message<S: Into<String>>(&mut self, val: S) -> &mut Input<'a, T> {
+        self.empty_error_message = Some(val.into());
+        self
+    }
+
+    /// Enables or disables trimming of the input.
+    ///
+    /// When enabled leading and trailing whitespace is removed before the
+    /// input is checked for being empty and parsed, and the trimmed value
+    /// is echoed.
+    ///
+    /// Trimming is disabled by default.
+    pub fn trim(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.trim = val;
+        self
+    }
+
+    /// Echoes every typed character as `mask`.
+    ///
+    /// Unlike a [Password](struct.Password.html) prompt the input keeps the
+    /// whole feature set of this prompt, e.g. defaults, validation and
+    /// parsing, while only the echo is masked. This also applies to the
+    /// final echo. With a mask [interact](#method.interact) reads the input
+    /// key by key like [interact_text](#method.interact_text).
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Input;
+    ///
+    /// let pin: u32 = Input::new()
+    ///     .with_prompt("PIN")
+    ///     .masked('*')
+    ///     .interact_text()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn masked(&mut self, mask: char) -> &mut Input<'a, T> {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Only accepts input made up of these characters.
+    ///
+    /// Input containing any other character is rejected with an error
+    /// naming it before the input is parsed.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Input;
+    ///
+    /// let pin: String = Input::new()
+    ///     .with_prompt("PIN")
+    ///     .allowed_chars("0123456789")
+    ///     .interact_text()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn allowed_chars(&mut self, chars: &str) -> &mut Input<'a, T> {
+        self.allowed_chars = Some(chars.into());
+        self
+    }
+
+    /// Rejects input containing any of these characters.
+    ///
+    /// The error names the offending character and is shown before the
+    /// input is parsed.
+    pub fn forbidden_chars(&mut self, chars: &str) -> &mut Input<'a, T> {
+        self.forbidden_chars = Some(chars.into());
+        self
+    }
+
+    /// Enables or disables validation while typing.
+    ///
+    /// When enabled the input is parsed and validated on every keystroke
+    /// and a status is shown after it, so the user gets feedback before
+    /// hitting enter. Submitting is still gated by the same validation.
+    /// This always reads the input by keystroke, like
+    /// [interact_text](#method.interact_text) does.
+    pub fn live_validate(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.live_validate = val;
+        self
+    }
+
+    /// Limits the input to `val` characters.
+    ///
+    /// Keystrokes beyond the limit are ignored and pasted or completed text
+    /// is cut off at it. Input that didn't come from the keyboard, e.g. piped
+    /// in, is rejected with an error if it's too long. This always reads the
+    /// input by keystroke, like [interact_text](#method.interact_text) does.
+    pub fn max_length(&mut self, val: usize) -> &mut Input<'a, T> {
+        self.max_length = Some(val);
+        self
+    }
+
+    /// Enables or disables a counter of the typed characters.
+    ///
+    /// The counter is shown after the input while typing and compares the
+    /// length to [max_length](#method.max_length), without which it has no
+    /// effect. The counter is disabled by default.
+    pub fn show_length(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.show_length = val;
+        self
+    }
+
+    /// Enables or disables flattening pasted newlines.
+    ///
+    /// Pasting text that spans multiple lines would otherwise submit the
+    /// input at the first newline. With this enabled a newline that is
+    /// immediately followed by more input is taken as part of a paste and
+    /// inserted as a space instead. A newline at the very end of the pasted
+    /// text still submits, as it can't be told apart from pressing enter.
+    /// This always reads the input by keystroke, like
+    /// [interact_text](#method.interact_text) does.
+    ///
+    /// Pastes are only detected on unix, elsewhere every newline submits.
+    /// [Password](struct.Password.html) has no paste handling either.
+    ///
+    /// Paste safety is disabled by default.
+    pub fn paste_safe(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.paste_safe = val;
+        self
+    }
+
     /// Disables or enables the default value display.
     ///
     /// The default behaviour is to append [`default`] to the prompt to tell the
@@ -121,6 +341,183 @@ where
         self
     }
 
+    /// Indents every rendered line by `val` spaces, see
+    /// [Select::indent](struct.Select.html#method.indent).
+    pub fn indent(&mut self, val: usize) -> &mut Input<'a, T> {
+        self.indent = val;
+        self
+    }
+
+    /// Sets an icon rendered in front of the prompt, see
+    /// [Select::prompt_icon](struct.Select.html#method.prompt_icon).
+    pub fn prompt_icon<S: Into<String>>(&mut self, val: S) -> &mut Input<'a, T> {
+        self.prompt_icon = Some(val.into());
+        self
+    }
+
+    /// Shows the position of the prompt in a sequence of prompts.
+    ///
+    /// The theme renders e.g. `Step 3/7` in front of the prompt and the
+    /// final answer, see
+    /// [Theme::format_step](../theme/trait.Theme.html#method.format_step).
+    pub fn wizard_step(&mut self, current: usize, total: usize) -> &mut Input<'a, T> {
+        self.wizard_step = Some((current, total));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    ///
+    /// The `interact` methods that don't take a terminal use this one
+    /// instead of stderr.
+    pub fn term(&mut self, term: Term) -> &mut Input<'a, T> {
+        self.term = Some(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    ///
+    /// This is the terminal set with [term](#method.term), or stderr.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.target_term().size()
+    }
+
+    /// Sets the separator between the prompt and the input, e.g. `" › "`.
+    ///
+    /// This replaces the theme's own separator, including any spacing.
+    pub fn prompt_suffix<S: Into<String>>(&mut self, val: S) -> &mut Input<'a, T> {
+        self.prompt_suffix = Some(val.into());
+        self
+    }
+
+    /// Truncates the echoed value to the width of the terminal.
+    ///
+    /// Long values are cut off with an ellipsis instead of wrapping onto
+    /// the following lines.
+    ///
+    /// Disabled by default.
+    pub fn truncate_echo(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.truncate_echo = val;
+        self
+    }
+
+    /// Inserts the contents of the clipboard when `key` is pressed.
+    ///
+    /// This only applies to [interact_text](#method.interact_text). Newlines
+    /// in the pasted text are stripped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::{console::Key, Input};
+    ///
+    /// let token: String = Input::new()
+    ///     .with_prompt("API token")
+    ///     .enable_paste_key(Key::Char('\x16'))
+    ///     .interact_text()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "clipboard")]
+    pub fn enable_paste_key(&mut self, key: Key) -> &mut Input<'a, T> {
+        self.paste_key = Some(key);
+        self
+    }
+
+    /// Records submitted values in a history.
+    ///
+    /// Every value the user submits, including an accepted default, is
+    /// written to the history. While typing the up and down arrows recall
+    /// earlier entries, replacing the current line. Moving down past the
+    /// most recent entry restores the line that was being typed.
+    ///
+    /// With a history [interact](#method.interact) reads the input key by
+    /// key like [interact_text](#method.interact_text).
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Input;
+    ///
+    /// let mut history = Vec::new();
+    /// let command: String = Input::new()
+    ///     .with_prompt("Command")
+    ///     .history_with(&mut history)
+    ///     .interact_text()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn history_with<H: History>(&mut self, history: &'a mut H) -> &mut Input<'a, T> {
+        self.history = Some(RefCell::new(history));
+        self
+    }
+
+    /// Completes the input with `completion` when tab is pressed.
+    ///
+    /// The completion replaces the input typed so far. It only changes what
+    /// is being edited, so on enter the input is still parsed and checked
+    /// by the validator registered with [validate_with](#method.validate_with).
+    /// The [default](#method.default) is used if the input is empty on
+    /// enter, which is no longer the case once a completion was inserted.
+    ///
+    /// With a completion [interact](#method.interact) reads the input key by
+    /// key like [interact_text](#method.interact_text).
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Input;
+    ///
+    /// let commands = ["build", "bench", "check", "test"];
+    /// let complete = |input: &str| {
+    ///     commands
+    ///         .iter()
+    ///         .find(|command| command.starts_with(input))
+    ///         .map(|command| command.to_string())
+    /// };
+    ///
+    /// let command: String = Input::new()
+    ///     .with_prompt("Command")
+    ///     .completion_with(&complete)
+    ///     .interact_text()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn completion_with<C: Completion>(&mut self, completion: &'a C) -> &mut Input<'a, T> {
+        self.completion = Some(completion);
+        self
+    }
+
+    /// Transforms the value before it is returned.
+    ///
+    /// `f` is applied to every parsed value after it passed validation, so
+    /// validators still see the input as typed. The echoed value is the
+    /// transformed one. An accepted [default](#method.default) is returned
+    /// as is.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Input;
+    ///
+    /// let email: String = Input::new()
+    ///     .with_prompt("Email")
+    ///     .with_post_processing(|email: String| email.trim().to_lowercase())
+    ///     .interact_text()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_post_processing<F>(&mut self, f: F) -> &mut Input<'a, T>
+    where
+        F: Fn(T) -> T + 'a,
+    {
+        self.post_processing = Some(Box::new(f));
+        self
+    }
+
     /// Registers a validator.
     ///
     /// # Example
@@ -162,19 +559,117 @@ where
         self
     }
 
+    /// Rejects values that are already in use.
+    ///
+    /// The user is prompted again when the input matches any of the
+    /// `existing` values. This is a shorthand for validating with a
+    /// case-sensitive [NotIn](struct.NotIn.html) validator, use that one
+    /// directly to ignore case.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Input;
+    ///
+    /// let name: String = Input::new()
+    ///     .with_prompt("Project name")
+    ///     .not_in(&["api", "web"])
+    ///     .interact_text()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn not_in<S: AsRef<str>>(&mut self, existing: &[S]) -> &mut Input<'a, T>
+    where
+        T: 'a,
+    {
+        self.validate_with(NotIn::new(existing))
+    }
+
+    /// Rejects values smaller than `min`.
+    ///
+    /// The user is prompted again with an error naming the minimum. This
+    /// is a shorthand for a [validator](#method.validate_with) and runs
+    /// after the ones registered before it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Input;
+    ///
+    /// let percentage: u32 = Input::new()
+    ///     .with_prompt("Percentage")
+    ///     .min(1)
+    ///     .max(100)
+    ///     .interact_text()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn min(&mut self, min: T) -> &mut Input<'a, T>
+    where
+        T: PartialOrd + 'a,
+    {
+        self.validate_with(move |value: &T| {
+            if *value < min {
+                Err(format!("Value must be at least {}", min))
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Rejects values greater than `max`.
+    ///
+    /// The user is prompted again with an error naming the maximum, see
+    /// [min](#method.min).
+    pub fn max(&mut self, max: T) -> &mut Input<'a, T>
+    where
+        T: PartialOrd + 'a,
+    {
+        self.validate_with(move |value: &T| {
+            if *value > max {
+                Err(format!("Value must be at most {}", max))
+            } else {
+                Ok(())
+            }
+        })
+    }
+
     /// Enables the user to enter a printable ascii sequence and returns the result.
     ///
     /// Its difference from [`interact`](#method.interact) is that it only allows ascii characters for string,
     /// while [`interact`](#method.interact) allows virtually any character to be used e.g arrow keys.
     ///
-    /// The dialog is rendered on stderr.
-    pub fn interact_text(&self) -> io::Result<T> {
-        self.interact_text_on(&Term::stderr())
+    /// The line can be edited with the usual readline keys: the left and
+    /// right arrows move the cursor, home or `Ctrl-A` and end or `Ctrl-E`
+    /// jump to the start and the end, backspace and delete remove the
+    /// character before and under the cursor, `Ctrl-W` removes the word
+    /// before the cursor and `Ctrl-U` everything before the cursor.
+    ///
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_text(&self) -> Result<T> {
+        self.interact_text_on(&self.target_term())
     }
 
     /// Like [`interact_text`](#method.interact_text) but allows a specific terminal to be set.
-    pub fn interact_text_on(&self, term: &Term) -> io::Result<T> {
+    pub fn interact_text_on(&self, term: &Term) -> Result<T> {
+        self.interact_text_with(term, &TermKeys)
+            .map(|(value, _)| value)
+    }
+
+    /// Like [`interact_text_on`](#method.interact_text_on) but reads the keys from `keys`.
+    fn interact_text_with(&self, term: &Term, keys: &dyn KeySource) -> Result<(T, InputSource)> {
+        if !keys.is_scripted() && self.timeout.is_none() && piped::is_piped(term) {
+            return self.interact_piped(term, &piped::Stdin);
+        }
+
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_prompt_suffix(self.prompt_suffix.as_deref());
+        render.set_truncate_echo(self.truncate_echo);
 
         loop {
             let default_string = self.default.as_ref().map(|x| x.to_string());
@@ -190,21 +685,61 @@ where
             term.flush()?;
 
             // Read input by keystroke so that we can suppress ascii control characters
-            if !term.features().is_attended() {
-                return Ok("".to_owned().parse::<T>().unwrap());
+            if !keys.is_scripted() && !term.features().is_attended() && self.timeout.is_none() {
+                return Ok(("".to_owned().parse::<T>().unwrap(), InputSource::Typed));
             }
 
             let mut chars: Vec<char> = Vec::new();
             let mut position = 0;
 
             if let Some(initial) = self.initial_text.as_ref() {
-                term.write_str(initial)?;
-                chars = initial.chars().collect();
+                chars = self.fit_length(initial.chars().collect());
                 position = chars.len();
+                term.write_str(&self.echo_text(&chars.iter().collect::<String>()))?;
+            }
+
+            if self.shows_live_status() {
+                self.render_live_status(&mut render, term, &chars, position)?;
             }
 
+            // Position of the recalled history entry and the line that was
+            // being typed before recalling it.
+            let mut history_pos = None;
+            let mut draft = vec![];
+
             loop {
-                match term.read_key()? {
+                let key = match keys.read_key(term, self.timeout)? {
+                    Some(Key::Enter) if self.paste_safe && keys.has_pending_key(term)? => {
+                        Key::Char(' ')
+                    }
+                    Some(key) => key,
+                    None => return self.timed_out(&mut render, term),
+                };
+
+                match key {
+                    #[cfg(feature = "clipboard")]
+                    key if Some(&key) == self.paste_key.as_ref() => {
+                        let mut count = clipboard::paste(&SystemClipboard, &mut chars, position);
+                        let excess = self
+                            .max_length
+                            .map_or(0, |max| chars.len().saturating_sub(max));
+                        chars.drain(position + count - excess..position + count);
+                        count -= excess;
+                        let tail: String = chars[position..].iter().collect();
+                        position += count;
+
+                        term.write_str(&self.echo_text(&tail))?;
+
+                        if position < chars.len() {
+                            term.move_cursor_left(chars.len() - position)?;
+                        }
+
+                        if self.shows_live_status() {
+                            self.render_live_status(&mut render, term, &chars, position)?;
+                        }
+
+                        term.flush()?;
+                    }
                     Key::Backspace if position > 0 => {
                         position -= 1;
                         chars.remove(position);
@@ -213,21 +748,49 @@ where
                         let tail: String = chars[position..].iter().collect();
 
                         if !tail.is_empty() {
-                            term.write_str(&tail)?;
-                            term.move_cursor_left(tail.len())?;
+                            term.write_str(&self.echo_text(&tail))?;
+                            term.move_cursor_left(tail.chars().count())?;
+                        }
+
+                        if self.shows_live_status() {
+                            self.render_live_status(&mut render, term, &chars, position)?;
                         }
 
                         term.flush()?;
                     }
-                    Key::Char(chr) if !chr.is_ascii_control() => {
+                    Key::Char(chr)
+                        if !chr.is_ascii_control()
+                            && self.max_length.map_or(true, |max| chars.len() < max) =>
+                    {
                         chars.insert(position, chr);
                         position += 1;
                         let tail: String =
                             iter::once(&chr).chain(chars[position..].iter()).collect();
-                        term.write_str(&tail)?;
-                        term.move_cursor_left(tail.len() - 1)?;
+                        term.write_str(&self.echo_text(&tail))?;
+                        term.move_cursor_left(tail.chars().count() - 1)?;
+
+                        if self.shows_live_status() {
+                            self.render_live_status(&mut render, term, &chars, position)?;
+                        }
+
                         term.flush()?;
                     }
+                    Key::Del if position < chars.len() => {
+                        let mut line = chars.clone();
+                        line.remove(position);
+                        let cursor = position;
+                        self.edit_line(&mut render, term, &mut chars, &mut position, line, cursor)?;
+                    }
+                    Key::Char('\u{17}') if position > 0 => {
+                        let start = previous_word_start(&chars, position);
+                        let mut line = chars.clone();
+                        line.drain(start..position);
+                        self.edit_line(&mut render, term, &mut chars, &mut position, line, start)?;
+                    }
+                    Key::Char('\u{15}') if position > 0 => {
+                        let line = chars[position..].to_vec();
+                        self.edit_line(&mut render, term, &mut chars, &mut position, line, 0)?;
+                    }
                     Key::ArrowLeft if position > 0 => {
                         term.move_cursor_left(1)?;
                         position -= 1;
@@ -238,47 +801,94 @@ where
                         position += 1;
                         term.flush()?;
                     }
+                    Key::Home | Key::Char('\u{1}') => {
+                        term.move_cursor_left(position)?;
+                        position = 0;
+                        term.flush()?;
+                    }
+                    Key::End | Key::Char('\u{5}') => {
+                        term.move_cursor_right(chars.len() - position)?;
+                        position = chars.len();
+                        term.flush()?;
+                    }
+                    Key::ArrowUp | Key::ArrowDown if self.history.is_some() => {
+                        let up = key == Key::ArrowUp;
+
+                        if let Some((pos, entry)) = self.history_step(history_pos, up) {
+                            if history_pos.is_none() {
+                                draft = chars.clone();
+                            }
+
+                            history_pos = pos;
+                            let line = entry.map_or_else(|| draft.clone(), |e| e.chars().collect());
+                            self.replace_line(&mut render, term, &mut chars, &mut position, line)?;
+                        }
+                    }
+                    Key::Tab if self.completion.is_some() => {
+                        let input: String = chars.iter().collect();
+
+                        if let Some(completed) = self.completion.and_then(|c| c.get(&input)) {
+                            let line = completed.chars().collect();
+                            self.replace_line(&mut render, term, &mut chars, &mut position, line)?;
+                        }
+                    }
                     Key::Enter => break,
                     Key::Unknown => {
-                        return Err(io::Error::new(
-                            io::ErrorKind::NotConnected,
-                            "Not a terminal",
-                        ))
+                        return Err(
+                            io::Error::new(io::ErrorKind::NotConnected, "Not a terminal").into(),
+                        )
                     }
                     _ => (),
                 }
             }
-            let input = chars.iter().collect::<String>();
+            let input = self
+                .trim_input(&chars.iter().collect::<String>())
+                .to_string();
 
             term.clear_line()?;
             render.clear()?;
 
-            if chars.is_empty() {
+            if input.is_empty() {
                 if let Some(ref default) = self.default {
-                    render.input_prompt_selection(&self.prompt, &default.to_string())?;
+                    let default_string = default.to_string();
+                    render
+                        .input_prompt_selection(&self.prompt, &self.echo_text(&default_string))?;
                     term.flush()?;
-                    return Ok(default.clone());
+                    self.write_history(&default_string);
+                    return Ok((default.clone(), InputSource::Default));
                 } else if !self.permit_empty {
+                    self.render_empty_error(&mut render)?;
                     continue;
                 }
             }
 
+            if let Some(err) = self.char_error(&input) {
+                render.validation_error(&err)?;
+                continue;
+            }
+
             match input.parse::<T>() {
                 Ok(value) => {
                     if let Some(ref validator) = self.validator {
                         if let Some(err) = validator(&value) {
-                            render.error(&err)?;
+                            render.validation_error(&err)?;
                             continue;
                         }
                     }
 
-                    render.input_prompt_selection(&self.prompt, &input)?;
+                    let value = self.post_process(value);
+
+                    render.input_prompt_selection(
+                        &self.prompt,
+                        &self.echo_text(&self.echoed(&input, &value)),
+                    )?;
                     term.flush()?;
+                    self.write_history(&input);
 
-                    return Ok(value);
+                    return Ok((value, InputSource::Typed));
                 }
                 Err(err) => {
-                    render.error(&err.to_string())?;
+                    render.parse_error(&err.to_string())?;
                     continue;
                 }
             }
@@ -292,14 +902,68 @@ where
     /// For more limited version, see [`interact_text`](#method.interact_text).
     ///
     /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<T> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact(&self) -> Result<T> {
+        self.interact_on(&self.target_term())
     }
 
     /// Like [`interact`](#method.interact) but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<T> {
+    pub fn interact_on(&self, term: &Term) -> Result<T> {
+        self.interact_with_source_on(term).map(|(value, _)| value)
+    }
+
+    /// Enables user interaction and returns the result along with where it
+    /// came from.
+    ///
+    /// The source tells whether the user entered the value or accepted the
+    /// [default](#method.default), either with an empty line or by letting
+    /// the [timeout](#method.timeout) run out. Otherwise this behaves like
+    /// [`interact`](#method.interact).
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::{Input, InputSource};
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (name, source) = Input::<String>::new()
+    ///     .with_prompt("Name")
+    ///     .default("anonymous".into())
+    ///     .interact_with_source()?;
+    ///
+    /// if source == InputSource::Default {
+    ///     println!("Accepted the default name");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn interact_with_source(&self) -> Result<(T, InputSource)> {
+        self.interact_with_source_on(&self.target_term())
+    }
+
+    /// Like [`interact_with_source`](#method.interact_with_source) but allows a specific terminal to be set.
+    pub fn interact_with_source_on(&self, term: &Term) -> Result<(T, InputSource)> {
+        if self.timeout.is_none() && piped::is_piped(term) {
+            return self.interact_piped(term, &piped::Stdin);
+        }
+
+        if self.live_validate
+            || self.max_length.is_some()
+            || self.paste_safe
+            || self.history.is_some()
+            || self.completion.is_some()
+            || self.mask.is_some()
+            || self.timeout.is_some()
+        {
+            return self.interact_text_with(term, &TermKeys);
+        }
+
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_prompt_suffix(self.prompt_suffix.as_deref());
+        render.set_truncate_echo(self.truncate_echo);
 
         loop {
             let default_string = self.default.as_ref().map(|x| x.to_string());
@@ -319,6 +983,7 @@ where
             } else {
                 term.read_line()?
             };
+            let input = self.trim_input(&input).to_string();
 
             render.add_line();
             term.clear_line()?;
@@ -326,33 +991,1095 @@ where
 
             if input.is_empty() {
                 if let Some(ref default) = self.default {
-                    render.input_prompt_selection(&self.prompt, &default.to_string())?;
+                    let default_string = default.to_string();
+                    render
+                        .input_prompt_selection(&self.prompt, &self.echo_text(&default_string))?;
                     term.flush()?;
-                    return Ok(default.clone());
+                    self.write_history(&default_string);
+                    return Ok((default.clone(), InputSource::Default));
                 } else if !self.permit_empty {
+                    self.render_empty_error(&mut render)?;
                     continue;
                 }
             }
 
+            if let Some(err) = self.char_error(&input) {
+                render.validation_error(&err)?;
+                continue;
+            }
+
             match input.parse::<T>() {
                 Ok(value) => {
                     if let Some(ref validator) = self.validator {
                         if let Some(err) = validator(&value) {
-                            render.error(&err)?;
+                            render.validation_error(&err)?;
                             continue;
                         }
                     }
 
-                    render.input_prompt_selection(&self.prompt, &input)?;
+                    let value = self.post_process(value);
+
+                    render.input_prompt_selection(
+                        &self.prompt,
+                        &self.echo_text(&self.echoed(&input, &value)),
+                    )?;
                     term.flush()?;
+                    self.write_history(&input);
 
-                    return Ok(value);
+                    return Ok((value, InputSource::Typed));
                 }
                 Err(err) => {
-                    render.error(&err.to_string())?;
+                    render.parse_error(&err.to_string())?;
                     continue;
                 }
             }
         }
     }
+
+    /// Enables the user to enter multiple values, one per line.
+    ///
+    /// Lines are read until the user enters a blank line. Every line is
+    /// parsed and validated on its own; an invalid line shows an error and
+    /// can be entered again. Unless [`allow_empty`](#method.allow_empty) is
+    /// set, at least one value has to be entered.
+    ///
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Input;
+    ///
+    /// let hosts: Vec<String> = Input::new()
+    ///     .with_prompt("Hostnames (blank line to finish)")
+    ///     .interact_lines()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn interact_lines(&self) -> Result<Vec<T>> {
+        self.interact_lines_on(&self.target_term())
+    }
+
+    /// Like [`interact_lines`](#method.interact_lines) but allows a specific terminal to be set.
+    pub fn interact_lines_on(&self, term: &Term) -> Result<Vec<T>> {
+        if piped::is_piped(term) {
+            return self.interact_lines_with(term, &piped::Stdin);
+        }
+
+        self.interact_lines_with(term, &piped::TermLines(term))
+    }
+
+    fn interact_lines_with(&self, term: &Term, lines: &dyn LineSource) -> Result<Vec<T>> {
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_prompt_suffix(self.prompt_suffix.as_deref());
+        render.set_truncate_echo(self.truncate_echo);
+
+        render.input_prompt(&self.prompt, None)?;
+        term.flush()?;
+
+        let mut values = Vec::new();
+        let mut inputs = Vec::new();
+
+        loop {
+            let input = match lines.read_line()? {
+                Some(input) => input,
+                None if values.is_empty() && !self.permit_empty => {
+                    return Err(piped::no_answer_error());
+                }
+                None => String::new(),
+            };
+            let input = self.trim_input(&input).to_string();
+            render.add_line();
+
+            if input.is_empty() {
+                if !values.is_empty() || self.permit_empty {
+                    break;
+                }
+
+                self.render_empty_error(&mut render)?;
+                continue;
+            }
+
+            if let Some(err) = self.char_error(&input) {
+                render.validation_error(&err)?;
+                continue;
+            }
+
+            match input.parse::<T>() {
+                Ok(value) => {
+                    if let Some(ref validator) = self.validator {
+                        if let Some(err) = validator(&value) {
+                            render.validation_error(&err)?;
+                            continue;
+                        }
+                    }
+
+                    let value = self.post_process(value);
+
+                    self.write_history(&input);
+                    inputs.push(self.echoed(&input, &value));
+                    values.push(value);
+                }
+                Err(err) => {
+                    render.parse_error(&err.to_string())?;
+                }
+            }
+        }
+
+        term.clear_line()?;
+        render.clear()?;
+        render.input_prompt_selection(&self.prompt, &inputs.join(", "))?;
+        term.flush()?;
+
+        Ok(values)
+    }
+
+    /// Enables the user to enter multiple values on one line.
+    ///
+    /// The line is split on `separator` and every token is parsed and
+    /// validated on its own. Whitespace around the tokens is removed and
+    /// empty tokens are skipped. If a token is invalid, an error is shown and
+    /// the whole line has to be entered again. Unless
+    /// [`allow_empty`](#method.allow_empty) is set, at least one value has to
+    /// be entered.
+    ///
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::Input;
+    ///
+    /// let tags: Vec<String> = Input::new()
+    ///     .with_prompt("Tags (comma separated)")
+    ///     .interact_separated(",")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn interact_separated(&self, separator: &str) -> Result<Vec<T>> {
+        self.interact_separated_on(&self.target_term(), separator)
+    }
+
+    /// Like [`interact_separated`](#method.interact_separated) but allows a specific terminal to be set.
+    pub fn interact_separated_on(&self, term: &Term, separator: &str) -> Result<Vec<T>> {
+        if piped::is_piped(term) {
+            return self.interact_separated_piped(term, &piped::Stdin, separator);
+        }
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_prompt_suffix(self.prompt_suffix.as_deref());
+        render.set_truncate_echo(self.truncate_echo);
+
+        'prompt: loop {
+            render.input_prompt(&self.prompt, None)?;
+            term.flush()?;
+
+            let input = if let Some(initial_text) = self.initial_text.as_ref() {
+                term.read_line_initial_text(initial_text)?
+            } else {
+                term.read_line()?
+            };
+
+            render.add_line();
+            term.clear_line()?;
+            render.clear()?;
+
+            let mut values = Vec::new();
+
+            for token in split_separated(&input, separator) {
+                if let Some(err) = self.char_error(token) {
+                    render.validation_error(&err)?;
+                    continue 'prompt;
+                }
+
+                match token.parse::<T>() {
+                    Ok(value) => {
+                        if let Some(ref validator) = self.validator {
+                            if let Some(err) = validator(&value) {
+                                render.validation_error(&err)?;
+                                continue 'prompt;
+                            }
+                        }
+
+                        values.push(self.post_process(value));
+                    }
+                    Err(err) => {
+                        render.parse_error(&err.to_string())?;
+                        continue 'prompt;
+                    }
+                }
+            }
+
+            if values.is_empty() && !self.permit_empty {
+                self.render_empty_error(&mut render)?;
+                continue;
+            }
+
+            let joined = values
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            render.input_prompt_selection(&self.prompt, &joined)?;
+            term.flush()?;
+            self.write_history(&input);
+
+            return Ok(values);
+        }
+    }
+
+    /// Returns the terminal set with [term](#method.term), or stderr.
+    fn target_term(&self) -> Term {
+        self.term.clone().unwrap_or_else(Term::stderr)
+    }
+
+    /// Redraws the input line followed by the validation status of `chars`.
+    ///
+    /// The cursor is put back at `position` afterwards.
+    fn render_live_status(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        chars: &[char],
+        position: usize,
+    ) -> io::Result<()> {
+        let input: String = chars.iter().collect();
+        let default_string = self.default.as_ref().map(|x| x.to_string());
+
+        term.clear_line()?;
+        render.input_prompt(
+            &self.prompt,
+            if self.show_default {
+                default_string.as_deref()
+            } else {
+                None
+            },
+        )?;
+        term.write_str(&self.echo_text(&input))?;
+
+        let mut status_width = 0;
+
+        if self.live_validate && !input.is_empty() {
+            status_width += render.input_live_status(self.validation_error(&input).as_deref())?;
+        }
+
+        if let (true, Some(max)) = (self.show_length, self.max_length) {
+            status_width += render.input_length(chars.len(), max)?;
+        }
+
+        term.move_cursor_left(status_width + chars.len() - position)
+    }
+
+    /// Whether a status is redrawn after the input on every keystroke.
+    fn shows_live_status(&self) -> bool {
+        self.live_validate || (self.show_length && self.max_length.is_some())
+    }
+
+    /// Cuts `line` off at the maximum length.
+    fn fit_length(&self, mut line: Vec<char>) -> Vec<char> {
+        if let Some(max) = self.max_length {
+            line.truncate(max);
+        }
+
+        line
+    }
+
+    /// Replaces the line being edited with `line` and moves the cursor to its end.
+    fn replace_line(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        chars: &mut Vec<char>,
+        position: &mut usize,
+        line: Vec<char>,
+    ) -> io::Result<()> {
+        let line = self.fit_length(line);
+        let cursor = line.len();
+        self.edit_line(render, term, chars, position, line, cursor)
+    }
+
+    /// Replaces the input with `line`, placing the cursor at `cursor`.
+    ///
+    /// Only the input is redrawn, the columns it no longer takes up are
+    /// blanked out.
+    fn edit_line(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        chars: &mut Vec<char>,
+        position: &mut usize,
+        line: Vec<char>,
+        cursor: usize,
+    ) -> io::Result<()> {
+        let removed = chars.len().saturating_sub(line.len());
+
+        term.move_cursor_left(*position)?;
+        term.write_str(&self.echo_text(&line.iter().collect::<String>()))?;
+        term.write_str(&" ".repeat(removed))?;
+        term.move_cursor_left(removed + line.len() - cursor)?;
+
+        *chars = line;
+        *position = cursor;
+
+        if self.shows_live_status() {
+            self.render_live_status(render, term, chars, *position)?;
+        }
+
+        term.flush()
+    }
+
+    /// Reads the input as a line from `source`.
+    ///
+    /// As the line can't be corrected, invalid input is an error.
+    fn interact_piped(&self, term: &Term, source: &dyn LineSource) -> Result<(T, InputSource)> {
+        let input = source.read_line()?.unwrap_or_default();
+        let input = self.trim_input(&input);
+
+        let origin = if input.is_empty() && self.default.is_some() {
+            InputSource::Default
+        } else {
+            InputSource::Typed
+        };
+
+        let value = match self.default {
+            Some(ref default) if input.is_empty() => default.clone(),
+            None if input.is_empty() && !self.permit_empty => return Err(piped::no_answer_error()),
+            _ => {
+                if let Some(err) = self.char_error(input) {
+                    return Err(piped::invalid_answer_error(&err));
+                }
+
+                let value = input
+                    .parse::<T>()
+                    .map_err(|err| piped::invalid_answer_error(&err.to_string()))?;
+
+                if let Some(err) = self.validator.as_ref().and_then(|v| v(&value)) {
+                    return Err(piped::invalid_answer_error(&err));
+                }
+
+                self.post_process(value)
+            }
+        };
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_prompt_suffix(self.prompt_suffix.as_deref());
+        render.set_truncate_echo(self.truncate_echo);
+
+        render.input_prompt_selection(&self.prompt, &self.echo_text(&value.to_string()))?;
+        term.flush()?;
+
+        if input.is_empty() {
+            self.write_history(&value.to_string());
+        } else {
+            self.write_history(input);
+        }
+
+        Ok((value, origin))
+    }
+
+    /// Reads the separated values as a line from `source`.
+    ///
+    /// As the line can't be corrected, an invalid token is an error.
+    fn interact_separated_piped(
+        &self,
+        term: &Term,
+        source: &dyn LineSource,
+        separator: &str,
+    ) -> Result<Vec<T>> {
+        let input = source.read_line()?.unwrap_or_default();
+        let mut values = Vec::new();
+
+        for token in split_separated(&input, separator) {
+            if let Some(err) = self.char_error(token) {
+                return Err(piped::invalid_answer_error(&err));
+            }
+
+            let value = token
+                .parse::<T>()
+                .map_err(|err| piped::invalid_answer_error(&err.to_string()))?;
+
+            if let Some(err) = self.validator.as_ref().and_then(|v| v(&value)) {
+                return Err(piped::invalid_answer_error(&err));
+            }
+
+            values.push(self.post_process(value));
+        }
+
+        if values.is_empty() && !self.permit_empty {
+            return Err(piped::no_answer_error());
+        }
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_prompt_suffix(self.prompt_suffix.as_deref());
+        render.set_truncate_echo(self.truncate_echo);
+
+        let joined = values
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        render.input_prompt_selection(&self.prompt, &joined)?;
+        term.flush()?;
+        self.write_history(&input);
+
+        Ok(values)
+    }
+
+    /// Resolves a prompt whose timeout ran out to the default.
+    fn timed_out(&self, render: &mut TermThemeRenderer, term: &Term) -> Result<(T, InputSource)> {
+        term.clear_line()?;
+        render.clear()?;
+
+        match self.default {
+            Some(ref default) => {
+                render
+                    .input_prompt_selection(&self.prompt, &self.echo_text(&default.to_string()))?;
+                term.flush()?;
+                Ok((default.clone(), InputSource::Default))
+            }
+            None => {
+                term.flush()?;
+                Err(timed_out_error())
+            }
+        }
+    }
+
+    /// Returns `text` as it is echoed, which is masked if a mask is set.
+    fn echo_text(&self, text: &str) -> String {
+        match self.mask {
+            Some(mask) => text.chars().map(|_| mask).collect(),
+            None => text.to_string(),
+        }
+    }
+
+    /// Returns the text echoed for `value` parsed from `input`.
+    ///
+    /// This is the input as typed unless post processing may have changed
+    /// the value.
+    fn echoed(&self, input: &str, value: &T) -> String {
+        match self.post_processing {
+            Some(_) => value.to_string(),
+            None => input.to_string(),
+        }
+    }
+
+    /// Applies the post processing to a parsed value.
+    fn post_process(&self, value: T) -> T {
+        match self.post_processing {
+            Some(ref post_processing) => post_processing(value),
+            None => value,
+        }
+    }
+
+    /// Returns an error if `input` is too long or naming the first character
+    /// of it that isn't allowed.
+    fn char_error(&self, input: &str) -> Option<String> {
+        if let Some(max) = self.max_length {
+            if input.chars().count() > max {
+                return Some(format!("Must be at most {} characters long", max));
+            }
+        }
+
+        input
+            .chars()
+            .find(|&chr| {
+                self.allowed_chars
+                    .as_ref()
+                    .map_or(false, |allowed| !allowed.contains(chr))
+                    || self
+                        .forbidden_chars
+                        .as_ref()
+                        .map_or(false, |forbidden| forbidden.contains(chr))
+            })
+            .map(|chr| format!("Character '{}' is not allowed", chr))
+    }
+
+    /// Trims `input` if trimming is enabled.
+    fn trim_input<'s>(&self, input: &'s str) -> &'s str {
+        if self.trim {
+            input.trim()
+        } else {
+            input
+        }
+    }
+
+    /// Parses and validates `input`, returning the error if any.
+    fn validation_error(&self, input: &str) -> Option<String> {
+        let input = self.trim_input(input);
+
+        if let Some(err) = self.char_error(input) {
+            return Some(err);
+        }
+
+        match input.parse::<T>() {
+            Ok(value) => self
+                .validator
+                .as_ref()
+                .and_then(|validator| validator(&value)),
+            Err(err) => Some(err.to_string()),
+        }
+    }
+
+    /// Renders the error for an empty input if one is set.
+    fn render_empty_error(&self, render: &mut TermThemeRenderer) -> io::Result<()> {
+        match self.empty_error_message {
+            Some(ref message) => render.validation_error(message),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves through the history from `pos` and returns the new position
+    /// and the entry there.
+    ///
+    /// Position `None` stands for the line being typed, which has no entry.
+    /// Returns `None` if there's nothing to move to.
+    fn history_step(
+        &self,
+        pos: Option<usize>,
+        up: bool,
+    ) -> Option<(Option<usize>, Option<String>)> {
+        let history = self.history.as_ref()?.borrow();
+
+        match (pos, up) {
+            (_, true) => {
+                let next = pos.map_or(0, |pos| pos + 1);
+                history.read(next).map(|entry| (Some(next), Some(entry)))
+            }
+            (Some(0), false) => Some((None, None)),
+            (Some(pos), false) => history
+                .read(pos - 1)
+                .map(|entry| (Some(pos - 1), Some(entry))),
+            (None, false) => None,
+        }
+    }
+
+    fn write_history(&self, val: &str) {
+        if let Some(ref history) = self.history {
+            history.borrow_mut().write(val);
+        }
+    }
+}
+
+/// Splits `input` on `separator` into trimmed, non-empty tokens.
+fn split_separated<'s>(input: &'s str, separator: &'s str) -> impl Iterator<Item = &'s str> {
+    input
+        .split(separator)
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Returns where the word before `position` starts.
+///
+/// Whitespace right before `position` counts as part of the word.
+fn previous_word_start(chars: &[char], position: usize) -> usize {
+    let end = chars[..position]
+        .iter()
+        .rposition(|c| !c.is_whitespace())
+        .map_or(0, |idx| idx + 1);
+
+    chars[..end]
+        .iter()
+        .rposition(|c| c.is_whitespace())
+        .map_or(0, |idx| idx + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::ScriptedKeys;
+
+    #[test]
+    fn test_piped() {
+        let term = Term::buffered_stderr();
+        let lines = piped::MockLines::new(&[" 42 ", "", "x", "7"]);
+
+        let mut input = Input::<u32>::new();
+        input.trim(true).max(10);
+
+        assert_eq!(
+            io::Error::from(input.interact_piped(&term, &lines).unwrap_err()).kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            io::Error::from(input.interact_piped(&term, &lines).unwrap_err()).kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(
+            io::Error::from(input.interact_piped(&term, &lines).unwrap_err()).kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            input.interact_piped(&term, &lines).unwrap(),
+            (7, InputSource::Typed)
+        );
+
+        input.default(3);
+        assert_eq!(
+            input.interact_piped(&term, &lines).unwrap(),
+            (3, InputSource::Default)
+        );
+    }
+
+    #[test]
+    fn test_initial_text_and_default() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<String>::new();
+        input.default("fallback".into()).with_initial_text("draft");
+
+        let keys = ScriptedKeys::new(&[Key::Enter]);
+        assert_eq!(
+            input.interact_text_with(&term, &keys).unwrap(),
+            ("draft".into(), InputSource::Typed)
+        );
+
+        let mut erase = vec![Key::Backspace; 5];
+        erase.push(Key::Enter);
+        let keys = ScriptedKeys::new(&erase);
+        assert_eq!(
+            input.interact_text_with(&term, &keys).unwrap(),
+            ("fallback".into(), InputSource::Default)
+        );
+    }
+
+    #[test]
+    fn test_timeout_resolves_to_default() {
+        let rv = Input::<u32>::new()
+            .default(42)
+            .timeout(Duration::from_millis(10))
+            .interact_on(&Term::buffered_stderr())
+            .unwrap();
+
+        assert_eq!(rv, 42);
+    }
+
+    #[test]
+    fn test_timeout_without_default() {
+        let err = Input::<String>::new()
+            .timeout(Duration::from_millis(10))
+            .interact_text_on(&Term::buffered_stderr())
+            .unwrap_err();
+
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_live_status_transitions() {
+        let mut input = Input::<u8>::new();
+        input.live_validate(true).validate_with(|value: &u8| {
+            if *value >= 10 {
+                Ok(())
+            } else {
+                Err("must be at least 10")
+            }
+        });
+
+        let mut typed = String::new();
+        let mut statuses = vec![];
+
+        for chr in "1x".chars() {
+            typed.push(chr);
+            statuses.push(input.validation_error(&typed));
+        }
+        typed.pop();
+
+        for chr in "259".chars() {
+            typed.push(chr);
+            statuses.push(input.validation_error(&typed));
+        }
+
+        assert_eq!(
+            statuses,
+            vec![
+                Some("must be at least 10".into()),
+                Some("invalid digit found in string".into()),
+                None,
+                None,
+                Some("number too large to fit in target type".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_step() {
+        let mut history = vec!["first".to_string(), "second".to_string()];
+        let mut input = Input::<String>::new();
+        input.history_with(&mut history);
+
+        assert_eq!(input.history_step(None, false), None);
+        assert_eq!(
+            input.history_step(None, true),
+            Some((Some(0), Some("second".into())))
+        );
+        assert_eq!(
+            input.history_step(Some(0), true),
+            Some((Some(1), Some("first".into())))
+        );
+        assert_eq!(input.history_step(Some(1), true), None);
+        assert_eq!(
+            input.history_step(Some(1), false),
+            Some((Some(0), Some("second".into())))
+        );
+        assert_eq!(input.history_step(Some(0), false), Some((None, None)));
+    }
+
+    #[test]
+    fn test_post_processing_after_validation() {
+        let mut input = Input::<String>::new();
+        input
+            .validate_with(|value: &String| {
+                if value.starts_with(' ') {
+                    Ok(())
+                } else {
+                    Err("expected the raw input")
+                }
+            })
+            .with_post_processing(|value: String| value.trim().to_lowercase());
+
+        assert_eq!(input.validation_error(" Mail@Example.com"), None);
+        assert_eq!(
+            input.post_process(" Mail@Example.com".to_string()),
+            "mail@example.com"
+        );
+    }
+
+    #[test]
+    fn test_trim() {
+        let mut input = Input::<u32>::new();
+        assert!(input.validation_error(" 42 ").is_some());
+
+        input.trim(true);
+        assert_eq!(input.trim_input(" 42 "), "42");
+        assert_eq!(input.trim_input("   "), "");
+        assert_eq!(input.validation_error(" 42 "), None);
+    }
+
+    #[test]
+    fn test_allowed_chars() {
+        let mut input = Input::<String>::new();
+        input.allowed_chars("abc123");
+
+        assert_eq!(input.char_error("cab321"), None);
+        assert_eq!(
+            input.char_error("ab-12"),
+            Some("Character '-' is not allowed".into())
+        );
+    }
+
+    #[test]
+    fn test_forbidden_chars() {
+        let mut input = Input::<String>::new();
+        input.forbidden_chars("/\\");
+
+        assert_eq!(input.char_error("report.txt"), None);
+        assert_eq!(
+            input.char_error("docs/report.txt"),
+            Some("Character '/' is not allowed".into())
+        );
+        assert_eq!(
+            input.validation_error("docs\\report.txt"),
+            Some("Character '\\' is not allowed".into())
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let mut input = Input::<i32>::new();
+        input.min(1).max(100);
+
+        assert_eq!(
+            input.validation_error("0"),
+            Some("Value must be at least 1".into())
+        );
+        assert_eq!(input.validation_error("1"), None);
+        assert_eq!(input.validation_error("100"), None);
+        assert_eq!(
+            input.validation_error("101"),
+            Some("Value must be at most 100".into())
+        );
+    }
+
+    #[test]
+    fn test_masked() {
+        let mut input = Input::<u32>::new();
+        assert_eq!(input.echo_text("1234"), "1234");
+
+        input.masked('•');
+        assert_eq!(input.echo_text("1234"), "••••");
+        assert_eq!(input.validation_error("1234"), None);
+    }
+
+    #[test]
+    fn test_empty_error_message() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<String>::new();
+
+        let (rv, output) = crate::capture_output(|| {
+            input.render_empty_error(&mut TermThemeRenderer::new(&term, input.theme))
+        });
+        rv.unwrap();
+        assert_eq!(output, "");
+
+        input.empty_error_message("A value is required");
+        let (rv, output) = crate::capture_output(|| {
+            input.render_empty_error(&mut TermThemeRenderer::new(&term, input.theme))
+        });
+        rv.unwrap();
+        assert_eq!(output, "error: A value is required\n");
+    }
+
+    #[test]
+    fn test_keys_empty_error_message() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<String>::new();
+        input
+            .with_prompt("Name")
+            .empty_error_message("A value is required");
+
+        let keys = ScriptedKeys::new(&[Key::Enter, Key::Char('x'), Key::Enter]);
+        let (rv, output) = crate::capture_output(|| input.interact_text_with(&term, &keys));
+        assert_eq!(rv.unwrap().0, "x");
+        assert!(output.contains("error: A value is required\n"));
+        assert!(output.ends_with("Name: x\n"));
+    }
+
+    #[test]
+    fn test_split_separated() {
+        let tokens: Vec<_> = split_separated(" rust, cli,, tui ,", ",").collect();
+        assert_eq!(tokens, vec!["rust", "cli", "tui"]);
+
+        assert_eq!(split_separated(" ; ", ";").count(), 0);
+    }
+
+    #[test]
+    fn test_keys_paste_safe() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<String>::new();
+        input.paste_safe(true);
+
+        let keys = [
+            Key::Char('a'),
+            Key::Enter,
+            Key::Enter,
+            Key::Char('b'),
+            Key::Enter,
+        ];
+        let keys = ScriptedKeys::new(&keys);
+        assert_eq!(input.interact_text_with(&term, &keys).unwrap().0, "a  b");
+    }
+
+    #[test]
+    fn test_previous_word_start() {
+        let chars: Vec<char> = "git commit  -m".chars().collect();
+
+        assert_eq!(previous_word_start(&chars, 14), 12);
+        assert_eq!(previous_word_start(&chars, 12), 4);
+        assert_eq!(previous_word_start(&chars, 6), 4);
+        assert_eq!(previous_word_start(&chars, 3), 0);
+    }
+
+    #[test]
+    fn test_keys_line_editing() {
+        let term = Term::buffered_stderr();
+        let input = Input::<String>::new();
+
+        let mut keys: Vec<Key> = "foo bar".chars().map(Key::Char).collect();
+        keys.extend(vec![
+            Key::Char('\u{17}'),
+            Key::Char('x'),
+            Key::Home,
+            Key::Del,
+            Key::Char('\u{5}'),
+            Key::ArrowLeft,
+            Key::Char('\u{15}'),
+            Key::Enter,
+        ]);
+        let keys = ScriptedKeys::new(&keys);
+        assert_eq!(input.interact_text_with(&term, &keys).unwrap().0, "x");
+
+        let mut keys: Vec<Key> = "abc".chars().map(Key::Char).collect();
+        keys.extend(vec![
+            Key::Char('\u{1}'),
+            Key::Del,
+            Key::End,
+            Key::Char('d'),
+            Key::Enter,
+        ]);
+        let keys = ScriptedKeys::new(&keys);
+        assert_eq!(input.interact_text_with(&term, &keys).unwrap().0, "bcd");
+    }
+
+    #[test]
+    fn test_keys_echo() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<u32>::new();
+        input.with_prompt("Port");
+
+        let run = |input: &Input<u32>| {
+            let mut keys: Vec<Key> = "0080".chars().map(Key::Char).collect();
+            keys.push(Key::Enter);
+            let keys = ScriptedKeys::new(&keys);
+            crate::capture_output(|| input.interact_text_with(&term, &keys).unwrap().0)
+        };
+
+        let (value, output) = run(&input);
+        assert_eq!(value, 80);
+        assert!(output.ends_with("Port: 0080\n"));
+
+        input.with_post_processing(|port| port + 1);
+        let (value, output) = run(&input);
+        assert_eq!(value, 81);
+        assert!(output.ends_with("Port: 81\n"));
+    }
+
+    #[test]
+    fn test_interact_lines() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<u32>::new();
+        input.with_prompt("Ports");
+
+        let lines = piped::MockLines::new(&["80", "x", "443", "", "8080"]);
+        let (values, output) =
+            crate::capture_output(|| input.interact_lines_with(&term, &lines).unwrap());
+        assert_eq!(values, vec![80, 443]);
+        assert!(output.ends_with("Ports: 80, 443\n"));
+
+        let lines = piped::MockLines::new(&["", "22"]);
+        assert_eq!(input.interact_lines_with(&term, &lines).unwrap(), vec![22]);
+
+        let lines = piped::MockLines::new(&[]);
+        assert!(input.interact_lines_with(&term, &lines).is_err());
+    }
+
+    #[test]
+    fn test_interact_lines_piped_eof() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<u32>::new();
+        input.with_prompt("Ports");
+
+        let lines = piped::MockLines::new(&["80", "443"]);
+        assert_eq!(
+            input.interact_lines_with(&term, &lines).unwrap(),
+            vec![80, 443]
+        );
+
+        let lines = piped::MockLines::new(&["", ""]);
+        assert_eq!(
+            io::Error::from(input.interact_lines_with(&term, &lines).unwrap_err()).kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+
+        input.allow_empty(true);
+        let lines = piped::MockLines::new(&[]);
+        assert!(input.interact_lines_with(&term, &lines).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_separated_piped() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<u32>::new();
+        input.with_prompt("Ports");
+
+        let lines = piped::MockLines::new(&["80, 443,", "80, x", " , ", "8080"]);
+        let (values, output) =
+            crate::capture_output(|| input.interact_separated_piped(&term, &lines, ",").unwrap());
+        assert_eq!(values, vec![80, 443]);
+        assert!(output.ends_with("Ports: 80, 443\n"));
+
+        assert_eq!(
+            io::Error::from(
+                input
+                    .interact_separated_piped(&term, &lines, ",")
+                    .unwrap_err()
+            )
+            .kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            io::Error::from(
+                input
+                    .interact_separated_piped(&term, &lines, ",")
+                    .unwrap_err()
+            )
+            .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(
+            input.interact_separated_piped(&term, &lines, ",").unwrap(),
+            vec![8080]
+        );
+        assert_eq!(
+            io::Error::from(
+                input
+                    .interact_separated_piped(&term, &lines, ",")
+                    .unwrap_err()
+            )
+            .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn test_max_length() {
+        let mut input = Input::<String>::new();
+        input.max_length(3);
+
+        assert_eq!(
+            input.fit_length("abcdef".chars().collect()),
+            vec!['a', 'b', 'c']
+        );
+        assert_eq!(input.validation_error("abc"), None);
+        assert_eq!(
+            input.validation_error("abcd"),
+            Some("Must be at most 3 characters long".into())
+        );
+        assert!(!input.shows_live_status());
+
+        input.show_length(true);
+        assert!(input.shows_live_status());
+    }
+
+    #[test]
+    fn test_keys_prompt_suffix() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<String>::new();
+        input.with_prompt("Name").prompt_suffix(" > ");
+
+        let keys = ScriptedKeys::new(&[Key::Char('x'), Key::Enter]);
+        let (rv, output) = crate::capture_output(|| input.interact_text_with(&term, &keys));
+        assert_eq!(rv.unwrap().0, "x");
+        assert!(output.starts_with("Name > "));
+    }
+
+    #[test]
+    fn test_keys_max_length() {
+        let term = Term::buffered_stderr();
+        let mut input = Input::<String>::new();
+        input.with_prompt("Code").max_length(3).show_length(true);
+
+        let mut keys: Vec<Key> = "abcdef".chars().map(Key::Char).collect();
+        keys.extend(vec![Key::Backspace, Key::Char('x'), Key::Enter]);
+        let keys = ScriptedKeys::new(&keys);
+
+        let (rv, output) = crate::capture_output(|| input.interact_text_with(&term, &keys));
+        assert_eq!(rv.unwrap().0, "abx");
+        assert!(output.contains("  (3/3)"));
+        assert!(!output.contains("  (4/3)"));
+        assert!(output.ends_with("Code: abx\n"));
+    }
 }
@@ -1,6 +1,10 @@
 pub mod confirm;
 pub mod input;
 pub mod multi_select;
+pub mod number_stepper;
 pub mod password;
+pub mod quantity_select;
+pub mod recap;
 pub mod select;
 pub mod sort;
+pub mod typed_select;
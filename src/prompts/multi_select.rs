@@ -1,9 +1,44 @@
-use std::{io, iter::repeat, ops::Rem};
+use std::{io, iter::repeat, ops::Rem, time::Duration};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::{
+    cursor::HiddenCursor,
+    error::{Error, Result},
+    keys::{KeySource, ScriptedKeys, TermKeys},
+    piped::{self, LineSource},
+    prompts::select::page_capacity,
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+    timeout::timed_out_error,
+    validate::Validator,
+};
 
 use console::{Key, Term};
 
+type ValidatorCallback<'a> = Box<dyn Fn(&[usize]) -> Option<String> + 'a>;
+
+/// The chosen items of a multi select or sort prompt.
+///
+/// Returned by [MultiSelect::interact_detailed](struct.MultiSelect.html#method.interact_detailed)
+/// and [Sort::interact_detailed](struct.Sort.html#method.interact_detailed).
+/// With the `serde` feature enabled it can be serialized, e.g. to persist
+/// the answers of a wizard.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelectionResult {
+    /// Indices of the chosen items in the order they were returned.
+    pub indices: Vec<usize>,
+    /// Texts of the chosen items in the same order.
+    pub items: Vec<String>,
+}
+
+impl SelectionResult {
+    pub(crate) fn new(indices: Vec<usize>, items: &[String]) -> SelectionResult {
+        SelectionResult {
+            items: indices.iter().map(|&idx| items[idx].clone()).collect(),
+            indices,
+        }
+    }
+}
+
 /// Renders a multi select prompt.
 ///
 /// ## Example usage
@@ -24,7 +59,29 @@ pub struct MultiSelect<'a> {
     prompt: Option<String>,
     clear: bool,
     theme: &'a dyn Theme,
+    indent: usize,
+    prompt_icon: Option<String>,
+    wizard_step: Option<(usize, usize)>,
+    term: Option<Term>,
+    truncate_echo: bool,
+    truncate_items: bool,
     paged: bool,
+    wrap: bool,
+    bell: bool,
+    max_length: Option<usize>,
+    max_height: Option<usize>,
+    timeout: Option<Duration>,
+    preserve_selection_order: bool,
+    min_selections: Option<usize>,
+    max_selections: Option<usize>,
+    bulk_keys: bool,
+    empty_echo: Option<String>,
+    help_toggle_key: Option<char>,
+    header: Option<String>,
+    group_headers: Vec<(usize, String)>,
+    cursor: usize,
+    validator: Option<ValidatorCallback<'a>>,
+    footer: Option<String>,
 }
 
 impl<'a> Default for MultiSelect<'a> {
@@ -47,7 +104,29 @@ impl<'a> MultiSelect<'a> {
             clear: true,
             prompt: None,
             theme,
+            indent: 0,
+            prompt_icon: None,
+            wizard_step: None,
+            term: None,
+            truncate_echo: false,
+            truncate_items: false,
             paged: false,
+            wrap: true,
+            bell: false,
+            max_length: None,
+            max_height: None,
+            timeout: None,
+            preserve_selection_order: false,
+            min_selections: None,
+            max_selections: None,
+            bulk_keys: true,
+            empty_echo: None,
+            help_toggle_key: None,
+            header: None,
+            group_headers: vec![],
+            cursor: 0,
+            validator: None,
+            footer: None,
         }
     }
 
@@ -57,6 +136,51 @@ impl<'a> MultiSelect<'a> {
         self
     }
 
+    /// Limits the number of items shown per page when paging is enabled.
+    ///
+    /// By default a page takes up the whole height of the terminal. With a
+    /// maximum length set a page holds at most `val` items, but never more
+    /// than fit into the terminal.
+    pub fn max_length(&mut self, val: usize) -> &mut MultiSelect<'a> {
+        self.max_length = Some(val);
+        self
+    }
+
+    /// Caps the total height of the prompt at `val` lines.
+    ///
+    /// The prompt line stays in place while the items scroll line by line
+    /// to keep the active one visible, and the help stays below them.
+    /// Unlike [max_length](#method.max_length) this also accounts for the
+    /// lines taken by the help and errors.
+    ///
+    /// Other list prompts, e.g. Sort, don't support this yet.
+    pub fn max_height(&mut self, val: usize) -> &mut MultiSelect<'a> {
+        self.max_height = Some(val);
+        self
+    }
+
+    /// Indicates whether the selection wraps around at the ends
+    ///
+    /// With wrapping disabled moving up from the first item or down from
+    /// the last one keeps the selection where it is.
+    ///
+    /// Wrapping is enabled by default
+    pub fn wrap(&mut self, val: bool) -> &mut MultiSelect<'a> {
+        self.wrap = val;
+        self
+    }
+
+    /// Indicates whether the terminal bell rings when a key is rejected
+    ///
+    /// The bell rings on keys that don't do anything, e.g. moving past the
+    /// end without wrapping or checking more than the maximum of items.
+    ///
+    /// The bell is disabled by default
+    pub fn bell(&mut self, val: bool) -> &mut MultiSelect<'a> {
+        self.bell = val;
+        self
+    }
+
     /// Sets the clear behavior of the menu.
     ///
     /// The default is to clear the menu.
@@ -65,6 +189,160 @@ impl<'a> MultiSelect<'a> {
         self
     }
 
+    /// Indents every rendered line by `val` spaces, see
+    /// [Select::indent](struct.Select.html#method.indent).
+    pub fn indent(&mut self, val: usize) -> &mut MultiSelect<'a> {
+        self.indent = val;
+        self
+    }
+
+    /// Sets an icon rendered in front of the prompt, see
+    /// [Select::prompt_icon](struct.Select.html#method.prompt_icon).
+    pub fn prompt_icon<S: Into<String>>(&mut self, val: S) -> &mut MultiSelect<'a> {
+        self.prompt_icon = Some(val.into());
+        self
+    }
+
+    /// Shows the position of the prompt in a sequence of prompts.
+    ///
+    /// The theme renders e.g. `Step 3/7` in front of the prompt and the
+    /// final selection, see
+    /// [Theme::format_step](../theme/trait.Theme.html#method.format_step).
+    pub fn wizard_step(&mut self, current: usize, total: usize) -> &mut MultiSelect<'a> {
+        self.wizard_step = Some((current, total));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    ///
+    /// The `interact` methods that don't take a terminal use this one
+    /// instead of stderr.
+    pub fn term(&mut self, term: Term) -> &mut MultiSelect<'a> {
+        self.term = Some(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    ///
+    /// This is the terminal set with [term](#method.term), or stderr.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.target_term().size()
+    }
+
+    /// Truncates the echoed selections to the width of the terminal.
+    ///
+    /// When many items are checked the echo is cut off with an ellipsis
+    /// instead of wrapping onto the following lines.
+    ///
+    /// Disabled by default.
+    pub fn truncate_echo(&mut self, val: bool) -> &mut MultiSelect<'a> {
+        self.truncate_echo = val;
+        self
+    }
+
+    /// Truncates items to the width of the terminal.
+    ///
+    /// Long items are cut off with an ellipsis instead of wrapping onto
+    /// the following lines. Wrapped items are still cleared correctly on
+    /// redraws.
+    ///
+    /// Disabled by default.
+    pub fn truncate_items(&mut self, val: bool) -> &mut MultiSelect<'a> {
+        self.truncate_items = val;
+        self
+    }
+
+    /// Requires at least `val` items to be checked.
+    ///
+    /// Pressing enter with fewer items checked shows an error and keeps the
+    /// menu open. Interaction fails right away if `val` is greater than the
+    /// number of items, since the prompt could never be confirmed.
+    pub fn min_selections(&mut self, val: usize) -> &mut MultiSelect<'a> {
+        self.min_selections = Some(val);
+        self
+    }
+
+    /// Allows at most `val` items to be checked.
+    ///
+    /// Once the limit is reached further items can't be checked until
+    /// another one is unchecked.
+    pub fn max_selections(&mut self, val: usize) -> &mut MultiSelect<'a> {
+        self.max_selections = Some(val);
+        self
+    }
+
+    /// Sets the text echoed after the prompt when nothing was selected.
+    ///
+    /// By default nothing is shown next to the prompt in that case.
+    pub fn empty_echo<S: Into<String>>(&mut self, val: S) -> &mut MultiSelect<'a> {
+        self.empty_echo = Some(val.into());
+        self
+    }
+
+    /// Enables the key binding help, shown and hidden with `val`.
+    ///
+    /// The help is hidden initially and shown below the items once the key
+    /// is pressed, e.g. `Some('?')`. Keys that are already bound, like the
+    /// vim-style and bulk keys, take precedence over it.
+    ///
+    /// Disabled by default.
+    pub fn help_toggle_key(&mut self, val: Option<char>) -> &mut MultiSelect<'a> {
+        self.help_toggle_key = val;
+        self
+    }
+
+    /// Sets a line rendered between the prompt and the items.
+    ///
+    /// The line is styled by the theme and kept across redraws.
+    pub fn header<S: Into<String>>(&mut self, val: S) -> &mut MultiSelect<'a> {
+        self.header = Some(val.into());
+        self
+    }
+
+    /// Sets a line rendered below the items, e.g. a legend of the keys.
+    ///
+    /// The line is styled by the theme and kept across redraws.
+    pub fn footer<S: Into<String>>(&mut self, val: S) -> &mut MultiSelect<'a> {
+        self.footer = Some(val.into());
+        self
+    }
+
+    /// Enables or disables the keys changing all items at once.
+    ///
+    /// With the keys enabled `a` checks all items, or unchecks them if all
+    /// are checked already, and `i` inverts the selection. Both apply to
+    /// every item, not only the current page.
+    ///
+    /// Bulk keys are enabled by default.
+    pub fn enable_bulk_keys(&mut self, val: bool) -> &mut MultiSelect<'a> {
+        self.bulk_keys = val;
+        self
+    }
+
+    /// Returns the selection in the order the items were checked.
+    ///
+    /// By default the selected indices are returned in the order the items
+    /// appear in the menu. When enabled they are returned in the order the
+    /// user checked them instead, items that were unchecked again are left
+    /// out. Items checked by default come first in menu order.
+    pub fn preserve_selection_order(&mut self, val: bool) -> &mut MultiSelect<'a> {
+        self.preserve_selection_order = val;
+        self
+    }
+
+    /// Sets a timeout after which the defaults are picked.
+    ///
+    /// The timeout restarts with every key press. If no key is pressed in
+    /// time the items checked by [defaults](#method.defaults) are picked
+    /// regardless of the changes made so far. An error of kind `TimedOut`
+    /// is returned when the defaults don't satisfy the
+    /// [minimum](#method.min_selections) or
+    /// [maximum](#method.max_selections) number of selections.
+    pub fn timeout(&mut self, val: Duration) -> &mut MultiSelect<'a> {
+        self.timeout = Some(val);
+        self
+    }
+
     /// Sets a defaults for the menu.
     pub fn defaults(&mut self, val: &[bool]) -> &mut MultiSelect<'a> {
         self.defaults = val
@@ -77,6 +355,98 @@ impl<'a> MultiSelect<'a> {
         self
     }
 
+    /// Checks the items at the given indices by default.
+    ///
+    /// Unlike [defaults](#method.defaults) this only needs the indices of
+    /// the items to check and keeps the other items as they are, e.g. the
+    /// ones added with [item_checked](#method.item_checked). The items have
+    /// to be added first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of range of the items added so far.
+    pub fn checked_indices(&mut self, indices: &[usize]) -> &mut MultiSelect<'a> {
+        for &idx in indices {
+            assert!(
+                idx < self.items.len(),
+                "checked index {} is out of range for {} items",
+                idx,
+                self.items.len()
+            );
+            self.defaults[idx] = true;
+        }
+        self
+    }
+
+    /// Highlights the item at `idx` initially.
+    ///
+    /// This only moves the cursor and doesn't check the item. The items
+    /// have to be added first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range of the items added so far.
+    pub fn cursor_at(&mut self, idx: usize) -> &mut MultiSelect<'a> {
+        assert!(
+            idx < self.items.len(),
+            "cursor index {} is out of range for {} items",
+            idx,
+            self.items.len()
+        );
+        self.cursor = idx;
+        self
+    }
+
+    /// Registers a validator for the indices of the checked items.
+    ///
+    /// The validator gets the indices as they would be returned and runs
+    /// after the [minimum](#method.min_selections) and
+    /// [maximum](#method.max_selections) are checked. If it rejects them,
+    /// its error is shown below the items and the user can change the
+    /// selection. Multiple validators run in the order they were
+    /// registered.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::MultiSelect;
+    ///
+    /// fn main() {
+    ///     let selection = MultiSelect::new()
+    ///         .items(&["Server", "Client", "Database"])
+    ///         .validate_with(|indices: &[usize]| -> Result<(), &str> {
+    ///             if indices.contains(&2) && !indices.contains(&0) {
+    ///                 Err("The database needs the server")
+    ///             } else {
+    ///                 Ok(())
+    ///             }
+    ///         })
+    ///         .interact()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn validate_with<V>(&mut self, validator: V) -> &mut MultiSelect<'a>
+    where
+        V: Validator<[usize]> + 'a,
+    {
+        let old_validator_func = self.validator.take();
+
+        self.validator = Some(Box::new(move |value: &[usize]| -> Option<String> {
+            if let Some(old) = old_validator_func.as_ref() {
+                if let Some(err) = old(value) {
+                    return Some(err);
+                }
+            }
+
+            match validator.validate(value) {
+                Ok(()) => None,
+                Err(err) => Some(err.to_string()),
+            }
+        }));
+
+        self
+    }
+
     /// Add a single item to the selector.
     #[inline]
     pub fn item<T: ToString>(&mut self, item: T) -> &mut MultiSelect<'a> {
@@ -108,6 +478,17 @@ impl<'a> MultiSelect<'a> {
         self
     }
 
+    /// Adds a section header in front of the items added next.
+    ///
+    /// Headers are rendered with their own styling and can't be checked.
+    /// They don't count as items, so the indices of the items stay the same
+    /// as without headers. A header added after the last item is rendered
+    /// below it.
+    pub fn group_header<S: Into<String>>(&mut self, header: S) -> &mut MultiSelect<'a> {
+        self.group_headers.push((self.items.len(), header.into()));
+        self
+    }
+
     /// Prefaces the menu with a prompt.
     ///
     /// When a prompt is set the system also prints out a confirmation after
@@ -121,23 +502,324 @@ impl<'a> MultiSelect<'a> {
     ///
     /// The user can select the items with the space bar and on enter
     /// the selected items will be returned.
-    pub fn interact(&self) -> io::Result<Vec<usize>> {
-        self.interact_on(&Term::stderr())
+    pub fn interact(&self) -> Result<Vec<usize>> {
+        self.interact_on(&self.target_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// This method is similar to [interact_on_opt](#method.interact_on_opt) except for the fact that it does not allow selection of the terminal.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    /// Result contains `Some(indices)` if the user confirmed with enter or `None` if user cancelled with 'Esc' or 'q'.
+    pub fn interact_opt(&self) -> Result<Option<Vec<usize>>> {
+        self.interact_on_opt(&self.target_term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<Vec<usize>> {
-        let mut page = 0;
+    ///
+    /// Escape returns the [defaults](#method.defaults) in this mode.
+    #[inline]
+    pub fn interact_on(&self, term: &Term) -> Result<Vec<usize>> {
+        self._interact_on(term, false, &TermKeys)?
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &Term) -> Result<Option<Vec<usize>>> {
+        self._interact_on(term, true, &TermKeys)
+    }
 
+    /// Runs the prompt on a fixed sequence of keys instead of user input.
+    ///
+    /// This allows testing code that drives a multi select prompt without a
+    /// terminal. The prompt is rendered to a buffered stderr. Like
+    /// [interact_opt](#method.interact_opt) the result is `None` if the keys
+    /// cancel the prompt. Running out of keys before the prompt is finished
+    /// is an error of kind `UnexpectedEof`. A [timeout](#method.timeout)
+    /// doesn't apply.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use dialoguer::{console::Key, MultiSelect};
+    ///
+    /// let selection = MultiSelect::new()
+    ///     .items(&["Option A", "Option B", "Option C"])
+    ///     .interact_with_keys(&[Key::ArrowDown, Key::Char(' '), Key::Enter])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(selection, Some(vec![1]));
+    /// ```
+    pub fn interact_with_keys(&self, keys: &[Key]) -> Result<Option<Vec<usize>>> {
+        self.interact_with_keys_on(&Term::buffered_stderr(), keys)
+    }
+
+    /// Like [interact_with_keys](#method.interact_with_keys) but allows a specific terminal to be set.
+    pub fn interact_with_keys_on(&self, term: &Term, keys: &[Key]) -> Result<Option<Vec<usize>>> {
+        self._interact_on(term, true, &ScriptedKeys::new(keys))
+    }
+
+    /// Enables user interaction and returns the indices and texts of the
+    /// selected items.
+    ///
+    /// Behaves like [interact](#method.interact) otherwise.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_detailed(&self) -> Result<SelectionResult> {
+        self.interact_detailed_on(&self.target_term())
+    }
+
+    /// Like [interact_detailed](#method.interact_detailed) but allows a specific terminal to be set.
+    pub fn interact_detailed_on(&self, term: &Term) -> Result<SelectionResult> {
+        let indices = self.interact_on(term)?;
+        Ok(SelectionResult::new(indices, &self.items))
+    }
+
+    /// Enables user interaction and maps the selected items through `f`.
+    ///
+    /// Behaves like [interact](#method.interact) but calls `f` with the
+    /// index and text of every selected item and returns the results in the
+    /// order the indices would have been returned in.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    ///
+    /// ## Example usage
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::MultiSelect;
+    ///
+    /// let features: Vec<String> = MultiSelect::new()
+    ///     .items(&["Logging", "Metrics", "Tracing"])
+    ///     .map_results(|_, item| item.to_lowercase())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn map_results<T, F: Fn(usize, &str) -> T>(&self, f: F) -> Result<Vec<T>> {
+        self.map_results_on(&self.target_term(), f)
+    }
+
+    /// Like [map_results](#method.map_results) but allows a specific terminal to be set.
+    pub fn map_results_on<T, F: Fn(usize, &str) -> T>(&self, term: &Term, f: F) -> Result<Vec<T>> {
+        self.interact_on(term)
+            .map(|selected| self.map_selected(&selected, f))
+    }
+
+    /// Enables user interaction and returns the texts of the selected items.
+    ///
+    /// Behaves like [interact](#method.interact) otherwise.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_items(&self) -> Result<Vec<String>> {
+        self.interact_items_on(&self.target_term())
+    }
+
+    /// Like [interact_items](#method.interact_items) but allows a specific terminal to be set.
+    pub fn interact_items_on(&self, term: &Term) -> Result<Vec<String>> {
+        self.map_results_on(term, |_, item| item.to_string())
+    }
+
+    /// Like [interact_items](#method.interact_items) but returns `None` if
+    /// the user cancelled like [interact_opt](#method.interact_opt).
+    pub fn interact_items_opt(&self) -> Result<Option<Vec<String>>> {
+        self.interact_items_on_opt(&self.target_term())
+    }
+
+    /// Like [interact_items_opt](#method.interact_items_opt) but allows a specific terminal to be set.
+    pub fn interact_items_on_opt(&self, term: &Term) -> Result<Option<Vec<String>>> {
+        Ok(self
+            .interact_on_opt(term)?
+            .map(|selected| self.map_selected(&selected, |_, item| item.to_string())))
+    }
+
+    /// Returns the terminal set with [term](#method.term), or stderr.
+    fn target_term(&self) -> Term {
+        self.term.clone().unwrap_or_else(Term::stderr)
+    }
+
+    /// Maps the `selected` indices and their items through `f`.
+    fn map_selected<T, F: Fn(usize, &str) -> T>(&self, selected: &[usize], f: F) -> Vec<T> {
+        selected
+            .iter()
+            .map(|&idx| f(idx, &self.items[idx]))
+            .collect()
+    }
+
+    /// Echoes the `selected` items after the prompt, if there is one.
+    fn echo(&self, render: &mut TermThemeRenderer, selected: &[usize]) -> io::Result<()> {
+        let prompt = match self.prompt {
+            Some(ref prompt) => prompt,
+            None => return Ok(()),
+        };
+
+        match self.empty_echo {
+            Some(ref text) if selected.is_empty() => {
+                render.multi_select_prompt_empty_selection(prompt, text)
+            }
+            _ => {
+                let selections: Vec<_> = selected
+                    .iter()
+                    .map(|&idx| self.items[idx].as_str())
+                    .collect();
+
+                render.multi_select_prompt_selection(prompt, &selections[..])
+            }
+        }
+    }
+
+    /// Returns the number of lines taken by the header and the footer.
+    fn frame_height(&self) -> usize {
+        self.header
+            .iter()
+            .chain(self.footer.iter())
+            .map(|text| text.lines().count().max(1))
+            .sum()
+    }
+
+    /// Returns the number of group header rows in front of each item,
+    /// counting trailing headers for the last item.
+    fn header_rows(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = (0..self.items.len())
+            .map(|idx| {
+                self.group_headers
+                    .iter()
+                    .filter(|&&(at, _)| at == idx)
+                    .count()
+            })
+            .collect();
+
+        if let Some(last) = rows.last_mut() {
+            *last += self.trailing_headers().count();
+        }
+
+        rows
+    }
+
+    /// Returns the group headers that were added after the last item.
+    fn trailing_headers(&self) -> impl Iterator<Item = &(usize, String)> {
+        let len = self.items.len();
+        self.group_headers.iter().filter(move |&&(at, _)| at >= len)
+    }
+
+    /// Returns the error to show if `count` checked items can't be confirmed.
+    fn selection_error(&self, count: usize) -> Option<String> {
+        match (self.min_selections, self.max_selections) {
+            (Some(min), _) if count < min => Some(format!(
+                "Select at least {} item{}",
+                min,
+                if min == 1 { "" } else { "s" }
+            )),
+            (_, Some(max)) if count > max => Some(format!(
+                "Select at most {} item{}",
+                max,
+                if max == 1 { "" } else { "s" }
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the error to show if the `selected` items can't be confirmed.
+    ///
+    /// This checks the number of items before running the validators.
+    fn validation_error(&self, selected: &[usize]) -> Option<String> {
+        self.selection_error(selected.len()).or_else(|| {
+            self.validator
+                .as_ref()
+                .and_then(|validator| validator(selected))
+        })
+    }
+
+    /// Reads a comma separated list of item indices or texts from `source`.
+    fn interact_piped(&self, term: &Term, source: &dyn LineSource) -> Result<Vec<usize>> {
+        let mut selected = match piped::read_answer(source)? {
+            Some(answer) => match piped::parse_indices(&answer, &self.items) {
+                Some(selected) => selected,
+                None => {
+                    return Err(piped::invalid_answer_error(&format!(
+                        "Invalid items: {}",
+                        answer
+                    )))
+                }
+            },
+            None => (0..self.defaults.len())
+                .filter(|&idx| self.defaults[idx])
+                .collect(),
+        };
+
+        if !self.preserve_selection_order {
+            selected.sort_unstable();
+        }
+
+        if let Some(err) = self.validation_error(&selected) {
+            return Err(piped::invalid_answer_error(&err));
+        }
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_truncate_echo(self.truncate_echo);
+        render.set_truncate_items(self.truncate_items);
+
+        self.echo(&mut render, &selected)?;
+        term.flush()?;
+
+        Ok(selected)
+    }
+
+    /// Resolves a prompt whose timeout ran out to the defaults.
+    fn timed_out(&self, render: &mut TermThemeRenderer, term: &Term) -> Result<Option<Vec<usize>>> {
+        if self.clear {
+            render.clear()?;
+        }
+
+        let selected: Vec<usize> = (0..self.defaults.len())
+            .filter(|&idx| self.defaults[idx])
+            .collect();
+
+        if self.validation_error(&selected).is_some() {
+            term.flush()?;
+            return Err(timed_out_error());
+        }
+
+        self.echo(render, &selected)?;
+        term.flush()?;
+
+        Ok(Some(selected))
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    fn _interact_on(
+        &self,
+        term: &Term,
+        allow_quit: bool,
+        keys: &dyn KeySource,
+    ) -> Result<Option<Vec<usize>>> {
         if self.items.is_empty() {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
                 "Empty list of items given to `MultiSelect`",
-            ));
+            )
+            .into());
+        }
+
+        if self
+            .min_selections
+            .map_or(false, |min| min > self.items.len())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Minimum number of selections of `MultiSelect` exceeds the number of items",
+            )
+            .into());
+        }
+
+        if !keys.is_scripted() && self.timeout.is_none() && piped::is_piped(term) {
+            return self.interact_piped(term, &piped::Stdin).map(Some);
         }
 
         let capacity = if self.paged {
-            term.size().0 as usize - 1
+            let rows = (term.size().0 as usize - 1)
+                .saturating_sub(self.frame_height())
+                .max(1);
+            let max = self.max_length.unwrap_or(rows);
+            page_capacity(&self.header_rows(), max, rows, false)
         } else {
             self.items.len()
         };
@@ -145,27 +827,33 @@ impl<'a> MultiSelect<'a> {
         let pages = (self.items.len() as f64 / capacity as f64).ceil() as usize;
 
         let mut render = TermThemeRenderer::new(term, self.theme);
-        let mut sel = 0;
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_truncate_echo(self.truncate_echo);
+        render.set_truncate_items(self.truncate_items);
+        render.set_max_height(self.max_height);
+        let _cursor = HiddenCursor::new(term)?;
+        let mut sel = self.cursor;
+        let mut page = sel / capacity;
 
         if let Some(ref prompt) = self.prompt {
             render.multi_select_prompt(prompt)?;
         }
 
-        let mut size_vec = Vec::new();
-
-        for items in self
-            .items
-            .iter()
-            .flat_map(|i| i.split('\n'))
-            .collect::<Vec<_>>()
-        {
-            let size = &items.len();
-            size_vec.push(*size);
-        }
-
         let mut checked: Vec<bool> = self.defaults.clone();
+        let mut selection_order: Vec<usize> =
+            (0..checked.len()).filter(|&idx| checked[idx]).collect();
+        let mut error: Option<String> = None;
+        let mut show_help = false;
 
         loop {
+            if let Some(ref header) = self.header {
+                render.select_prompt_header(header)?;
+            }
+
+            render.begin_scroll_region();
+
             for (idx, item) in self
                 .items
                 .iter()
@@ -173,102 +861,158 @@ impl<'a> MultiSelect<'a> {
                 .skip(page * capacity)
                 .take(capacity)
             {
+                for (_, header) in self.group_headers.iter().filter(|&&(at, _)| at == idx) {
+                    render.select_prompt_group_header(header)?;
+                }
+
+                if sel == idx {
+                    render.begin_scroll_focus();
+                }
+
                 render.multi_select_prompt_item(item, checked[idx], sel == idx)?;
+
+                if sel == idx {
+                    render.end_scroll_focus();
+                }
+            }
+
+            if page == pages - 1 {
+                for (_, header) in self.trailing_headers() {
+                    render.select_prompt_group_header(header)?;
+                }
+            }
+
+            render.begin_scroll_footer();
+
+            if show_help {
+                render.multi_select_prompt_help()?;
             }
 
-            term.hide_cursor()?;
+            if let Some(ref footer) = self.footer {
+                render.select_prompt_footer(footer)?;
+            }
+
+            if let Some(ref err) = error.take() {
+                render.validation_error(err)?;
+            }
+
+            render.end_scroll_region()?;
+
+            // The widths of the lines as rendered, including the header and
+            // the footer, so wrapped lines are cleared completely.
+            let size_vec = render.line_widths().to_vec();
+
             term.flush()?;
 
-            match term.read_key()? {
+            let key = match keys.read_key(term, self.timeout)? {
+                Some(key) => key,
+                None => return self.timed_out(&mut render, term),
+            };
+
+            match key {
                 Key::ArrowDown | Key::Char('j') => {
                     if sel == !0 {
                         sel = 0;
-                    } else {
+                    } else if self.wrap || sel + 1 < self.items.len() {
                         sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
+                    } else if self.bell {
+                        render.bell()?;
                     }
                 }
                 Key::ArrowUp | Key::Char('k') => {
                     if sel == !0 {
                         sel = self.items.len() - 1;
-                    } else {
+                    } else if self.wrap || sel > 0 {
                         sel = ((sel as i64 - 1 + self.items.len() as i64)
                             % (self.items.len() as i64)) as usize;
+                    } else if self.bell {
+                        render.bell()?;
                     }
                 }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if self.paged {
-                        if page == 0 {
-                            page = pages - 1;
-                        } else {
-                            page -= 1;
-                        }
-
-                        sel = page * capacity;
+                Key::ArrowLeft | Key::Char('h') if self.paged => {
+                    if page == 0 {
+                        page = pages - 1;
+                    } else {
+                        page -= 1;
                     }
-                }
-                Key::ArrowRight | Key::Char('l') => {
-                    if self.paged {
-                        if page == pages - 1 {
-                            page = 0;
-                        } else {
-                            page += 1;
-                        }
 
-                        sel = page * capacity;
+                    sel = page * capacity;
+                }
+                Key::ArrowRight | Key::Char('l') if self.paged => {
+                    if page == pages - 1 {
+                        page = 0;
+                    } else {
+                        page += 1;
                     }
+
+                    sel = page * capacity;
                 }
-                Key::Char(' ') => {
-                    checked[sel] = !checked[sel];
+                Key::Char(' ')
+                    if checked[sel]
+                        || self
+                            .max_selections
+                            .map_or(true, |max| selection_order.len() < max) =>
+                {
+                    toggle(&mut checked, &mut selection_order, sel);
+                }
+                Key::Char('a') if self.bulk_keys => {
+                    toggle_all(&mut checked, &mut selection_order, self.max_selections);
+                }
+                Key::Char('i') if self.bulk_keys => {
+                    invert(&mut checked, &mut selection_order, self.max_selections);
+                }
+                Key::Escape | Key::Char('q') if allow_quit => {
+                    if self.clear {
+                        render.clear_preserve_prompt(&size_vec)?;
+                        term.flush()?;
+                    }
+
+                    return Ok(None);
                 }
                 Key::Escape => {
                     if self.clear {
                         render.clear()?;
                     }
 
-                    if let Some(ref prompt) = self.prompt {
-                        render.multi_select_prompt_selection(prompt, &[][..])?;
-                    }
+                    self.echo(&mut render, &[])?;
 
-                    term.show_cursor()?;
                     term.flush()?;
 
-                    return Ok(self
-                        .defaults
-                        .clone()
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
-                        .collect());
+                    return Ok(Some(
+                        self.defaults
+                            .clone()
+                            .into_iter()
+                            .enumerate()
+                            .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
+                            .collect(),
+                    ));
+                }
+                Key::Char(chr) if Some(chr) == self.help_toggle_key => {
+                    show_help = !show_help;
                 }
                 Key::Enter => {
-                    if self.clear {
-                        render.clear()?;
-                    }
+                    let selected = if self.preserve_selection_order {
+                        selection_order.clone()
+                    } else {
+                        (0..checked.len()).filter(|&idx| checked[idx]).collect()
+                    };
 
-                    if let Some(ref prompt) = self.prompt {
-                        let selections: Vec<_> = checked
-                            .iter()
-                            .enumerate()
-                            .filter_map(|(idx, &checked)| {
-                                if checked {
-                                    Some(self.items[idx].as_str())
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect();
-
-                        render.multi_select_prompt_selection(prompt, &selections[..])?;
-                    }
+                    if let Some(err) = self.validation_error(&selected) {
+                        error = Some(err);
+                    } else {
+                        if self.clear {
+                            render.clear()?;
+                        }
 
-                    term.show_cursor()?;
-                    term.flush()?;
+                        self.echo(&mut render, &selected)?;
+
+                        term.flush()?;
 
-                    return Ok(checked
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
-                        .collect());
+                        return Ok(Some(selected));
+                    }
+                }
+                _ if self.bell => {
+                    render.bell()?;
                 }
                 _ => {}
             }
@@ -281,3 +1025,339 @@ impl<'a> MultiSelect<'a> {
         }
     }
 }
+
+/// Toggles the item at `idx`, keeping track of the order items were checked in.
+fn toggle(checked: &mut [bool], selection_order: &mut Vec<usize>, idx: usize) {
+    checked[idx] = !checked[idx];
+
+    if checked[idx] {
+        selection_order.push(idx);
+    } else {
+        selection_order.retain(|&x| x != idx);
+    }
+}
+
+/// Checks all items, or unchecks them if all are checked already.
+///
+/// Items are checked in order until `max` items are checked.
+fn toggle_all(checked: &mut [bool], selection_order: &mut Vec<usize>, max: Option<usize>) {
+    if checked.iter().all(|&c| c) {
+        for c in checked.iter_mut() {
+            *c = false;
+        }
+        selection_order.clear();
+        return;
+    }
+
+    for idx in 0..checked.len() {
+        if max.map_or(false, |max| selection_order.len() >= max) {
+            break;
+        }
+
+        if !checked[idx] {
+            toggle(checked, selection_order, idx);
+        }
+    }
+}
+
+/// Inverts the selection unless that would check more than `max` items.
+fn invert(checked: &mut [bool], selection_order: &mut Vec<usize>, max: Option<usize>) {
+    let unchecked = checked.iter().filter(|&&c| !c).count();
+
+    if max.map_or(false, |max| unchecked > max) {
+        return;
+    }
+
+    for idx in 0..checked.len() {
+        toggle(checked, selection_order, idx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_rows() {
+        let mut select = MultiSelect::new();
+        select
+            .group_header("Fruits")
+            .items(&["Apple", "Banana"])
+            .group_header("Vegetables")
+            .item("Carrot")
+            .group_header("More soon");
+
+        assert_eq!(select.header_rows(), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_checked_indices() {
+        let mut select = MultiSelect::new();
+        select
+            .item_checked("a", true)
+            .items(&["b", "c", "d"])
+            .checked_indices(&[3, 1]);
+
+        assert_eq!(select.defaults, vec![true, true, false, true]);
+    }
+
+    #[test]
+    fn test_cursor_at() {
+        let mut select = MultiSelect::new();
+        select.items(&["a", "b"]).cursor_at(1);
+
+        assert_eq!(select.cursor, 1);
+        assert_eq!(select.defaults, vec![false, false]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cursor index 2 is out of range for 2 items")]
+    fn test_cursor_at_out_of_range() {
+        MultiSelect::new().items(&["a", "b"]).cursor_at(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "checked index 2 is out of range for 2 items")]
+    fn test_checked_indices_out_of_range() {
+        MultiSelect::new().items(&["a", "b"]).checked_indices(&[2]);
+    }
+
+    #[test]
+    fn test_selection_result() {
+        let items = vec!["a".to_string(), "b".into(), "c".into()];
+        let result = SelectionResult::new(vec![2, 0], &items);
+
+        assert_eq!(result.indices, vec![2, 0]);
+        assert_eq!(result.items, vec!["c", "a"]);
+    }
+
+    #[test]
+    fn test_piped() {
+        let term = Term::buffered_stderr();
+        let lines = piped::MockLines::new(&["2, a", "", "d", "c,b,a"]);
+
+        let mut select = MultiSelect::new();
+        select.items(&["a", "b", "c"]).defaults(&[false, true]);
+        select.max_selections(2);
+
+        assert_eq!(select.interact_piped(&term, &lines).unwrap(), vec![0, 2]);
+        assert_eq!(select.interact_piped(&term, &lines).unwrap(), vec![1]);
+        assert_eq!(
+            io::Error::from(select.interact_piped(&term, &lines).unwrap_err()).kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            io::Error::from(select.interact_piped(&term, &lines).unwrap_err()).kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn test_timeout_resolves_to_defaults() {
+        let rv = MultiSelect::new()
+            .items(&["a", "b", "c"])
+            .defaults(&[true, false, true])
+            .timeout(Duration::from_millis(10))
+            .interact_on(&Term::buffered_stderr())
+            .unwrap();
+
+        assert_eq!(rv, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_timeout_with_unsatisfied_defaults() {
+        let err = MultiSelect::new()
+            .items(&["a", "b"])
+            .min_selections(1)
+            .timeout(Duration::from_millis(10))
+            .interact_on(&Term::buffered_stderr())
+            .unwrap_err();
+
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_toggle_selection_order() {
+        let mut checked = vec![false; 4];
+        let mut selection_order = vec![];
+
+        for &idx in &[2, 0, 3, 0, 1] {
+            toggle(&mut checked, &mut selection_order, idx);
+        }
+
+        assert_eq!(checked, vec![false, true, true, true]);
+        assert_eq!(selection_order, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_keys_selection_order() {
+        let mut select = MultiSelect::new();
+        select.items(&["Apple", "Banana", "Cherry"]);
+
+        let keys = [
+            Key::ArrowDown,
+            Key::ArrowDown,
+            Key::Char(' '),
+            Key::ArrowUp,
+            Key::ArrowUp,
+            Key::Char(' '),
+            Key::Enter,
+        ];
+        assert_eq!(select.interact_with_keys(&keys).unwrap(), Some(vec![0, 2]));
+
+        select.preserve_selection_order(true);
+        assert_eq!(select.interact_with_keys(&keys).unwrap(), Some(vec![2, 0]));
+
+        let keys = [Key::ArrowDown, Key::Char(' '), Key::Char('a'), Key::Enter];
+        assert_eq!(
+            select.interact_with_keys(&keys).unwrap(),
+            Some(vec![1, 0, 2])
+        );
+    }
+
+    #[test]
+    fn test_keys_tall_header_footer() {
+        let header = ["header"; 20].join("\n");
+        let footer = ["footer"; 10].join("\n");
+        let mut select = MultiSelect::new();
+        select
+            .items(&["Apple", "Banana", "Cherry"])
+            .paged(true)
+            .header(&header)
+            .footer(&footer);
+
+        let keys = [Key::ArrowDown, Key::Char(' '), Key::Enter];
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(vec![1]));
+        assert!(output.contains("header\n"));
+        assert!(output.contains("footer\n"));
+    }
+
+    #[test]
+    fn test_keys_bulk() {
+        let mut select = MultiSelect::new();
+        select
+            .items(&["a", "b", "c", "d"])
+            .defaults(&[false, true])
+            .paged(true)
+            .max_length(2);
+
+        let run = |select: &MultiSelect, keys: &[Key]| select.interact_with_keys(keys).unwrap();
+
+        assert_eq!(
+            run(&select, &[Key::Char('a'), Key::Enter]),
+            Some(vec![0, 1, 2, 3])
+        );
+        assert_eq!(
+            run(&select, &[Key::Char('a'), Key::Char('a'), Key::Enter]),
+            Some(vec![])
+        );
+        assert_eq!(
+            run(&select, &[Key::Char('i'), Key::Enter]),
+            Some(vec![0, 2, 3])
+        );
+
+        select.enable_bulk_keys(false);
+        assert_eq!(
+            run(&select, &[Key::Char('a'), Key::Char('i'), Key::Enter]),
+            Some(vec![1])
+        );
+    }
+
+    #[test]
+    fn test_bulk_toggles() {
+        let mut checked = vec![false, true, false];
+        let mut selection_order = vec![1];
+
+        invert(&mut checked, &mut selection_order, None);
+        assert_eq!(checked, vec![true, false, true]);
+        assert_eq!(selection_order, vec![0, 2]);
+
+        toggle_all(&mut checked, &mut selection_order, None);
+        assert_eq!(checked, vec![true, true, true]);
+        assert_eq!(selection_order, vec![0, 2, 1]);
+
+        toggle_all(&mut checked, &mut selection_order, None);
+        assert_eq!(checked, vec![false, false, false]);
+        assert!(selection_order.is_empty());
+
+        toggle_all(&mut checked, &mut selection_order, Some(2));
+        assert_eq!(checked, vec![true, true, false]);
+
+        invert(&mut checked, &mut selection_order, Some(0));
+        assert_eq!(checked, vec![true, true, false]);
+    }
+
+    #[test]
+    fn test_selection_error() {
+        let mut select = MultiSelect::new();
+        select.min_selections(2).max_selections(4);
+
+        assert_eq!(
+            select.selection_error(1),
+            Some("Select at least 2 items".into())
+        );
+        assert_eq!(select.selection_error(2), None);
+        assert_eq!(select.selection_error(4), None);
+        assert_eq!(
+            select.selection_error(5),
+            Some("Select at most 4 items".into())
+        );
+    }
+
+    #[test]
+    fn test_validation_error() {
+        let mut select = MultiSelect::new();
+        select
+            .items(&["Server", "Client", "Database"])
+            .max_selections(2)
+            .validate_with(|indices: &[usize]| -> std::result::Result<(), &str> {
+                if indices.contains(&2) && !indices.contains(&0) {
+                    Err("The database needs the server")
+                } else {
+                    Ok(())
+                }
+            });
+
+        assert_eq!(select.validation_error(&[0, 2]), None);
+        assert_eq!(
+            select.validation_error(&[1, 2]),
+            Some("The database needs the server".into())
+        );
+        assert_eq!(
+            select.validation_error(&[0, 1, 2]),
+            Some("Select at most 2 items".into())
+        );
+    }
+
+    #[test]
+    fn test_map_selected() {
+        #[derive(Debug, PartialEq)]
+        struct Topping {
+            id: usize,
+            name: String,
+        }
+
+        let mut select = MultiSelect::new();
+        select.items(&["Cheese", "Olives", "Basil"]);
+
+        let toppings = select.map_selected(&[2, 0], |id, name| Topping {
+            id,
+            name: name.into(),
+        });
+
+        assert_eq!(
+            toppings,
+            vec![
+                Topping {
+                    id: 2,
+                    name: "Basil".into()
+                },
+                Topping {
+                    id: 0,
+                    name: "Cheese".into()
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use std::{fmt::Display, io, str::FromStr};
+
+use crate::{
+    cursor::HiddenCursor,
+    error::Result,
+    piped::{self, LineSource},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
+
+use console::{Key, Term};
+
+/// Integer types that can be used with a [NumberStepper](struct.NumberStepper.html).
+///
+/// This is implemented for all primitive integer types.
+pub trait Steppable: Copy + Default + Display + FromStr + PartialOrd {
+    /// Adds `step`, saturating at the numeric bounds of the type.
+    fn step_up(self, step: Self) -> Self;
+
+    /// Subtracts `step`, saturating at the numeric bounds of the type.
+    fn step_down(self, step: Self) -> Self;
+
+    /// Returns the step used when none is configured.
+    fn one() -> Self;
+}
+
+macro_rules! impl_steppable {
+    ($($t:ty),*) => {
+        $(
+            impl Steppable for $t {
+                fn step_up(self, step: Self) -> Self {
+                    self.saturating_add(step)
+                }
+
+                fn step_down(self, step: Self) -> Self {
+                    self.saturating_sub(step)
+                }
+
+                fn one() -> Self {
+                    1
+                }
+            }
+        )*
+    };
+}
+
+impl_steppable!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Renders a prompt for a number that is changed in steps.
+///
+/// The value is shown next to the prompt and increased with the up arrow
+/// or `+` and decreased with the down arrow or `-`. Enter confirms it.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::NumberStepper;
+///
+/// let workers: u32 = NumberStepper::new()
+///     .with_prompt("Workers")
+///     .default(4)
+///     .min(1)
+///     .max(16)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct NumberStepper<'a, T> {
+    prompt: String,
+    default: Option<T>,
+    step: T,
+    min: Option<T>,
+    max: Option<T>,
+    theme: &'a dyn Theme,
+    indent: usize,
+    prompt_icon: Option<String>,
+    wizard_step: Option<(usize, usize)>,
+    term: Option<Term>,
+}
+
+impl<'a, T> Default for NumberStepper<'a, T>
+where
+    T: Steppable,
+{
+    fn default() -> NumberStepper<'a, T> {
+        NumberStepper::new()
+    }
+}
+
+impl<'a, T> NumberStepper<'a, T>
+where
+    T: Steppable,
+{
+    /// Creates a number stepper prompt.
+    pub fn new() -> NumberStepper<'a, T> {
+        NumberStepper::with_theme(&SimpleTheme)
+    }
+
+    /// Creates a number stepper prompt with a specific theme.
+    pub fn with_theme(theme: &'a dyn Theme) -> NumberStepper<'a, T> {
+        NumberStepper {
+            prompt: "".into(),
+            default: None,
+            step: T::one(),
+            min: None,
+            max: None,
+            theme,
+            indent: 0,
+            prompt_icon: None,
+            wizard_step: None,
+            term: None,
+        }
+    }
+
+    /// Sets the number stepper prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut NumberStepper<'a, T> {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Sets the initial value.
+    ///
+    /// Without a default the prompt starts at the minimum if there is one
+    /// and at zero otherwise.
+    pub fn default(&mut self, val: T) -> &mut NumberStepper<'a, T> {
+        self.default = Some(val);
+        self
+    }
+
+    /// Sets the amount a single key press changes the value by.
+    ///
+    /// The default step is one.
+    pub fn step(&mut self, val: T) -> &mut NumberStepper<'a, T> {
+        self.step = val;
+        self
+    }
+
+    /// Sets the smallest value that can be chosen.
+    pub fn min(&mut self, val: T) -> &mut NumberStepper<'a, T> {
+        self.min = Some(val);
+        self
+    }
+
+    /// Sets the largest value that can be chosen.
+    pub fn max(&mut self, val: T) -> &mut NumberStepper<'a, T> {
+        self.max = Some(val);
+        self
+    }
+
+    /// Indents every rendered line by `val` spaces, see
+    /// [Select::indent](struct.Select.html#method.indent).
+    pub fn indent(&mut self, val: usize) -> &mut NumberStepper<'a, T> {
+        self.indent = val;
+        self
+    }
+
+    /// Sets an icon rendered in front of the prompt, see
+    /// [Select::prompt_icon](struct.Select.html#method.prompt_icon).
+    pub fn prompt_icon<S: Into<String>>(&mut self, val: S) -> &mut NumberStepper<'a, T> {
+        self.prompt_icon = Some(val.into());
+        self
+    }
+
+    /// Shows the position of the prompt in a sequence of prompts.
+    ///
+    /// The theme renders e.g. `Step 3/7` in front of the prompt and the
+    /// final answer, see
+    /// [Theme::format_step](../theme/trait.Theme.html#method.format_step).
+    pub fn wizard_step(&mut self, current: usize, total: usize) -> &mut NumberStepper<'a, T> {
+        self.wizard_step = Some((current, total));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    ///
+    /// The `interact` methods that don't take a terminal use this one
+    /// instead of stderr.
+    pub fn term(&mut self, term: Term) -> &mut NumberStepper<'a, T> {
+        self.term = Some(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    ///
+    /// This is the terminal set with [term](#method.term), or stderr.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.target_term().size()
+    }
+
+    /// Returns the terminal set with [term](#method.term), or stderr.
+    fn target_term(&self) -> Term {
+        self.term.clone().unwrap_or_else(Term::stderr)
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact(&self) -> Result<T> {
+        self.interact_on(&self.target_term())
+    }
+
+    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<T> {
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Minimum of `NumberStepper` is greater than its maximum",
+                )
+                .into());
+            }
+        }
+
+        if piped::is_piped(term) {
+            return self.interact_piped(term, &piped::Stdin);
+        }
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+
+        let mut value = self.initial_value();
+
+        let _cursor = HiddenCursor::new(term)?;
+
+        loop {
+            term.clear_line()?;
+            render.number_stepper_prompt(&self.prompt, &value.to_string())?;
+            term.flush()?;
+
+            match term.read_key()? {
+                Key::ArrowUp | Key::Char('+') => {
+                    value = self.step_up(value);
+                }
+                Key::ArrowDown | Key::Char('-') => {
+                    value = self.step_down(value);
+                }
+                Key::Enter => break,
+                Key::Unknown => {
+                    return Err(
+                        io::Error::new(io::ErrorKind::NotConnected, "Not a terminal").into(),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        term.clear_line()?;
+        render.number_stepper_prompt_selection(&self.prompt, &value.to_string())?;
+        term.flush()?;
+
+        Ok(value)
+    }
+
+    /// Reads the value from `source`, which has to be within the bounds.
+    ///
+    /// A blank answer picks the value the prompt starts at.
+    fn interact_piped(&self, term: &Term, source: &dyn LineSource) -> Result<T> {
+        let value = match piped::read_answer(source)? {
+            Some(answer) => match answer.parse::<T>() {
+                Ok(value) if self.clamp(value) == value => value,
+                _ => {
+                    return Err(piped::invalid_answer_error(&format!(
+                        "Invalid number: {}",
+                        answer
+                    )))
+                }
+            },
+            None => self.initial_value(),
+        };
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.number_stepper_prompt_selection(&self.prompt, &value.to_string())?;
+        term.flush()?;
+
+        Ok(value)
+    }
+
+    /// Returns the value the prompt starts at.
+    fn initial_value(&self) -> T {
+        let value = self.default.or(self.min).unwrap_or_default();
+        self.clamp(value)
+    }
+
+    fn step_up(&self, value: T) -> T {
+        self.clamp(value.step_up(self.step))
+    }
+
+    fn step_down(&self, value: T) -> T {
+        self.clamp(value.step_down(self.step))
+    }
+
+    /// Limits `value` to the configured bounds.
+    fn clamp(&self, value: T) -> T {
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => min,
+            (_, Some(max)) if value > max => max,
+            _ => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps() {
+        let mut stepper = NumberStepper::new();
+        stepper.default(5).step(2);
+
+        let value = stepper.initial_value();
+        assert_eq!(value, 5);
+        assert_eq!(stepper.step_up(value), 7);
+        assert_eq!(stepper.step_down(stepper.step_down(value)), 1);
+    }
+
+    #[test]
+    fn test_clamps_to_bounds() {
+        let mut stepper = NumberStepper::new();
+        stepper.min(-2).max(10).step(3);
+
+        assert_eq!(stepper.initial_value(), -2);
+        assert_eq!(stepper.step_down(-2), -2);
+        assert_eq!(stepper.step_up(9), 10);
+
+        stepper.default(42);
+        assert_eq!(stepper.initial_value(), 10);
+    }
+
+    #[test]
+    fn test_saturates_at_type_bounds() {
+        let stepper = NumberStepper::<u8>::new();
+
+        assert_eq!(stepper.initial_value(), 0);
+        assert_eq!(stepper.step_down(0), 0);
+        assert_eq!(stepper.step_up(255), 255);
+    }
+
+    #[test]
+    fn test_piped() {
+        let term = Term::buffered_stderr();
+        let lines = piped::MockLines::new(&[" 7 ", "", "42", "x"]);
+        let mut stepper = NumberStepper::new();
+        stepper.default(4).min(1).max(16);
+
+        assert_eq!(stepper.interact_piped(&term, &lines).unwrap(), 7);
+        assert_eq!(stepper.interact_piped(&term, &lines).unwrap(), 4);
+        assert!(stepper.interact_piped(&term, &lines).is_err());
+        assert!(stepper.interact_piped(&term, &lines).is_err());
+    }
+}
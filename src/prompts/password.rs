@@ -1,10 +1,26 @@
-use std::io;
+use std::{io, time::Duration};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::{
+    error::{Error, Result},
+    keys::{KeySource, TermKeys},
+    piped::{self, LineSource},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+    timeout::timed_out_error,
+};
 
-use console::Term;
+use console::{Key, Term};
 use zeroize::Zeroizing;
 
+/// What a key press does while reading a password that can be revealed.
+#[derive(Debug, PartialEq, Eq)]
+enum SecretEdit {
+    Push(char),
+    Pop,
+    Toggle,
+    Submit,
+    Ignore,
+}
+
 /// Renders a password input prompt.
 ///
 /// ## Example usage
@@ -22,8 +38,16 @@ use zeroize::Zeroizing;
 pub struct Password<'a> {
     prompt: String,
     theme: &'a dyn Theme,
+    indent: usize,
+    prompt_icon: Option<String>,
+    wizard_step: Option<(usize, usize)>,
+    term: Option<Term>,
     allow_empty_password: bool,
     confirmation_prompt: Option<(String, String)>,
+    max_attempts: Option<usize>,
+    allow_reveal: bool,
+    reveal_key: Key,
+    timeout: Option<Duration>,
 }
 
 impl<'a> Default for Password<'a> {
@@ -43,8 +67,16 @@ impl<'a> Password<'a> {
         Password {
             prompt: "".into(),
             theme,
+            indent: 0,
+            prompt_icon: None,
+            wizard_step: None,
+            term: None,
             allow_empty_password: false,
             confirmation_prompt: None,
+            max_attempts: None,
+            allow_reveal: false,
+            reveal_key: Key::Char('\x12'),
+            timeout: None,
         }
     }
 
@@ -64,6 +96,22 @@ impl<'a> Password<'a> {
         self
     }
 
+    /// Limits the number of failed confirmations.
+    ///
+    /// After `val` mismatching confirmations the interaction is aborted with
+    /// an error. Until then every mismatch shows how many attempts are left.
+    /// By default the user can retry forever.
+    ///
+    /// The first confirmation always counts as an attempt, so `0` behaves
+    /// like `1`: the first mismatch aborts the interaction.
+    ///
+    /// The error is an `Error::ValidationFailed` carrying the mismatch error
+    /// message.
+    pub fn max_attempts(&mut self, val: usize) -> &mut Password<'a> {
+        self.max_attempts = Some(val);
+        self
+    }
+
     /// Allows/Disables empty password.
     ///
     /// By default this setting is set to false (i.e. password is not empty).
@@ -72,24 +120,108 @@ impl<'a> Password<'a> {
         self
     }
 
+    /// Allows/Disables revealing the password while typing it.
+    ///
+    /// When allowed, the [reveal key](#method.reveal_key) switches between
+    /// hidden entry and echoing the typed password. Entry always starts out
+    /// hidden.
+    ///
+    /// By default this setting is set to false.
+    pub fn allow_reveal(&mut self, val: bool) -> &mut Password<'a> {
+        self.allow_reveal = val;
+        self
+    }
+
+    /// Sets the key that reveals or hides the password.
+    ///
+    /// This only has an effect if [revealing](#method.allow_reveal) is
+    /// allowed. The default key is Ctrl-R.
+    pub fn reveal_key(&mut self, val: Key) -> &mut Password<'a> {
+        self.reveal_key = val;
+        self
+    }
+
+    /// Sets a timeout after which the interaction is aborted.
+    ///
+    /// The timeout restarts with every key press. As a password has no
+    /// default, an error of kind `TimedOut` is returned if no key is
+    /// pressed in time.
+    pub fn timeout(&mut self, val: Duration) -> &mut Password<'a> {
+        self.timeout = Some(val);
+        self
+    }
+
+    /// Indents every rendered line by `val` spaces, see
+    /// [Select::indent](struct.Select.html#method.indent).
+    pub fn indent(&mut self, val: usize) -> &mut Password<'a> {
+        self.indent = val;
+        self
+    }
+
+    /// Sets an icon rendered in front of the prompt, see
+    /// [Select::prompt_icon](struct.Select.html#method.prompt_icon).
+    pub fn prompt_icon<S: Into<String>>(&mut self, val: S) -> &mut Password<'a> {
+        self.prompt_icon = Some(val.into());
+        self
+    }
+
+    /// Shows the position of the prompt in a sequence of prompts.
+    ///
+    /// The theme renders e.g. `Step 3/7` in front of the prompt and the
+    /// final answer, see
+    /// [Theme::format_step](../theme/trait.Theme.html#method.format_step).
+    pub fn wizard_step(&mut self, current: usize, total: usize) -> &mut Password<'a> {
+        self.wizard_step = Some((current, total));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    ///
+    /// The `interact` methods that don't take a terminal use this one
+    /// instead of stderr.
+    pub fn term(&mut self, term: Term) -> &mut Password<'a> {
+        self.term = Some(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    ///
+    /// This is the terminal set with [term](#method.term), or stderr.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.target_term().size()
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<String> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact(&self) -> Result<String> {
+        self.interact_on(&self.target_term())
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<String> {
+    pub fn interact_on(&self, term: &Term) -> Result<String> {
+        self.interact_with(term, &TermKeys)
+    }
+
+    fn interact_with(&self, term: &Term, keys: &dyn KeySource) -> Result<String> {
+        if !keys.is_scripted() && self.timeout.is_none() && piped::is_piped(term) {
+            return self.interact_piped(term, &piped::Stdin);
+        }
+
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
         render.set_prompts_reset_height(false);
 
+        let mut attempts = 0;
+
         loop {
-            let password = Zeroizing::new(self.prompt_password(&mut render, &self.prompt)?);
+            let password = Zeroizing::new(self.prompt_password(&mut render, &self.prompt, keys)?);
 
             if let Some((ref prompt, ref err)) = self.confirmation_prompt {
-                let pw2 = Zeroizing::new(self.prompt_password(&mut render, &prompt)?);
+                let pw2 = Zeroizing::new(self.prompt_password(&mut render, prompt, keys)?);
 
                 if *password == *pw2 {
                     render.clear()?;
@@ -98,7 +230,17 @@ impl<'a> Password<'a> {
                     return Ok((*password).clone());
                 }
 
-                render.error(err)?;
+                attempts += 1;
+
+                let attempts_left = self.attempts_left(attempts);
+
+                if attempts_left == Some(0) {
+                    render.clear()?;
+                    term.flush()?;
+                    return Err(Error::ValidationFailed(err.clone()));
+                }
+
+                render.password_mismatch(err, attempts_left)?;
             } else {
                 render.clear()?;
                 render.password_prompt_selection(&self.prompt)?;
@@ -109,12 +251,137 @@ impl<'a> Password<'a> {
         }
     }
 
-    fn prompt_password(&self, render: &mut TermThemeRenderer, prompt: &str) -> io::Result<String> {
+    /// Returns the terminal set with [term](#method.term), or stderr.
+    fn target_term(&self) -> Term {
+        self.term.clone().unwrap_or_else(Term::stderr)
+    }
+
+    /// Reads the password, and its confirmation if enabled, as lines from `source`.
+    fn interact_piped(&self, term: &Term, source: &dyn LineSource) -> Result<String> {
+        let read = || -> Result<Zeroizing<String>> {
+            match source.read_line()? {
+                Some(line) if !line.is_empty() || self.allow_empty_password => {
+                    Ok(Zeroizing::new(line))
+                }
+                _ => Err(piped::no_answer_error()),
+            }
+        };
+
+        let password = read()?;
+
+        if let Some((_, ref err)) = self.confirmation_prompt {
+            if *password != *read()? {
+                return Err(piped::invalid_answer_error(err));
+            }
+        }
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.password_prompt_selection(&self.prompt)?;
+        term.flush()?;
+
+        Ok((*password).clone())
+    }
+
+    /// Returns the attempts left after `attempts` failed confirmations.
+    fn attempts_left(&self, attempts: usize) -> Option<usize> {
+        self.max_attempts.map(|max| max.saturating_sub(attempts))
+    }
+
+    /// Reads a password key by key, echoing it while it is revealed.
+    fn read_by_key(
+        &self,
+        render: &mut TermThemeRenderer,
+        prompt: &str,
+        keys: &dyn KeySource,
+    ) -> Result<String> {
+        let term = render.term();
+
+        if !keys.is_scripted() && !term.features().is_attended() && self.timeout.is_none() {
+            return Ok("".into());
+        }
+
+        let mut input = Zeroizing::new(String::new());
+        let mut revealed = false;
+
+        loop {
+            let key = match keys.read_key(term, self.timeout)? {
+                Some(key) => key,
+                None => {
+                    term.clear_line()?;
+                    render.clear()?;
+                    term.flush()?;
+
+                    return Err(timed_out_error());
+                }
+            };
+
+            if key == Key::Unknown {
+                return Err(io::Error::new(io::ErrorKind::NotConnected, "Not a terminal").into());
+            }
+
+            match self.secret_edit(&key) {
+                SecretEdit::Push(chr) => {
+                    input.push(chr);
+
+                    if revealed {
+                        term.write_str(chr.encode_utf8(&mut [0; 4]))?;
+                    }
+                }
+                SecretEdit::Pop => {
+                    if input.pop().is_some() && revealed {
+                        term.clear_chars(1)?;
+                    }
+                }
+                SecretEdit::Toggle => {
+                    revealed = !revealed;
+
+                    term.clear_line()?;
+                    render.password_prompt(prompt)?;
+
+                    if revealed {
+                        term.write_str(&input)?;
+                    }
+                }
+                SecretEdit::Submit => {
+                    term.write_line("")?;
+                    return Ok((*input).clone());
+                }
+                SecretEdit::Ignore => {}
+            }
+
+            term.flush()?;
+        }
+    }
+
+    /// Returns what `key` does while reading a password that can be revealed.
+    fn secret_edit(&self, key: &Key) -> SecretEdit {
+        match *key {
+            ref key if self.allow_reveal && *key == self.reveal_key => SecretEdit::Toggle,
+            Key::Enter => SecretEdit::Submit,
+            Key::Backspace => SecretEdit::Pop,
+            Key::Char(chr) if !chr.is_control() => SecretEdit::Push(chr),
+            _ => SecretEdit::Ignore,
+        }
+    }
+
+    fn prompt_password(
+        &self,
+        render: &mut TermThemeRenderer,
+        prompt: &str,
+        keys: &dyn KeySource,
+    ) -> Result<String> {
         loop {
             render.password_prompt(prompt)?;
             render.term().flush()?;
 
-            let input = render.term().read_secure_line()?;
+            let input = if keys.is_scripted() || self.allow_reveal || self.timeout.is_some() {
+                self.read_by_key(render, prompt, keys)?
+            } else {
+                render.term().read_secure_line()?
+            };
 
             render.add_line();
 
@@ -124,3 +391,97 @@ impl<'a> Password<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::ScriptedKeys;
+
+    #[test]
+    fn test_attempts_left() {
+        let mut password = Password::new();
+        assert_eq!(password.attempts_left(5), None);
+
+        password.max_attempts(3);
+        assert_eq!(password.attempts_left(1), Some(2));
+        assert_eq!(password.attempts_left(3), Some(0));
+
+        password.max_attempts(0);
+        assert_eq!(password.attempts_left(1), Some(0));
+    }
+
+    #[test]
+    fn test_keys_attempts_left() {
+        let term = Term::buffered_stderr();
+        let mut password = Password::new();
+        password
+            .with_prompt("Password")
+            .with_confirmation("Confirm", "Mismatch")
+            .max_attempts(3);
+
+        let mut keys = vec![];
+        for typed in &["ab", "ac", "ab", "ad", "ab", "ae"] {
+            keys.extend(typed.chars().map(Key::Char));
+            keys.push(Key::Enter);
+        }
+
+        let (result, output) =
+            crate::capture_output(|| password.interact_with(&term, &ScriptedKeys::new(&keys)));
+        match result.unwrap_err() {
+            Error::ValidationFailed(message) => assert_eq!(message, "Mismatch"),
+            err => panic!("unexpected error: {}", err),
+        }
+
+        let two_left = output.find("Mismatch (2 attempts left)").unwrap();
+        let one_left = output.find("Mismatch (1 attempt left)").unwrap();
+        assert!(two_left < one_left);
+        assert!(!output.contains("(0 attempts left)"));
+    }
+
+    #[test]
+    fn test_piped() {
+        let term = Term::buffered_stderr();
+        let lines = piped::MockLines::new(&["secret", "secret", "secret", "typo", ""]);
+
+        let mut password = Password::new();
+        password.with_confirmation("Confirm", "Mismatch");
+
+        assert_eq!(password.interact_piped(&term, &lines).unwrap(), "secret");
+
+        match password.interact_piped(&term, &lines).unwrap_err() {
+            Error::ValidationFailed(message) => assert_eq!(message, "Mismatch"),
+            err => panic!("unexpected error: {}", err),
+        }
+
+        let err = password.interact_piped(&term, &lines).unwrap_err();
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let err = Password::new()
+            .timeout(Duration::from_millis(10))
+            .interact_on(&Term::buffered_stderr())
+            .unwrap_err();
+
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_secret_edit() {
+        let mut password = Password::new();
+        assert_eq!(password.secret_edit(&Key::Char('\x12')), SecretEdit::Ignore);
+
+        password.allow_reveal(true);
+
+        assert_eq!(password.secret_edit(&Key::Char('\x12')), SecretEdit::Toggle);
+        assert_eq!(password.secret_edit(&Key::Char('x')), SecretEdit::Push('x'));
+        assert_eq!(password.secret_edit(&Key::Char('\x01')), SecretEdit::Ignore);
+        assert_eq!(password.secret_edit(&Key::Backspace), SecretEdit::Pop);
+        assert_eq!(password.secret_edit(&Key::Enter), SecretEdit::Submit);
+
+        password.reveal_key(Key::Tab);
+        assert_eq!(password.secret_edit(&Key::Tab), SecretEdit::Toggle);
+        assert_eq!(password.secret_edit(&Key::Char('\x12')), SecretEdit::Ignore);
+    }
+}
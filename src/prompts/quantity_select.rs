@@ -0,0 +1,452 @@
+use std::{io, ops::Rem};
+
+use crate::{
+    cursor::HiddenCursor,
+    error::Result,
+    keys::{KeySource, TermKeys},
+    piped::{self, LineSource},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
+
+use console::{Key, Term};
+
+/// Renders a multi select prompt with a quantity per item.
+///
+/// The user moves between items with the up and down arrows, includes items
+/// with the space bar and changes the quantity of the highlighted item with
+/// the left and right arrows or `-` and `+`.
+///
+/// Interaction returns pairs of item index and quantity for all included
+/// items with a nonzero quantity.
+///
+/// ## Example usage
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::QuantitySelect;
+///
+/// let items = vec!["Apples", "Pears"];
+/// let cart: Vec<(usize, u32)> = QuantitySelect::new()
+///     .with_prompt("Fill your cart")
+///     .items(&items)
+///     .interact()?;
+///
+/// for (idx, quantity) in cart {
+///     println!("{} x {}", quantity, items[idx]);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct QuantitySelect<'a> {
+    items: Vec<String>,
+    quantities: Vec<u32>,
+    max_quantity: Option<u32>,
+    prompt: Option<String>,
+    clear: bool,
+    theme: &'a dyn Theme,
+    indent: usize,
+    prompt_icon: Option<String>,
+    wizard_step: Option<(usize, usize)>,
+    term: Option<Term>,
+    truncate_echo: bool,
+}
+
+impl<'a> Default for QuantitySelect<'a> {
+    fn default() -> QuantitySelect<'a> {
+        QuantitySelect::new()
+    }
+}
+
+impl<'a> QuantitySelect<'a> {
+    /// Creates a quantity select prompt.
+    pub fn new() -> QuantitySelect<'a> {
+        QuantitySelect::with_theme(&SimpleTheme)
+    }
+
+    /// Creates a quantity select prompt with a specific theme.
+    pub fn with_theme(theme: &'a dyn Theme) -> QuantitySelect<'a> {
+        QuantitySelect {
+            items: vec![],
+            quantities: vec![],
+            max_quantity: None,
+            prompt: None,
+            clear: true,
+            theme,
+            indent: 0,
+            prompt_icon: None,
+            wizard_step: None,
+            term: None,
+            truncate_echo: false,
+        }
+    }
+
+    /// Sets the clear behavior of the menu.
+    ///
+    /// The default is to clear the menu.
+    pub fn clear(&mut self, val: bool) -> &mut QuantitySelect<'a> {
+        self.clear = val;
+        self
+    }
+
+    /// Indents every rendered line by `val` spaces, see
+    /// [Select::indent](struct.Select.html#method.indent).
+    pub fn indent(&mut self, val: usize) -> &mut QuantitySelect<'a> {
+        self.indent = val;
+        self
+    }
+
+    /// Sets an icon rendered in front of the prompt, see
+    /// [Select::prompt_icon](struct.Select.html#method.prompt_icon).
+    pub fn prompt_icon<S: Into<String>>(&mut self, val: S) -> &mut QuantitySelect<'a> {
+        self.prompt_icon = Some(val.into());
+        self
+    }
+
+    /// Shows the position of the prompt in a sequence of prompts.
+    ///
+    /// The theme renders e.g. `Step 3/7` in front of the prompt and the
+    /// final answer, see
+    /// [Theme::format_step](../theme/trait.Theme.html#method.format_step).
+    pub fn wizard_step(&mut self, current: usize, total: usize) -> &mut QuantitySelect<'a> {
+        self.wizard_step = Some((current, total));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    ///
+    /// The `interact` methods that don't take a terminal use this one
+    /// instead of stderr.
+    pub fn term(&mut self, term: Term) -> &mut QuantitySelect<'a> {
+        self.term = Some(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    ///
+    /// This is the terminal set with [term](#method.term), or stderr.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.target_term().size()
+    }
+
+    /// Returns the terminal set with [term](#method.term), or stderr.
+    fn target_term(&self) -> Term {
+        self.term.clone().unwrap_or_else(Term::stderr)
+    }
+
+    /// Truncates the echoed selections to the width of the terminal.
+    ///
+    /// When many items are picked the echo is cut off with an ellipsis
+    /// instead of wrapping onto the following lines.
+    ///
+    /// Disabled by default.
+    pub fn truncate_echo(&mut self, val: bool) -> &mut QuantitySelect<'a> {
+        self.truncate_echo = val;
+        self
+    }
+
+    /// Sets the highest quantity that can be picked for an item.
+    ///
+    /// By default quantities are unbounded.
+    pub fn max_quantity(&mut self, val: u32) -> &mut QuantitySelect<'a> {
+        self.max_quantity = Some(val);
+        self
+    }
+
+    /// Add a single item to the selector.
+    #[inline]
+    pub fn item<T: ToString>(&mut self, item: T) -> &mut QuantitySelect<'a> {
+        self.item_with_quantity(item, 0)
+    }
+
+    /// Add a single item to the selector with an initial quantity.
+    ///
+    /// Items with a nonzero initial quantity start out included.
+    pub fn item_with_quantity<T: ToString>(
+        &mut self,
+        item: T,
+        quantity: u32,
+    ) -> &mut QuantitySelect<'a> {
+        self.items.push(item.to_string());
+        self.quantities.push(quantity);
+        self
+    }
+
+    /// Adds multiple items to the selector.
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut QuantitySelect<'a> {
+        for item in items {
+            self.items.push(item.to_string());
+            self.quantities.push(0);
+        }
+        self
+    }
+
+    /// Prefaces the menu with a prompt.
+    ///
+    /// When a prompt is set the system also prints out a confirmation after
+    /// the selection.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut QuantitySelect<'a> {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// On enter the index and quantity of every included item with a
+    /// nonzero quantity is returned.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact(&self) -> Result<Vec<(usize, u32)>> {
+        self.interact_on(&self.target_term())
+    }
+
+    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<Vec<(usize, u32)>> {
+        self.interact_with(term, &TermKeys)
+    }
+
+    fn interact_with(&self, term: &Term, keys: &dyn KeySource) -> Result<Vec<(usize, u32)>> {
+        if self.items.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of items given to `QuantitySelect`",
+            )
+            .into());
+        }
+
+        if !keys.is_scripted() && piped::is_piped(term) {
+            return self.interact_piped(term, &piped::Stdin);
+        }
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_truncate_echo(self.truncate_echo);
+        let _cursor = HiddenCursor::new(term)?;
+        let mut sel = 0;
+
+        if let Some(ref prompt) = self.prompt {
+            render.quantity_select_prompt(prompt)?;
+        }
+
+        let size_vec: Vec<_> = self.items.iter().map(|item| item.len()).collect();
+
+        let mut quantities = self.quantities.clone();
+        let mut checked: Vec<bool> = quantities.iter().map(|&q| q > 0).collect();
+
+        loop {
+            for (idx, item) in self.items.iter().enumerate() {
+                render.quantity_select_prompt_item(
+                    item,
+                    quantities[idx],
+                    checked[idx],
+                    sel == idx,
+                )?;
+            }
+
+            term.flush()?;
+
+            // Without a timeout a key is always read.
+            match keys.read_key(term, None)?.unwrap_or(Key::Unknown) {
+                Key::ArrowDown | Key::Char('j') => {
+                    sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
+                }
+                Key::ArrowUp | Key::Char('k') => {
+                    sel = ((sel as i64 - 1 + self.items.len() as i64) % (self.items.len() as i64))
+                        as usize;
+                }
+                Key::ArrowRight | Key::Char('l') | Key::Char('+')
+                    if self.max_quantity.map_or(true, |max| quantities[sel] < max) =>
+                {
+                    quantities[sel] += 1;
+                    checked[sel] = true;
+                }
+                Key::ArrowLeft | Key::Char('h') | Key::Char('-') => {
+                    quantities[sel] = quantities[sel].saturating_sub(1);
+                }
+                Key::Char(' ') => {
+                    checked[sel] = !checked[sel];
+
+                    if checked[sel] && quantities[sel] == 0 {
+                        quantities[sel] = 1;
+                    }
+                }
+                Key::Enter => {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    let rv = included(&checked, &quantities);
+
+                    if let Some(ref prompt) = self.prompt {
+                        let selections: Vec<_> = rv
+                            .iter()
+                            .map(|&(idx, quantity)| (self.items[idx].as_str(), quantity))
+                            .collect();
+
+                        render.quantity_select_prompt_selection(prompt, &selections[..])?;
+                    }
+
+                    term.flush()?;
+
+                    return Ok(rv);
+                }
+                Key::Unknown => {
+                    return Err(
+                        io::Error::new(io::ErrorKind::NotConnected, "Not a terminal").into(),
+                    );
+                }
+                _ => {}
+            }
+
+            render.clear_preserve_prompt(&size_vec)?;
+        }
+    }
+
+    /// Reads a comma separated list of item indices or texts from `source`,
+    /// each optionally followed by `=` and a quantity.
+    ///
+    /// Items without a quantity are included once. A blank answer picks the
+    /// initial quantities.
+    fn interact_piped(&self, term: &Term, source: &dyn LineSource) -> Result<Vec<(usize, u32)>> {
+        let quantities = match piped::read_answer(source)? {
+            Some(answer) => match self.parse_quantities(&answer) {
+                Some(quantities) => quantities,
+                None => {
+                    return Err(piped::invalid_answer_error(&format!(
+                        "Invalid items: {}",
+                        answer
+                    )))
+                }
+            },
+            None => self.quantities.clone(),
+        };
+
+        let checked: Vec<bool> = quantities.iter().map(|&q| q > 0).collect();
+        let rv = included(&checked, &quantities);
+
+        if let Some(ref prompt) = self.prompt {
+            let mut render = TermThemeRenderer::new(term, self.theme);
+            render.set_indent(self.indent);
+            render.set_prompt_icon(self.prompt_icon.as_deref());
+            render.set_step(self.wizard_step);
+            render.set_truncate_echo(self.truncate_echo);
+
+            let selections: Vec<_> = rv
+                .iter()
+                .map(|&(idx, quantity)| (self.items[idx].as_str(), quantity))
+                .collect();
+
+            render.quantity_select_prompt_selection(prompt, &selections[..])?;
+        }
+
+        term.flush()?;
+
+        Ok(rv)
+    }
+
+    /// Parses a piped answer into the quantities of all items.
+    fn parse_quantities(&self, answer: &str) -> Option<Vec<u32>> {
+        let mut quantities = vec![0; self.items.len()];
+
+        for part in answer
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+        {
+            let (item, quantity) = match part.rfind('=') {
+                Some(pos) => (part[..pos].trim(), part[pos + 1..].trim().parse().ok()?),
+                None => (part, 1),
+            };
+
+            if self.max_quantity.map_or(false, |max| quantity > max) {
+                return None;
+            }
+
+            quantities[piped::parse_index(item, &self.items)?] = quantity;
+        }
+
+        Some(quantities)
+    }
+}
+
+/// Collects the index and quantity of included items with a nonzero quantity.
+fn included(checked: &[bool], quantities: &[u32]) -> Vec<(usize, u32)> {
+    checked
+        .iter()
+        .zip(quantities)
+        .enumerate()
+        .filter_map(|(idx, (&checked, &quantity))| {
+            if checked && quantity > 0 {
+                Some((idx, quantity))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::ScriptedKeys;
+
+    #[test]
+    fn test_keys() {
+        let term = Term::buffered_stderr();
+        let mut select = QuantitySelect::new();
+        select.items(&["Apples", "Pears"]).max_quantity(2);
+
+        let keys = [
+            Key::Char(' '),
+            Key::ArrowDown,
+            Key::Char('+'),
+            Key::Char('+'),
+            Key::Char('+'),
+            Key::Enter,
+        ];
+        assert_eq!(
+            select
+                .interact_with(&term, &ScriptedKeys::new(&keys))
+                .unwrap(),
+            vec![(0, 1), (1, 2)]
+        );
+
+        let keys = ScriptedKeys::new(&[Key::ArrowDown, Key::Unknown]);
+        let err = select.interact_with(&term, &keys).unwrap_err();
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::NotConnected);
+    }
+
+    #[test]
+    fn test_item_with_quantity() {
+        let mut select = QuantitySelect::new();
+        select.item("a").item_with_quantity("b", 3);
+
+        assert_eq!(select.quantities, vec![0, 3]);
+    }
+
+    #[test]
+    fn test_included() {
+        let checked = [true, false, true, true];
+        let quantities = [2, 5, 0, 1];
+
+        assert_eq!(included(&checked, &quantities), vec![(0, 2), (3, 1)]);
+    }
+
+    #[test]
+    fn test_piped() {
+        let term = Term::buffered_stderr();
+        let lines = piped::MockLines::new(&["pears = 2, 0", "", "Plums", "1=4"]);
+        let mut select = QuantitySelect::new();
+        select
+            .item("Apples")
+            .item_with_quantity("Pears", 3)
+            .max_quantity(3);
+
+        assert_eq!(
+            select.interact_piped(&term, &lines).unwrap(),
+            vec![(0, 1), (1, 2)]
+        );
+        assert_eq!(select.interact_piped(&term, &lines).unwrap(), vec![(1, 3)]);
+        assert!(select.interact_piped(&term, &lines).is_err());
+        assert!(select.interact_piped(&term, &lines).is_err());
+    }
+}
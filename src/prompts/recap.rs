@@ -0,0 +1,258 @@
+use std::io;
+
+use crate::{
+    error::{Error, Result},
+    keys::{KeySource, TermKeys},
+    prompts::{confirm::Confirm, select::Select},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
+
+use console::{measure_text_width, Term};
+
+/// The outcome of a recap that allows editing.
+///
+/// Returned by [Recap::interact_edit](struct.Recap.html#method.interact_edit).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecapOutcome {
+    /// The user submitted the answers.
+    Submit,
+    /// The user wants to change the answer at this index.
+    Edit(usize),
+}
+
+/// Renders a recap of the answers given in a multi step form.
+///
+/// The answers are listed as aligned label and value pairs followed by a
+/// confirm prompt asking whether to submit them.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Input, Recap, RecapOutcome};
+///
+/// let mut name: String = Input::new().with_prompt("Name").interact_text()?;
+/// let mut email: String = Input::new().with_prompt("Email").interact_text()?;
+///
+/// loop {
+///     let outcome = Recap::new()
+///         .item("Name", &name)
+///         .item("Email", &email)
+///         .interact_edit()?;
+///
+///     match outcome {
+///         RecapOutcome::Submit => break,
+///         RecapOutcome::Edit(0) => name = Input::new().with_prompt("Name").interact_text()?,
+///         RecapOutcome::Edit(_) => email = Input::new().with_prompt("Email").interact_text()?,
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Recap<'a> {
+    items: Vec<(String, String)>,
+    prompt: String,
+    edit_prompt: String,
+    theme: &'a dyn Theme,
+    indent: usize,
+    term: Option<Term>,
+}
+
+impl<'a> Default for Recap<'a> {
+    fn default() -> Recap<'a> {
+        Recap::new()
+    }
+}
+
+impl<'a> Recap<'a> {
+    /// Creates a recap.
+    pub fn new() -> Recap<'a> {
+        Recap::with_theme(&SimpleTheme)
+    }
+
+    /// Creates a recap with a specific theme.
+    pub fn with_theme(theme: &'a dyn Theme) -> Recap<'a> {
+        Recap {
+            items: vec![],
+            prompt: "Submit?".into(),
+            edit_prompt: "Which answer do you want to change?".into(),
+            theme,
+            indent: 0,
+            term: None,
+        }
+    }
+
+    /// Adds an answer to the recap.
+    pub fn item<L: ToString, V: ToString>(&mut self, label: L, value: V) -> &mut Recap<'a> {
+        self.items.push((label.to_string(), value.to_string()));
+        self
+    }
+
+    /// Adds multiple answers to the recap.
+    pub fn items<L: ToString, V: ToString>(&mut self, items: &[(L, V)]) -> &mut Recap<'a> {
+        for (label, value) in items {
+            self.item(label.to_string(), value.to_string());
+        }
+        self
+    }
+
+    /// Sets the confirm prompt shown below the answers.
+    ///
+    /// The default prompt is `Submit?`.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Recap<'a> {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Sets the prompt asking which answer to change.
+    ///
+    /// This is only used by [interact_edit](#method.interact_edit).
+    pub fn with_edit_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Recap<'a> {
+        self.edit_prompt = prompt.into();
+        self
+    }
+
+    /// Indents every rendered line by `val` spaces, see
+    /// [Select::indent](struct.Select.html#method.indent).
+    pub fn indent(&mut self, val: usize) -> &mut Recap<'a> {
+        self.indent = val;
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    ///
+    /// The `interact` methods that don't take a terminal use this one
+    /// instead of stderr.
+    pub fn term(&mut self, term: Term) -> &mut Recap<'a> {
+        self.term = Some(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    ///
+    /// This is the terminal set with [term](#method.term), or stderr.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.target_term().size()
+    }
+
+    /// Shows the answers and asks whether to submit them.
+    ///
+    /// The result is `true` if the user confirms.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact(&self) -> Result<bool> {
+        self.interact_on(&self.target_term())
+    }
+
+    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> Result<bool> {
+        self.interact_with(term, &TermKeys)
+    }
+
+    /// Shows the answers and asks whether to submit them or which to change.
+    ///
+    /// If the user declines to submit, a select prompt with the labels asks
+    /// for the answer to change.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_edit(&self) -> Result<RecapOutcome> {
+        self.interact_edit_on(&self.target_term())
+    }
+
+    /// Like [interact_edit](#method.interact_edit) but allows a specific terminal to be set.
+    pub fn interact_edit_on(&self, term: &Term) -> Result<RecapOutcome> {
+        self.interact_edit_with(term, &TermKeys)
+    }
+
+    fn interact_with(&self, term: &Term, keys: &dyn KeySource) -> Result<bool> {
+        self.render_items(term)?;
+
+        Confirm::with_theme(self.theme)
+            .with_prompt(self.prompt.as_str())
+            .indent(self.indent)
+            ._interact_on(term, false, keys)?
+            .ok_or(Error::Cancelled)
+    }
+
+    fn interact_edit_with(&self, term: &Term, keys: &dyn KeySource) -> Result<RecapOutcome> {
+        if self.interact_with(term, keys)? {
+            return Ok(RecapOutcome::Submit);
+        }
+
+        let labels: Vec<&str> = self.items.iter().map(|(label, _)| label.as_str()).collect();
+
+        Select::with_theme(self.theme)
+            .with_prompt(self.edit_prompt.as_str())
+            .items(&labels)
+            .default(0)
+            .indent(self.indent)
+            ._interact_on(term, false, keys)?
+            .map(|outcome| RecapOutcome::Edit(outcome.index))
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Returns the terminal set with [term](#method.term), or stderr.
+    fn target_term(&self) -> Term {
+        self.term.clone().unwrap_or_else(Term::stderr)
+    }
+
+    /// Renders the answers as aligned lines.
+    fn render_items(&self, term: &Term) -> io::Result<()> {
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+
+        let label_width = self.label_width();
+
+        for (label, value) in &self.items {
+            render.recap_item(label, value, label_width)?;
+        }
+
+        term.flush()
+    }
+
+    /// Returns the width of the widest label.
+    fn label_width(&self) -> usize {
+        self.items
+            .iter()
+            .map(|(label, _)| measure_text_width(label))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::keys::ScriptedKeys;
+    use console::Key;
+
+    #[test]
+    fn test_label_width() {
+        let mut recap = Recap::new();
+        assert_eq!(recap.label_width(), 0);
+
+        recap.items(&[("Name", "Ferris"), ("Language", "Rust")]);
+        assert_eq!(recap.label_width(), 8);
+    }
+
+    #[test]
+    fn test_keys_outcome() {
+        let term = Term::buffered_stderr();
+        let mut recap = Recap::new();
+        recap.items(&[("Name", "Ferris"), ("Language", "Rust")]);
+
+        let keys = ScriptedKeys::new(&[Key::Char('y')]);
+        let (outcome, output) = crate::capture_output(|| recap.interact_edit_with(&term, &keys));
+        assert_eq!(outcome.unwrap(), RecapOutcome::Submit);
+        assert!(output.contains("Ferris"));
+        assert!(output.contains("Rust"));
+
+        let keys = ScriptedKeys::new(&[Key::Char('n'), Key::ArrowDown, Key::Enter]);
+        assert_eq!(
+            recap.interact_edit_with(&term, &keys).unwrap(),
+            RecapOutcome::Edit(1)
+        );
+
+        let keys = ScriptedKeys::new(&[Key::Char('n')]);
+        assert!(!recap.interact_with(&term, &keys).unwrap());
+    }
+}
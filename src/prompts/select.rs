@@ -1,8 +1,63 @@
-use std::{io, ops::Rem};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    io,
+    ops::Rem,
+    time::{Duration, Instant},
+};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::{
+    cursor::HiddenCursor,
+    error::{Error, Result},
+    keys::{CountingKeys, KeyAction, KeyBindings, KeySource, ScriptedKeys, TermKeys},
+    memory::SelectionMemory,
+    piped::{self, LineSource},
+    theme::{shortcut_label, SimpleTheme, TermThemeRenderer, Theme},
+    timeout::timed_out_error,
+    validate::Validator,
+};
 
-use console::{Key, Term};
+use console::{measure_text_width, strip_ansi_codes, Key, Term};
+
+type PageProvider<'a> = Box<dyn Fn(usize, usize) -> Vec<String> + 'a>;
+type ValidatorCallback<'a> = Box<dyn Fn(&usize) -> Option<String> + 'a>;
+
+/// The outcome of a select prompt with details about the interaction.
+///
+/// Returned by [Select::interact_detailed](struct.Select.html#method.interact_detailed).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectionOutcome {
+    /// Index of the selected item.
+    pub index: usize,
+    /// Whether the preselected item was accepted without navigating.
+    pub was_default: bool,
+    /// The key that confirmed the selection.
+    pub used_key: Key,
+}
+
+/// Measurements of how a prompt was answered.
+///
+/// Returned by [Select::interact_with_report](struct.Select.html#method.interact_with_report).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InteractionReport {
+    /// Time from showing the prompt until it was answered.
+    pub duration: Duration,
+    /// Number of keys pressed, including the confirming one.
+    pub keystrokes: usize,
+}
+
+/// Where the descriptions of select prompt items are rendered.
+///
+/// See [Select::description_layout](struct.Select.html#method.description_layout).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DescriptionLayout {
+    /// Inline if the terminal is wide enough, below the active item otherwise.
+    Auto,
+    /// In a column to the right of the items.
+    Inline,
+    /// On a line below the active item.
+    BelowActive,
+}
 
 /// Renders a select prompt.
 ///
@@ -35,11 +90,41 @@ use console::{Key, Term};
 /// ```
 pub struct Select<'a> {
     default: usize,
-    items: Vec<String>,
+    items: Vec<Cow<'a, str>>,
+    disabled: Vec<bool>,
+    descriptions: Vec<Option<String>>,
+    shortcuts: Vec<Option<char>>,
+    description_layout: DescriptionLayout,
     prompt: Option<String>,
     clear: bool,
     theme: &'a dyn Theme,
+    indent: usize,
+    prompt_icon: Option<String>,
+    wizard_step: Option<(usize, usize)>,
+    term: Option<Term>,
+    truncate_echo: bool,
+    truncate_items: bool,
     paged: bool,
+    max_length: Option<usize>,
+    max_height: Option<usize>,
+    viewport: Option<usize>,
+    timeout: Option<Duration>,
+    memory: Option<(String, RefCell<&'a mut dyn SelectionMemory>)>,
+    fuzzy: bool,
+    pre_styled: bool,
+    key_bindings: KeyBindings,
+    report: bool,
+    wrap: bool,
+    bell: bool,
+    typed_confirmation: Vec<usize>,
+    sink_disabled: bool,
+    no_items_message: Option<String>,
+    help_toggle_key: Option<char>,
+    header: Option<String>,
+    group_headers: Vec<(usize, String)>,
+    validator: Option<ValidatorCallback<'a>>,
+    footer: Option<String>,
+    page_provider: Option<PageProvider<'a>>,
 }
 
 impl<'a> Default for Select<'a> {
@@ -50,7 +135,7 @@ impl<'a> Default for Select<'a> {
 
 impl<'a> Select<'a> {
     /// Creates a select prompt builder with default theme.
-    pub fn new() -> Select<'static> {
+    pub fn new() -> Select<'a> {
         Select::with_theme(&SimpleTheme)
     }
 
@@ -76,21 +161,208 @@ impl<'a> Select<'a> {
         Select {
             default: !0,
             items: vec![],
+            disabled: vec![],
+            descriptions: vec![],
+            shortcuts: vec![],
+            description_layout: DescriptionLayout::Auto,
             prompt: None,
             clear: true,
             theme,
+            indent: 0,
+            prompt_icon: None,
+            wizard_step: None,
+            term: None,
+            truncate_echo: false,
+            truncate_items: false,
             paged: false,
+            max_length: None,
+            max_height: None,
+            viewport: None,
+            timeout: None,
+            memory: None,
+            fuzzy: false,
+            pre_styled: false,
+            key_bindings: KeyBindings::vim(),
+            report: true,
+            wrap: true,
+            bell: false,
+            typed_confirmation: vec![],
+            sink_disabled: false,
+            no_items_message: None,
+            help_toggle_key: None,
+            header: None,
+            group_headers: vec![],
+            validator: None,
+            footer: None,
+            page_provider: None,
         }
     }
 
     /// Enables or disables paging
     ///
-    /// Paging is disabled by default
+    /// Paging is disabled by default. While there is more than one page the
+    /// current page is shown below the items, see
+    /// [Theme::format_select_prompt_page](trait.Theme.html#method.format_select_prompt_page).
     pub fn paged(&mut self, val: bool) -> &mut Select<'a> {
         self.paged = val;
         self
     }
 
+    /// Limits the number of items shown per page when paging is enabled.
+    ///
+    /// By default a page takes up the whole height of the terminal. With a
+    /// maximum length set a page holds at most `val` items, but never more
+    /// than fit into the terminal.
+    pub fn max_length(&mut self, val: usize) -> &mut Select<'a> {
+        self.max_length = Some(val);
+        self
+    }
+
+    /// Caps the total height of the prompt at `val` lines.
+    ///
+    /// The prompt line stays in place while the items scroll line by line
+    /// to keep the active one visible, and the help stays below them.
+    /// Unlike [max_length](#method.max_length) this also accounts for the
+    /// lines taken by the help and descriptions.
+    ///
+    /// Other list prompts, e.g. Sort, don't support this yet.
+    pub fn max_height(&mut self, val: usize) -> &mut Select<'a> {
+        self.max_height = Some(val);
+        self
+    }
+
+    /// Shows a scrolling window of `val` items around the selection.
+    ///
+    /// Instead of flipping whole pages, the window moves one item at a time
+    /// so the selection stays centered, except near the start and the end
+    /// of the list. The window never grows beyond what fits into the
+    /// terminal. While a viewport is set no page indicator is shown and
+    /// [max_length](#method.max_length) doesn't apply.
+    pub fn viewport(&mut self, val: usize) -> &mut Select<'a> {
+        self.viewport = Some(val);
+        self
+    }
+
+    /// Enables or disables fuzzy filtering
+    ///
+    /// When enabled, typed characters filter the items by a case-insensitive
+    /// fuzzy match and backspace removes the last character of the filter.
+    /// Navigation only moves between the matching items and the returned
+    /// index still refers to the position in the full list of items. The
+    /// vim-style navigation keys and space are taken as filter input in
+    /// this mode.
+    ///
+    /// Fuzzy filtering is disabled by default
+    pub fn fuzzy(&mut self, val: bool) -> &mut Select<'a> {
+        self.fuzzy = val;
+        self
+    }
+
+    /// Renders disabled items after all enabled ones
+    ///
+    /// This keeps the selectable items together regardless of the order
+    /// they were added in. The returned index still refers to the position
+    /// the item was added at.
+    ///
+    /// Disabled items are rendered in place by default
+    pub fn sink_disabled(&mut self, val: bool) -> &mut Select<'a> {
+        self.sink_disabled = val;
+        self
+    }
+
+    /// Enables the key binding help, shown and hidden with `val`.
+    ///
+    /// The help is hidden initially and shown below the items once the key
+    /// is pressed, e.g. `Some('?')`. The key can't be used for jumping to
+    /// items or as filter input, and keys bound to an action, like the
+    /// vim-style keys, take precedence over it.
+    ///
+    /// Disabled by default.
+    pub fn help_toggle_key(&mut self, val: Option<char>) -> &mut Select<'a> {
+        self.help_toggle_key = val;
+        self
+    }
+
+    /// Sets a line rendered between the prompt and the items.
+    ///
+    /// The line is styled by the theme and kept across redraws.
+    pub fn header<S: Into<String>>(&mut self, val: S) -> &mut Select<'a> {
+        self.header = Some(val.into());
+        self
+    }
+
+    /// Sets a line rendered below the items, e.g. a legend of the keys.
+    ///
+    /// The line is styled by the theme and kept across redraws.
+    pub fn footer<S: Into<String>>(&mut self, val: S) -> &mut Select<'a> {
+        self.footer = Some(val.into());
+        self
+    }
+
+    /// Enables or disables the vim-style navigation keys
+    ///
+    /// With the keys enabled `j`, `k`, `h` and `l` move the selection and
+    /// `q` quits the prompt where that is allowed. Any other printable
+    /// character jumps to the next item starting with it. Disabling the
+    /// keys makes every letter available for jumping.
+    ///
+    /// Vim-style keys are enabled by default. This is a shortcut for the
+    /// [KeyBindings::vim](struct.KeyBindings.html#method.vim) and
+    /// [KeyBindings::arrows_only](struct.KeyBindings.html#method.arrows_only)
+    /// presets.
+    pub fn vim_keys(&mut self, val: bool) -> &mut Select<'a> {
+        self.key_bindings = if val {
+            KeyBindings::vim()
+        } else {
+            KeyBindings::arrows_only()
+        };
+        self
+    }
+
+    /// Sets the keys bound to moving, confirming and cancelling
+    ///
+    /// Character bindings are ignored in fuzzy mode, where characters are
+    /// typed into the filter instead.
+    pub fn key_bindings(&mut self, val: KeyBindings) -> &mut Select<'a> {
+        self.key_bindings = val;
+        self
+    }
+
+    /// Indicates whether the selection wraps around at the ends
+    ///
+    /// With wrapping disabled moving up from the first item or down from
+    /// the last one keeps the selection where it is.
+    ///
+    /// Wrapping is enabled by default
+    pub fn wrap(&mut self, val: bool) -> &mut Select<'a> {
+        self.wrap = val;
+        self
+    }
+
+    /// Indicates whether the terminal bell rings when a key is rejected
+    ///
+    /// The bell rings on keys that don't do anything, e.g. moving past the
+    /// end without wrapping or typing a character no item starts with.
+    ///
+    /// The bell is disabled by default
+    pub fn bell(&mut self, val: bool) -> &mut Select<'a> {
+        self.bell = val;
+        self
+    }
+
+    /// Indicates whether the items already contain styling
+    ///
+    /// Pre-styled items, e.g. the output of a syntax highlighter, are
+    /// rendered verbatim next to the active item marker instead of being
+    /// styled by the theme. ANSI codes are ignored when measuring the width
+    /// of items either way.
+    ///
+    /// Items are not pre-styled by default
+    pub fn pre_styled_items(&mut self, val: bool) -> &mut Select<'a> {
+        self.pre_styled = val;
+        self
+    }
+
     /// Indicates whether select menu should be ereased from the screen after interaction.
     ///
     /// The default is to clear the menu.
@@ -99,14 +371,164 @@ impl<'a> Select<'a> {
         self
     }
 
+    /// Indicates whether the selection is printed after interaction.
+    ///
+    /// This is independent of [clear](#method.clear), so disabling both
+    /// leaves the menu on screen without a line repeating the answer and
+    /// disabling only the report erases the prompt without a trace. The
+    /// selection is only printed for prompts with a text.
+    ///
+    /// The default is to print the selection.
+    pub fn report(&mut self, val: bool) -> &mut Select<'a> {
+        self.report = val;
+        self
+    }
+
+    /// Indents every rendered line by `val` spaces.
+    ///
+    /// This applies to the prompt, the items, help, errors and the final
+    /// selection alike, which is useful to visually nest prompts under a
+    /// wizard step. All prompts support this in the same way.
+    pub fn indent(&mut self, val: usize) -> &mut Select<'a> {
+        self.indent = val;
+        self
+    }
+
+    /// Sets an icon, e.g. an emoji, that is rendered in front of the prompt.
+    ///
+    /// The icon precedes the theme's prefix on the prompt and the final
+    /// selection. This is useful to tell the steps of a wizard apart.
+    pub fn prompt_icon<S: Into<String>>(&mut self, val: S) -> &mut Select<'a> {
+        self.prompt_icon = Some(val.into());
+        self
+    }
+
+    /// Shows the position of the prompt in a sequence of prompts.
+    ///
+    /// The theme renders e.g. `Step 3/7` in front of the prompt and the
+    /// final selection, see
+    /// [Theme::format_step](../theme/trait.Theme.html#method.format_step).
+    pub fn wizard_step(&mut self, current: usize, total: usize) -> &mut Select<'a> {
+        self.wizard_step = Some((current, total));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    ///
+    /// The `interact` methods that don't take a terminal use this one
+    /// instead of stderr.
+    pub fn term(&mut self, term: Term) -> &mut Select<'a> {
+        self.term = Some(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    ///
+    /// This is the terminal set with [term](#method.term), or stderr.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.target_term().size()
+    }
+
+    /// Truncates the echoed selection to the width of the terminal.
+    ///
+    /// Long items are cut off with an ellipsis instead of wrapping onto
+    /// the following lines.
+    ///
+    /// Disabled by default.
+    pub fn truncate_echo(&mut self, val: bool) -> &mut Select<'a> {
+        self.truncate_echo = val;
+        self
+    }
+
+    /// Truncates items to the width of the terminal.
+    ///
+    /// Long items are cut off with an ellipsis instead of wrapping onto
+    /// the following lines. Wrapped items are still cleared correctly on
+    /// redraws.
+    ///
+    /// Disabled by default.
+    pub fn truncate_items(&mut self, val: bool) -> &mut Select<'a> {
+        self.truncate_items = val;
+        self
+    }
+
+    /// Requires the user to type the name of these items to select them.
+    ///
+    /// This is meant as a safeguard for destructive choices. Selecting one
+    /// of the items replaces the menu with a prompt asking for the item's
+    /// text, and the selection is only returned if it is typed exactly. On
+    /// a mismatch or empty input the user is taken back to the menu.
+    ///
+    /// Elements are indicated by the index at which they appear in `item` method invocation or `items` slice.
+    pub fn require_typed_confirmation(&mut self, indices: &[usize]) -> &mut Select<'a> {
+        self.typed_confirmation.extend_from_slice(indices);
+        self
+    }
+
+    /// Sets the message shown when there are no items to select from.
+    ///
+    /// The message is rendered as an error and interaction ends right away.
+    /// [interact_opt](#method.interact_opt) returns `None` in that case,
+    /// the other methods return an error carrying the message.
+    pub fn no_items_message<S: Into<String>>(&mut self, val: S) -> &mut Select<'a> {
+        self.no_items_message = Some(val.into());
+        self
+    }
+
     /// Sets initial selected element when select menu is rendered
     ///
     /// Element is indicated by the index at which it appears in `item` method invocation or `items` slice.
+    /// Without a default the first enabled item is highlighted, but picking
+    /// it is not reported as picking the default.
     pub fn default(&mut self, val: usize) -> &mut Select<'a> {
         self.default = val;
         self
     }
 
+    /// Sets a timeout after which the preselected item is picked.
+    ///
+    /// The timeout restarts with every key press. If no key is pressed in
+    /// time the [default](#method.default), or the item recalled from the
+    /// [memory](#method.with_memory), is picked regardless of the item that
+    /// is currently active. An error of kind `TimedOut` is returned when
+    /// there is no such item. The `used_key` of a
+    /// [detailed outcome](#method.interact_detailed) is `Key::Unknown` then.
+    pub fn timeout(&mut self, val: Duration) -> &mut Select<'a> {
+        self.timeout = Some(val);
+        self
+    }
+
+    /// Remembers the selection across runs in a memory.
+    ///
+    /// The index last stored under `key` is preselected instead of the
+    /// [default](#method.default) if it is still in range, and the new
+    /// choice is stored again once the user confirms a selection.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use std::collections::HashMap;
+    /// use dialoguer::Select;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut memory = HashMap::new();
+    ///     let selection = Select::new()
+    ///         .item("Option A")
+    ///         .item("Option B")
+    ///         .with_memory("option", &mut memory)
+    ///         .interact()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_memory<S: Into<String>, M: SelectionMemory>(
+        &mut self,
+        key: S,
+        memory: &'a mut M,
+    ) -> &mut Select<'a> {
+        self.memory = Some((key.into(), RefCell::new(memory)));
+        self
+    }
+
     /// Add a single item to the selector.
     ///
     /// ## Examples
@@ -123,38 +545,125 @@ impl<'a> Select<'a> {
     /// }
     /// ```
     pub fn item<T: ToString>(&mut self, item: T) -> &mut Select<'a> {
-        self.items.push(item.to_string());
-        self
+        self.item_disabled(item, false)
     }
 
-    /// Adds multiple items to the selector.
+    /// Add a single item that can be disabled.
+    ///
+    /// Disabled items are rendered, usually dimmed, but are skipped when
+    /// navigating and can never be selected. This is useful for section
+    /// headers or options that are currently unavailable.
+    pub fn item_disabled<T: ToString>(&mut self, item: T, disabled: bool) -> &mut Select<'a> {
+        self.push_item(Cow::Owned(item.to_string()), disabled)
+    }
+
+    /// Add a single item that is picked right away by pressing `key`.
+    ///
+    /// The key is shown in front of the item, see
+    /// [Theme::format_select_prompt_shortcut](../theme/trait.Theme.html#method.format_select_prompt_shortcut).
+    /// Keys bound to an action with [key_bindings](#method.key_bindings)
+    /// and the [help toggle key](#method.help_toggle_key) can't be used as
+    /// shortcuts, so set those first. Shortcuts of disabled items are
+    /// ignored and shortcuts don't apply while [fuzzy](#method.fuzzy)
+    /// filtering is enabled.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `key` is already the shortcut of another item, or if it is
+    /// bound to an action or toggles the help, e.g. `j` or `q` with the
+    /// default bindings.
     ///
     /// ## Examples
     /// ```rust,no_run
     /// use dialoguer::Select;
     ///
     /// fn main() -> std::io::Result<()> {
-    ///     let items = vec!["Item 1", "Item 2"];
-    ///     let selection: usize = Select::new()
-    ///         .items(&items)
+    ///     let selection = Select::new()
+    ///         .item_with_key('n', "New file")
+    ///         .item_with_key('o', "Open file")
     ///         .interact()?;
     ///
-    ///     println!("{}", items[selection]);
-    ///
     ///     Ok(())
     /// }
     /// ```
-    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Select<'a> {
+    pub fn item_with_key<T: ToString>(&mut self, key: char, item: T) -> &mut Select<'a> {
+        assert!(
+            !self.shortcuts.contains(&Some(key)),
+            "shortcut {:?} is already used by another item",
+            key
+        );
+        assert!(
+            self.key_bindings.action(&Key::Char(key)).is_none()
+                && self.help_toggle_key != Some(key),
+            "shortcut {:?} is already bound to an action",
+            key
+        );
+
+        self.item(item);
+        if let Some(shortcut) = self.shortcuts.last_mut() {
+            *shortcut = Some(key);
+        }
+        self
+    }
+
+    /// Add a single item without copying it.
+    ///
+    /// The item is borrowed for the lifetime of the prompt, which avoids
+    /// allocating large menus built from constants.
+    pub fn item_borrowed(&mut self, item: &'a str) -> &mut Select<'a> {
+        self.push_item(Cow::Borrowed(item), false)
+    }
+
+    /// Adds multiple items without copying them.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    ///
+    /// const FRUITS: &[&str] = &["Apple", "Banana", "Cherry"];
+    ///
+    /// fn main() {
+    ///     let selection = Select::new()
+    ///         .items_borrowed(FRUITS)
+    ///         .interact()
+    ///         .unwrap();
+    ///
+    ///     println!("You selected {}", FRUITS[selection]);
+    /// }
+    /// ```
+    pub fn items_borrowed(&mut self, items: &[&'a str]) -> &mut Select<'a> {
         for item in items {
-            self.items.push(item.to_string());
+            self.item_borrowed(item);
         }
         self
     }
 
-    /// Sets the select prompt.
+    /// Returns the terminal set with [term](#method.term), or stderr.
+    pub(crate) fn target_term(&self) -> Term {
+        self.term.clone().unwrap_or_else(Term::stderr)
+    }
+
+    /// Returns the error of the validators for the item at `index`, if any.
+    fn validation_error(&self, index: usize) -> Option<String> {
+        self.validator
+            .as_ref()
+            .and_then(|validator| validator(&index))
+    }
+
+    /// Adds an item, owned or borrowed.
+    fn push_item(&mut self, item: Cow<'a, str>, disabled: bool) -> &mut Select<'a> {
+        self.items.push(item);
+        self.disabled.push(disabled);
+        self.descriptions.push(None);
+        self.shortcuts.push(None);
+        self
+    }
+
+    /// Add a single item with a description.
     ///
-    /// When a prompt is set the system also prints out a confirmation after
-    /// the selection.
+    /// The description is rendered next to the item or below it while it
+    /// is active, see [description_layout](#method.description_layout).
     ///
     /// ## Examples
     /// ```rust,no_run
@@ -162,217 +671,2244 @@ impl<'a> Select<'a> {
     ///
     /// fn main() -> std::io::Result<()> {
     ///     let selection = Select::new()
-    ///         .with_prompt("Which option do you prefer?")
-    ///         .item("Option A")
-    ///         .item("Option B")
+    ///         .item_with_description("debug", "Fast builds with debug info")
+    ///         .item_with_description("release", "Optimized builds")
     ///         .interact()?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Select<'a> {
-        self.prompt = Some(prompt.into());
+    pub fn item_with_description<T: ToString, D: ToString>(
+        &mut self,
+        item: T,
+        description: D,
+    ) -> &mut Select<'a> {
+        self.item(item);
+
+        if let Some(last) = self.descriptions.last_mut() {
+            *last = Some(description.to_string());
+        }
+
         self
     }
 
-    /// Enables user interaction and returns the result.
+    /// Sets where item descriptions are rendered.
     ///
-    /// Similar to [interact_on](#method.interact_on) except for the fact that it does not allow selection of the terminal.
-    /// The dialog is rendered on stderr.
-    /// Result contains index of a selected item.
-    pub fn interact(&self) -> io::Result<usize> {
-        self.interact_on(&Term::stderr())
-    }
-
-    /// Enables user interaction and returns the result.
+    /// With [DescriptionLayout::Auto](enum.DescriptionLayout.html) the
+    /// descriptions are shown in a column aligned to the right of the items
+    /// if the terminal is wide enough to fit the widest item and the widest
+    /// description, and below the active item otherwise.
     ///
-    /// This method is similar to [interact_on_opt](#method.interact_on_opt) except for the fact that it does not allow selection of the terminal.
-    /// The dialog is rendered on stderr.
-    /// Result contains `Some(index)` if user selected one of items or `None` if user cancelled with 'Esc' or 'q'.
-    pub fn interact_opt(&self) -> io::Result<Option<usize>> {
-        self.interact_on_opt(&Term::stderr())
+    /// The default is `Auto`.
+    pub fn description_layout(&mut self, val: DescriptionLayout) -> &mut Select<'a> {
+        self.description_layout = val;
+        self
     }
 
-    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    /// Adds multiple items to the selector.
     ///
     /// ## Examples
-    ///```rust,no_run
+    /// ```rust,no_run
     /// use dialoguer::Select;
-    /// use console::Term;
     ///
     /// fn main() -> std::io::Result<()> {
-    ///     let selection = Select::new()
-    ///         .item("Option A")
-    ///         .item("Option B")
-    ///         .interact_on(&Term::stderr())?;
+    ///     let items = vec!["Item 1", "Item 2"];
+    ///     let selection: usize = Select::new()
+    ///         .items(&items)
+    ///         .interact()?;
     ///
-    ///     println!("User selected option at index {}", selection);
+    ///     println!("{}", items[selection]);
     ///
     ///     Ok(())
     /// }
-    ///```
-    pub fn interact_on(&self, term: &Term) -> io::Result<usize> {
-        self._interact_on(term, false)?
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    /// ```
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Select<'a> {
+        for item in items {
+            self.item(item.to_string());
+        }
+        self
     }
 
-    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    /// Lists the items yielded by an iterator.
+    ///
+    /// The iterator is consumed lazily, a page at a time, so a long or slow
+    /// listing doesn't need to be collected first. A loading line is shown
+    /// while a page is fetched. Fetched items are kept, so pages that were
+    /// shown before don't need to be fetched again.
+    ///
+    /// This is a [page_provider](#method.page_provider) over the iterator,
+    /// and the same rules apply: the total is unknown until the iterator
+    /// ends, and items added with [item](#method.item) are ignored.
     ///
     /// ## Examples
     /// ```rust,no_run
     /// use dialoguer::Select;
-    /// use console::Term;
     ///
     /// fn main() -> std::io::Result<()> {
     ///     let selection = Select::new()
-    ///         .item("Option A")
-    ///         .item("Option B")
-    ///         .interact_on_opt(&Term::stdout())?;
+    ///         .items_iter((1..=1000).map(|n| format!("Entry {}", n)))
+    ///         .interact()?;
     ///
-    ///     match selection {
-    ///         Some(position) => println!("User selected option at index {}", position),
-    ///         None => println!("User did not select anything")
-    ///     }
+    ///     println!("User picked entry {}", selection + 1);
     ///
     ///     Ok(())
     /// }
     /// ```
-    #[inline]
-    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<usize>> {
-        self._interact_on(term, true)
-    }
+    pub fn items_iter<I>(&mut self, items: I) -> &mut Select<'a>
+    where
+        I: IntoIterator,
+        I::IntoIter: 'a,
+        I::Item: ToString,
+    {
+        let source = RefCell::new(items.into_iter());
+        let fetched = RefCell::new(Vec::new());
 
-    /// Like `interact` but allows a specific terminal to be set.
-    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<usize>> {
-        let mut page = 0;
+        self.page_provider(move |offset, count| {
+            let mut fetched = fetched.borrow_mut();
+            let missing = (offset + count).saturating_sub(fetched.len());
+            fetched.extend(
+                source
+                    .borrow_mut()
+                    .by_ref()
+                    .take(missing)
+                    .map(|item| item.to_string()),
+            );
+            fetched.iter().skip(offset).take(count).cloned().collect()
+        })
+    }
 
-        if self.items.is_empty() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                "Empty list of items given to `Select`",
-            ));
-        }
+    /// Adds a section header in front of the items added next.
+    ///
+    /// Headers are rendered with their own styling and can't be selected.
+    /// They don't count as items, so the indices of the items stay the same
+    /// as without headers. Headers are hidden while the fuzzy filter is used.
+    /// A header added after the last item is rendered below it.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    ///
+    /// fn main() {
+    ///     let selection = Select::new()
+    ///         .group_header("Fruits")
+    ///         .items(&["Apple", "Banana"])
+    ///         .group_header("Vegetables")
+    ///         .items(&["Carrot", "Pea"])
+    ///         .interact()
+    ///         .unwrap();
+    ///
+    ///     // Picking "Carrot" returns 2.
+    ///     println!("You selected {}", selection);
+    /// }
+    /// ```
+    pub fn group_header<S: Into<String>>(&mut self, header: S) -> &mut Select<'a> {
+        self.group_headers.push((self.items.len(), header.into()));
+        self
+    }
 
-        let capacity = if self.paged {
-            term.size().0 as usize - 1
-        } else {
+    /// Registers a validator for the index of the picked item.
+    ///
+    /// If the validator rejects the item, its error is shown below the
+    /// items and the user has to pick again. Rejected piped answers and a
+    /// rejected default the timeout runs out on are an error. Multiple
+    /// validators run in the order they were registered.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    ///
+    /// fn main() {
+    ///     let licensed = false;
+    ///     let selection = Select::new()
+    ///         .items(&["Community", "Enterprise"])
+    ///         .validate_with(|index: &usize| -> Result<(), &str> {
+    ///             if *index == 1 && !licensed {
+    ///                 Err("This option requires a license")
+    ///             } else {
+    ///                 Ok(())
+    ///             }
+    ///         })
+    ///         .interact()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn validate_with<V>(&mut self, validator: V) -> &mut Select<'a>
+    where
+        V: Validator<usize> + 'a,
+    {
+        let old_validator_func = self.validator.take();
+
+        self.validator = Some(Box::new(move |value: &usize| -> Option<String> {
+            if let Some(old) = old_validator_func.as_ref() {
+                if let Some(err) = old(value) {
+                    return Some(err);
+                }
+            }
+
+            match validator.validate(value) {
+                Ok(()) => None,
+                Err(err) => Some(err.to_string()),
+            }
+        }));
+
+        self
+    }
+
+    /// Fetches the items lazily from a callback instead.
+    ///
+    /// The callback is invoked with an offset and a count and returns the
+    /// items starting at that offset, so only the page that is shown needs
+    /// to be materialized. Paging is always enabled with a provider and a
+    /// page is fetched again whenever the user moves to it.
+    ///
+    /// The total number of items doesn't need to be known upfront. The list
+    /// ends once the callback returns fewer items than requested, which is
+    /// only discovered when the last page is fetched. Until then moving up
+    /// from the first item or left from the first page stays put instead of
+    /// wrapping around, and end doesn't do anything. An infinite list never
+    /// ends.
+    ///
+    /// The callback runs on the thread of the prompt, which shows a loading
+    /// line below the previous page until it returns. A slow source should
+    /// therefore be queried in pages small enough to answer quickly.
+    ///
+    /// The returned index is the offset of the chosen item in the whole
+    /// list. Any items added with [item](#method.item) are ignored, and
+    /// fuzzy filtering, disabled items and typed confirmations are not
+    /// available.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let selection = Select::new()
+    ///         .with_prompt("Pick a number")
+    ///         .page_provider(|offset, count| {
+    ///             (offset..offset + count).map(|n| n.to_string()).collect()
+    ///         })
+    ///         .interact()?;
+    ///
+    ///     println!("User picked {}", selection);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn page_provider<F>(&mut self, provider: F) -> &mut Select<'a>
+    where
+        F: Fn(usize, usize) -> Vec<String> + 'a,
+    {
+        self.page_provider = Some(Box::new(provider));
+        self
+    }
+
+    /// Sets the select prompt.
+    ///
+    /// When a prompt is set the system also prints out a confirmation after
+    /// the selection.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let selection = Select::new()
+    ///         .with_prompt("Which option do you prefer?")
+    ///         .item("Option A")
+    ///         .item("Option B")
+    ///         .interact()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Select<'a> {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// Similar to [interact_on](#method.interact_on) except for the fact that it does not allow selection of the terminal.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    /// Result contains index of a selected item.
+    pub fn interact(&self) -> Result<usize> {
+        self.interact_on(&self.target_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// This method is similar to [interact_on_opt](#method.interact_on_opt) except for the fact that it does not allow selection of the terminal.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    /// Result contains `Some(index)` if user selected one of items or `None` if user cancelled with 'Esc' or 'q'.
+    pub fn interact_opt(&self) -> Result<Option<usize>> {
+        self.interact_on_opt(&self.target_term())
+    }
+
+    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    ///
+    /// ## Examples
+    ///```rust,no_run
+    /// use dialoguer::Select;
+    /// use console::Term;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let selection = Select::new()
+    ///         .item("Option A")
+    ///         .item("Option B")
+    ///         .interact_on(&Term::stderr())?;
+    ///
+    ///     println!("User selected option at index {}", selection);
+    ///
+    ///     Ok(())
+    /// }
+    ///```
+    pub fn interact_on(&self, term: &Term) -> Result<usize> {
+        self._interact_on(term, false, &TermKeys)?
+            .map(|outcome| outcome.index)
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    /// use console::Term;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let selection = Select::new()
+    ///         .item("Option A")
+    ///         .item("Option B")
+    ///         .interact_on_opt(&Term::stdout())?;
+    ///
+    ///     match selection {
+    ///         Some(position) => println!("User selected option at index {}", position),
+    ///         None => println!("User did not select anything")
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[inline]
+    pub fn interact_on_opt(&self, term: &Term) -> Result<Option<usize>> {
+        Ok(self
+            ._interact_on(term, true, &TermKeys)?
+            .map(|outcome| outcome.index))
+    }
+
+    /// Enables user interaction and returns the text of the selected item.
+    ///
+    /// Behaves like [interact](#method.interact) but returns the item itself
+    /// instead of its index.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_text(&self) -> Result<String> {
+        self.interact_text_on(&self.target_term())
+    }
+
+    /// Like [interact_text](#method.interact_text) but allows a specific terminal to be set.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    /// use console::Term;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let selection = Select::new()
+    ///         .item("Option A")
+    ///         .item("Option B")
+    ///         .interact_text_on(&Term::stderr())?;
+    ///
+    ///     println!("User selected {}", selection);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn interact_text_on(&self, term: &Term) -> Result<String> {
+        let sel = self.interact_on(term)?;
+
+        Ok(match self.page_provider {
+            Some(ref provider) => provider(sel, 1).into_iter().next().unwrap_or_default(),
+            None => self.items[sel].to_string(),
+        })
+    }
+
+    /// Enables user interaction and returns details about the selection.
+    ///
+    /// Besides the index of the selected item the outcome tells whether the
+    /// user accepted the preselected item without navigating and which key
+    /// confirmed the selection.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_detailed(&self) -> Result<SelectionOutcome> {
+        self.interact_detailed_on(&self.target_term())
+    }
+
+    /// Like [interact_detailed](#method.interact_detailed) but allows a specific terminal to be set.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    /// use console::Term;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let outcome = Select::new()
+    ///         .item("Option A")
+    ///         .item("Option B")
+    ///         .default(0)
+    ///         .interact_detailed_on(&Term::stderr())?;
+    ///
+    ///     if outcome.was_default {
+    ///         println!("User accepted the default");
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn interact_detailed_on(&self, term: &Term) -> Result<SelectionOutcome> {
+        self._interact_on(term, false, &TermKeys)?
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Enables user interaction and returns the selection along with how it
+    /// was made.
+    ///
+    /// The report tells how long the user took and how many keys they
+    /// pressed. Answers piped in count as no keystrokes. Otherwise this
+    /// behaves like [interact](#method.interact).
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// use dialoguer::Select;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let (selection, report) = Select::new()
+    ///         .items(&["Option A", "Option B"])
+    ///         .interact_with_report()?;
+    ///
+    ///     println!(
+    ///         "Picked {} after {:?} and {} keys",
+    ///         selection, report.duration, report.keystrokes
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn interact_with_report(&self) -> Result<(usize, InteractionReport)> {
+        self.interact_with_report_on(&self.target_term())
+    }
+
+    /// Like [interact_with_report](#method.interact_with_report) but allows a specific terminal to be set.
+    pub fn interact_with_report_on(&self, term: &Term) -> Result<(usize, InteractionReport)> {
+        self.interact_reported(term, &TermKeys)
+    }
+
+    /// Like `interact_on` but measures the interaction.
+    fn interact_reported(
+        &self,
+        term: &Term,
+        keys: &dyn KeySource,
+    ) -> Result<(usize, InteractionReport)> {
+        let started = Instant::now();
+        let keys = CountingKeys::new(keys);
+
+        let outcome = self
+            ._interact_on(term, false, &keys)?
+            .ok_or(Error::Cancelled)?;
+
+        Ok((
+            outcome.index,
+            InteractionReport {
+                duration: started.elapsed(),
+                keystrokes: keys.count(),
+            },
+        ))
+    }
+
+    /// Runs the prompt on a fixed sequence of keys instead of user input.
+    ///
+    /// This allows testing code that drives a select prompt, e.g. a wizard,
+    /// without a terminal. The prompt is rendered to a buffered stderr.
+    /// Like [interact_opt](#method.interact_opt) the result is `None` if the
+    /// keys cancel the prompt. Running out of keys before the prompt is
+    /// finished is an error of kind `UnexpectedEof`. A
+    /// [timeout](#method.timeout) doesn't apply. The text of a
+    /// [typed confirmation](#method.require_typed_confirmation) is given as
+    /// `Key::Char` keys followed by `Key::Enter`.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use dialoguer::{console::Key, Select};
+    ///
+    /// let selection = Select::new()
+    ///     .items(&["Option A", "Option B", "Option C"])
+    ///     .default(0)
+    ///     .interact_with_keys(&[Key::ArrowUp, Key::ArrowUp, Key::Enter])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(selection, Some(1));
+    /// ```
+    pub fn interact_with_keys(&self, keys: &[Key]) -> Result<Option<usize>> {
+        self.interact_with_keys_on(&Term::buffered_stderr(), keys)
+    }
+
+    /// Like [interact_with_keys](#method.interact_with_keys) but allows a specific terminal to be set.
+    pub fn interact_with_keys_on(&self, term: &Term, keys: &[Key]) -> Result<Option<usize>> {
+        Ok(self
+            ._interact_on(term, true, &ScriptedKeys::new(keys))?
+            .map(|outcome| outcome.index))
+    }
+
+    /// Returns the initially selected item, preferring a remembered one.
+    fn initial_selection(&self) -> usize {
+        if let Some((ref key, ref memory)) = self.memory {
+            if let Some(index) = memory.borrow().load(key) {
+                if index < self.items.len() || self.page_provider.is_some() {
+                    return index;
+                }
+            }
+        }
+
+        self.default
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    pub(crate) fn _interact_on(
+        &self,
+        term: &Term,
+        allow_quit: bool,
+        keys: &dyn KeySource,
+    ) -> Result<Option<SelectionOutcome>> {
+        if let Some(ref provider) = self.page_provider {
+            return self.interact_provided(term, allow_quit, keys, provider.as_ref());
+        }
+
+        if self.items.is_empty() {
+            let message = match self.no_items_message {
+                Some(ref message) => message,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "Empty list of items given to `Select`",
+                    )
+                    .into())
+                }
+            };
+
+            let mut render = TermThemeRenderer::new(term, self.theme);
+            render.set_indent(self.indent);
+            render.set_prompt_icon(self.prompt_icon.as_deref());
+            render.set_step(self.wizard_step);
+            render.error(message)?;
+            term.flush()?;
+
+            return if allow_quit {
+                Ok(None)
+            } else {
+                Err(io::Error::new(io::ErrorKind::Other, message.as_str()).into())
+            };
+        }
+
+        if !keys.is_scripted() && self.timeout.is_none() && piped::is_piped(term) {
+            return self.interact_piped(term, &piped::Stdin).map(Some);
+        }
+
+        let inline_descriptions =
+            self.inline_descriptions((term.size().1 as usize).saturating_sub(self.indent));
+        let description_column = self.description_column();
+        let description_line =
+            !inline_descriptions && self.descriptions.iter().any(Option::is_some);
+
+        let capacity = if self.paged || self.viewport.is_some() {
+            let rows = (term.size().0 as usize)
+                .saturating_sub(if self.fuzzy { 3 } else { 2 })
+                .saturating_sub(if description_line { 1 } else { 0 })
+                .saturating_sub(self.frame_height())
+                .max(1);
+            let header_rows = self.header_rows(&self.display_order(0..self.items.len()));
+            let max = self.viewport.or(self.max_length).unwrap_or(rows);
+            page_capacity(&header_rows, max, rows, self.viewport.is_some())
+        } else {
             self.items.len()
         };
 
-        let pages = (self.items.len() as f64 / capacity as f64).ceil() as usize;
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_truncate_echo(self.truncate_echo);
+        render.set_truncate_items(self.truncate_items);
+        render.set_max_height(self.max_height);
+        let _cursor = HiddenCursor::new(term)?;
+        let mut show_help = false;
+
+        if let Some(ref prompt) = self.prompt {
+            render.select_prompt(prompt)?;
+        }
+
+        // Indices of the items matching the fuzzy filter in the order they
+        // are displayed, `sel` and `page` refer to positions in this list.
+        // `sel` is `!0` exactly when no item is visible.
+        let mut filter = String::new();
+        let mut visible = self.display_order(0..self.items.len());
+
+        let initial_index = self.initial_selection();
+        let default_pos = visible.iter().position(|&idx| idx == initial_index);
+        let initial = default_pos
+            .filter(|&pos| !self.disabled[visible[pos]])
+            .unwrap_or(!0);
+
+        // Without a default the first item is highlighted, but picking it
+        // right away doesn't count as picking the default. A disabled
+        // default highlights the next enabled item instead.
+        let mut sel = if initial == !0 {
+            self.skip_disabled(&visible, default_pos.unwrap_or(0), false)
+        } else {
+            initial
+        };
+        let mut navigated = initial == !0;
+        let mut page = sel / capacity;
+        let mut error: Option<String> = None;
+
+        loop {
+            let pages = (visible.len() as f64 / capacity as f64).ceil() as usize;
+            let first = match self.viewport {
+                Some(_) if sel != !0 => sel
+                    .saturating_sub(capacity / 2)
+                    .min(visible.len().saturating_sub(capacity)),
+                Some(_) => 0,
+                None => page * capacity,
+            };
+
+            if self.fuzzy {
+                render.select_prompt_filter(&filter, visible.len(), self.items.len())?;
+            }
+
+            if let Some(ref header) = self.header {
+                render.select_prompt_header(header)?;
+            }
+
+            render.begin_scroll_region();
+
+            for (idx, &index) in visible.iter().enumerate().skip(first).take(capacity) {
+                let item = match self.shortcuts[index] {
+                    Some(key) => Cow::Owned(render.shortcut_label(key, &self.items[index])),
+                    None => Cow::Borrowed(&*self.items[index]),
+                };
+                let item = &*item;
+                let description = self.descriptions[index].as_deref();
+
+                if filter.is_empty() {
+                    for (_, header) in self.group_headers.iter().filter(|&&(at, _)| at == index) {
+                        render.select_prompt_group_header(header)?;
+                    }
+                }
+
+                if sel == idx {
+                    render.begin_scroll_focus();
+                }
+
+                let inline = match description {
+                    Some(description) if inline_descriptions => {
+                        Some((description, description_column - last_line_width(item)))
+                    }
+                    _ => None,
+                };
+
+                if self.disabled[index] {
+                    render.select_prompt_disabled_item(item, sel == idx, inline)?;
+                } else if self.pre_styled {
+                    render.select_prompt_styled_item(item, sel == idx, inline)?;
+                } else {
+                    render.select_prompt_item(item, sel == idx, inline)?;
+                }
+
+                if let (Some(description), true) = (description, sel == idx && !inline_descriptions)
+                {
+                    render.select_prompt_description(description)?;
+                }
+
+                if sel == idx {
+                    render.end_scroll_focus();
+                }
+            }
+
+            if filter.is_empty() && first + capacity >= visible.len() {
+                for (_, header) in self.trailing_headers() {
+                    render.select_prompt_group_header(header)?;
+                }
+            }
+
+            render.begin_scroll_footer();
+
+            if self.paged && self.viewport.is_none() && pages > 1 {
+                render.select_prompt_page(page + 1, pages)?;
+            }
+
+            if show_help {
+                render.select_prompt_help()?;
+            }
+
+            if let Some(ref footer) = self.footer {
+                render.select_prompt_footer(footer)?;
+            }
+
+            if let Some(ref err) = error.take() {
+                render.validation_error(err)?;
+            }
+
+            render.end_scroll_region()?;
+
+            // The widths of the lines as rendered, so lines that only wrap
+            // because of the item prefix are cleared completely as well.
+            let size_vec = render.line_widths().to_vec();
+
+            term.flush()?;
+
+            let input = match keys.read_key(term, self.timeout)? {
+                Some(input) => input,
+                None => {
+                    let index = self.initial_selection();
+                    let item = if index < self.items.len() && !self.disabled[index] {
+                        Some(self.items[index].to_string())
+                    } else {
+                        None
+                    };
+
+                    return self.timed_out(&mut render, term, index, item);
+                }
+            };
+
+            let prev_sel = sel;
+
+            let action = match input {
+                Key::Char(_) if self.fuzzy => None,
+                _ => self.key_bindings.action(&input),
+            };
+
+            // A shortcut moves to its item and picks it like confirming.
+            let action = match (action, &input) {
+                (None, &Key::Char(chr)) if !self.fuzzy && Some(chr) != self.help_toggle_key => {
+                    match self.shortcut_position(&visible, chr) {
+                        Some(pos) => {
+                            sel = pos;
+                            navigated = navigated || pos != initial;
+                            Some(KeyAction::Confirm)
+                        }
+                        None => None,
+                    }
+                }
+                _ => action,
+            };
+
+            match (action, input.clone()) {
+                (None, Key::Char(chr)) if Some(chr) == self.help_toggle_key => {
+                    show_help = !show_help;
+                }
+                (None, Key::Char(chr)) if self.fuzzy && !chr.is_ascii_control() => {
+                    filter.push(chr);
+                    visible = self.fuzzy_matches(&filter);
+                    sel = if visible.is_empty() { !0 } else { 0 };
+                    page = 0;
+                }
+                (None, Key::Backspace) if self.fuzzy => {
+                    filter.pop();
+                    visible = self.fuzzy_matches(&filter);
+                    sel = if visible.is_empty() { !0 } else { 0 };
+                    page = 0;
+                }
+                (Some(KeyAction::Down), _)
+                    if !visible.is_empty() && (self.wrap || sel + 1 < visible.len()) =>
+                {
+                    sel = (sel as u64 + 1).rem(visible.len() as u64) as usize;
+                }
+                (Some(KeyAction::Cancel), _) if allow_quit => {
+                    if self.clear {
+                        render.clear_preserve_prompt(&size_vec)?;
+                        term.flush()?;
+                    }
+
+                    return Ok(None);
+                }
+                (Some(KeyAction::Up), _) if !visible.is_empty() && (self.wrap || sel > 0) => {
+                    sel =
+                        ((sel as i64 - 1 + visible.len() as i64) % (visible.len() as i64)) as usize;
+                }
+                (Some(KeyAction::PrevPage), _) if self.paged && !visible.is_empty() => {
+                    if page == 0 {
+                        page = pages - 1;
+                    } else {
+                        page -= 1;
+                    }
+
+                    sel = page * capacity;
+                }
+                (Some(KeyAction::NextPage), _) if self.paged && !visible.is_empty() => {
+                    if page == pages - 1 {
+                        page = 0;
+                    } else {
+                        page += 1;
+                    }
+
+                    sel = page * capacity;
+                }
+                (None, Key::Home) if !visible.is_empty() => {
+                    sel = 0;
+                }
+                (None, Key::End) if !visible.is_empty() => {
+                    sel = visible.len() - 1;
+                }
+                (None, Key::PageUp) if !visible.is_empty() => {
+                    sel = sel.saturating_sub(capacity);
+                }
+                (None, Key::PageDown) if !visible.is_empty() => {
+                    sel = (sel + capacity).min(visible.len() - 1);
+                }
+                (Some(KeyAction::Confirm), _) if sel != !0 && !self.disabled[visible[sel]] => {
+                    let index = visible[sel];
+
+                    if let Some(err) = self.validation_error(index) {
+                        error = Some(err);
+                    } else {
+                        if self.typed_confirmation.contains(&index)
+                            && !self.confirm_phrase(&mut render, term, keys, &size_vec, index)?
+                        {
+                            continue;
+                        }
+
+                        if self.clear {
+                            render.clear()?;
+                        }
+
+                        self.report_selection(&mut render, &self.items[index])?;
+
+                        term.flush()?;
+
+                        if let Some((ref key, ref memory)) = self.memory {
+                            memory.borrow_mut().store(key, index);
+                        }
+
+                        return Ok(Some(SelectionOutcome {
+                            index,
+                            was_default: !navigated,
+                            used_key: input,
+                        }));
+                    }
+                }
+                (None, Key::Char(chr)) if !chr.is_control() => {
+                    if let Some(idx) = self.jump_to(&visible, sel, chr) {
+                        sel = idx;
+                    } else if self.bell {
+                        render.bell()?;
+                    }
+                }
+                _ if self.bell => {
+                    render.bell()?;
+                }
+                _ => {}
+            }
+
+            if sel != !0 && self.disabled[visible[sel]] {
+                let backward = match (action, &input) {
+                    (Some(KeyAction::Up), _) | (None, Key::PageUp) | (None, Key::End) => true,
+                    _ => false,
+                };
+                let next = self.skip_disabled(&visible, sel, backward);
+
+                // Without wrapping the selection stays put if only disabled
+                // items are left in the direction of the move.
+                let wrapped = if backward { next > sel } else { next < sel };
+                sel = if wrapped && !self.wrap {
+                    prev_sel
+                } else {
+                    next
+                };
+            }
+
+            if sel != initial {
+                navigated = true;
+            }
+
+            if sel != !0 && (sel < page * capacity || sel >= (page + 1) * capacity) {
+                page = sel / capacity;
+            }
+
+            render.clear_preserve_prompt(&size_vec)?;
+        }
+    }
+
+    /// Reads the index or text of the item as a line from `source`.
+    fn interact_piped(&self, term: &Term, source: &dyn LineSource) -> Result<SelectionOutcome> {
+        let (index, was_default) = match piped::read_answer(source)? {
+            Some(answer) => match piped::parse_index(&answer, &self.items) {
+                Some(index) if !self.disabled[index] => (index, false),
+                _ => {
+                    return Err(piped::invalid_answer_error(&format!(
+                        "Invalid item: {}",
+                        answer
+                    )))
+                }
+            },
+            None => {
+                let index = self.initial_selection();
+
+                if index >= self.items.len() || self.disabled[index] {
+                    return Err(piped::no_answer_error());
+                }
+
+                (index, true)
+            }
+        };
+
+        if let Some(err) = self.validation_error(index) {
+            return Err(piped::invalid_answer_error(&err));
+        }
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_truncate_echo(self.truncate_echo);
+        render.set_truncate_items(self.truncate_items);
+
+        self.report_selection(&mut render, &self.items[index])?;
+
+        term.flush()?;
+
+        if let Some((ref key, ref memory)) = self.memory {
+            memory.borrow_mut().store(key, index);
+        }
+
+        Ok(SelectionOutcome {
+            index,
+            was_default,
+            used_key: Key::Enter,
+        })
+    }
+
+    /// Prints the selected `item` below the prompt if reporting is enabled.
+    fn report_selection(&self, render: &mut TermThemeRenderer, item: &str) -> io::Result<()> {
+        match self.prompt {
+            Some(ref prompt) if self.report => render.select_prompt_selection(prompt, item),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolves a prompt whose timeout ran out to the preselected `item`.
+    fn timed_out(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        index: usize,
+        item: Option<String>,
+    ) -> Result<Option<SelectionOutcome>> {
+        if self.clear {
+            render.clear()?;
+        }
+
+        let item = match item {
+            Some(item) => item,
+            None => {
+                term.flush()?;
+                return Err(timed_out_error());
+            }
+        };
+
+        if self.validation_error(index).is_some() {
+            term.flush()?;
+            return Err(timed_out_error());
+        }
+
+        self.report_selection(render, &item)?;
+
+        term.flush()?;
+
+        Ok(Some(SelectionOutcome {
+            index,
+            was_default: true,
+            used_key: Key::Unknown,
+        }))
+    }
+
+    /// Like `_interact_on` but for items fetched from a page provider.
+    fn interact_provided(
+        &self,
+        term: &Term,
+        allow_quit: bool,
+        keys: &dyn KeySource,
+        provider: &dyn Fn(usize, usize) -> Vec<String>,
+    ) -> Result<Option<SelectionOutcome>> {
+        let capacity = (term.size().0 as usize - 1)
+            .saturating_sub(self.frame_height())
+            .max(1);
+        let capacity = self
+            .max_length
+            .map_or(capacity, |max| max.max(1).min(capacity));
+
+        let mut items = PagedItems::new(provider, capacity);
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_truncate_echo(self.truncate_echo);
+        render.set_truncate_items(self.truncate_items);
+        let _cursor = HiddenCursor::new(term)?;
+        let mut show_help = false;
+        let mut error: Option<String> = None;
+
+        if let Some(ref prompt) = self.prompt {
+            render.select_prompt(prompt)?;
+        }
+
+        // Without a default the first item is highlighted, but picking it
+        // right away doesn't count as picking the default.
+        let initial = self.initial_selection();
+        if initial != !0 {
+            self.load_page(&mut render, term, &mut items, initial)?;
+        }
+        let has_default = initial != !0 && items.contains(initial);
+        let initial = if has_default { initial } else { 0 };
+        self.load_page(&mut render, term, &mut items, initial)?;
+        render.clear_preserve_prompt(&[])?;
+
+        if !items.contains(0) {
+            render.clear()?;
+            term.flush()?;
+
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Empty list of items given to `Select`",
+            )
+            .into());
+        }
+
+        let mut sel = initial;
+        let mut navigated = !has_default;
+
+        loop {
+            let page = sel / capacity;
+
+            if self.load_page(&mut render, term, &mut items, sel)? {
+                render.clear_preserve_prompt(&[])?;
+            }
+
+            if let Some(ref header) = self.header {
+                render.select_prompt_header(header)?;
+            }
+
+            for (idx, item) in items.page(page).iter().enumerate() {
+                if self.pre_styled {
+                    render.select_prompt_styled_item(item, sel == page * capacity + idx, None)?;
+                } else {
+                    render.select_prompt_item(item, sel == page * capacity + idx, None)?;
+                }
+            }
+
+            if show_help {
+                render.select_prompt_help()?;
+            }
+
+            if let Some(ref footer) = self.footer {
+                render.select_prompt_footer(footer)?;
+            }
+
+            if let Some(ref err) = error.take() {
+                render.validation_error(err)?;
+            }
+
+            let size_vec = render.line_widths().to_vec();
+
+            term.flush()?;
+
+            let input = match keys.read_key(term, self.timeout)? {
+                Some(input) => input,
+                None => {
+                    let index = self.initial_selection();
+                    let item = if index != !0 && items.contains(index) {
+                        Some(items.page(index / capacity)[index % capacity].clone())
+                    } else {
+                        None
+                    };
+
+                    return self.timed_out(&mut render, term, index, item);
+                }
+            };
+
+            match (self.key_bindings.action(&input), input.clone()) {
+                (None, Key::Char(chr)) if Some(chr) == self.help_toggle_key => {
+                    show_help = !show_help;
+                }
+                (Some(KeyAction::Down), _) => {
+                    self.load_page(&mut render, term, &mut items, sel + 1)?;
+
+                    if items.contains(sel + 1) {
+                        sel += 1;
+                    } else if self.wrap {
+                        sel = 0;
+                    }
+                }
+                (Some(KeyAction::Up), _) => {
+                    sel = match (sel, items.total) {
+                        (0, Some(total)) if self.wrap => total - 1,
+                        (0, _) => 0,
+                        _ => sel - 1,
+                    };
+                }
+                (Some(KeyAction::NextPage), _) | (None, Key::PageDown) => {
+                    let next = (page + 1) * capacity;
+                    self.load_page(&mut render, term, &mut items, next)?;
+                    sel = if items.contains(next) { next } else { 0 };
+                }
+                (Some(KeyAction::PrevPage), _) | (None, Key::PageUp) => {
+                    sel = match (page, items.total) {
+                        (0, Some(total)) => (total - 1) / capacity * capacity,
+                        (0, None) => 0,
+                        _ => (page - 1) * capacity,
+                    };
+                }
+                (None, Key::Home) => {
+                    sel = 0;
+                }
+                (None, Key::End) => {
+                    if let Some(total) = items.total {
+                        sel = total - 1;
+                    }
+                }
+                (Some(KeyAction::Cancel), _) if allow_quit => {
+                    if self.clear {
+                        render.clear_preserve_prompt(&size_vec)?;
+                        term.flush()?;
+                    }
+
+                    return Ok(None);
+                }
+                (Some(KeyAction::Confirm), _) => {
+                    if let Some(err) = self.validation_error(sel) {
+                        error = Some(err);
+                    } else {
+                        if self.clear {
+                            render.clear()?;
+                        }
+
+                        self.report_selection(&mut render, &items.page(page)[sel % capacity])?;
+
+                        term.flush()?;
+
+                        if let Some((ref key, ref memory)) = self.memory {
+                            memory.borrow_mut().store(key, sel);
+                        }
+
+                        return Ok(Some(SelectionOutcome {
+                            index: sel,
+                            was_default: !navigated,
+                            used_key: input,
+                        }));
+                    }
+                }
+                (_, Key::Unknown) => {
+                    return Err(
+                        io::Error::new(io::ErrorKind::NotConnected, "Not a terminal").into(),
+                    )
+                }
+                _ if self.bell => {
+                    render.bell()?;
+                }
+                _ => {}
+            }
+
+            if sel != initial {
+                navigated = true;
+            }
+
+            render.clear_preserve_prompt(&size_vec)?;
+        }
+    }
+
+    /// Fetches the page with the item at `index` unless it is loaded,
+    /// showing the loading line meanwhile.
+    ///
+    /// Returns whether the loading line was shown. It is cleared along with
+    /// the items.
+    fn load_page(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        items: &mut PagedItems,
+        index: usize,
+    ) -> io::Result<bool> {
+        if items.is_loaded(index) {
+            return Ok(false);
+        }
+
+        render.select_prompt_loading()?;
+        term.flush()?;
+        items.contains(index);
+
+        Ok(true)
+    }
+
+    /// Asks the user to type the text of the item at `index`.
+    ///
+    /// The items are cleared before asking and the prompt is cleared again
+    /// afterwards. Returns whether the typed text matched.
+    fn confirm_phrase(
+        &self,
+        render: &mut TermThemeRenderer,
+        term: &Term,
+        keys: &dyn KeySource,
+        size_vec: &[usize],
+        index: usize,
+    ) -> Result<bool> {
+        let phrase = self.confirmation_phrase(index);
+
+        render.clear_preserve_prompt(size_vec)?;
+        render.select_prompt_confirm_phrase(&phrase)?;
+        term.show_cursor()?;
+        term.flush()?;
+
+        let typed = keys.read_line(term)?;
+        render.add_line();
+        term.hide_cursor()?;
+        render.clear_preserve_prompt(&[])?;
+
+        Ok(typed.trim() == phrase)
+    }
+
+    /// Returns the text the user has to type to confirm the item at `index`.
+    fn confirmation_phrase(&self, index: usize) -> String {
+        strip_ansi_codes(&self.items[index]).trim().to_string()
+    }
+
+    /// Returns the position in `visible` of the enabled item with the shortcut `chr`.
+    fn shortcut_position(&self, visible: &[usize], chr: char) -> Option<usize> {
+        visible
+            .iter()
+            .position(|&idx| self.shortcuts[idx] == Some(chr) && !self.disabled[idx])
+    }
+
+    /// Returns the position in `visible` of the next item after `sel` starting with `chr`.
+    ///
+    /// The search wraps around and ignores case, so repeatedly jumping with
+    /// the same character cycles through all items starting with it.
+    fn jump_to(&self, visible: &[usize], sel: usize, chr: char) -> Option<usize> {
+        let len = visible.len();
+        let start = if sel == !0 { 0 } else { sel + 1 };
+
+        (0..len).map(|offset| (start + offset) % len).find(|&pos| {
+            let idx = visible[pos];
+
+            !self.disabled[idx]
+                && strip_ansi_codes(&self.items[idx])
+                    .chars()
+                    .next()
+                    .map_or(false, |first| first.to_lowercase().eq(chr.to_lowercase()))
+        })
+    }
+
+    /// Moves `sel` to the nearest position in `visible` that is not disabled.
+    ///
+    /// The search wraps around and `sel` is returned unchanged if every
+    /// visible item is disabled.
+    fn skip_disabled(&self, visible: &[usize], sel: usize, backward: bool) -> usize {
+        let len = visible.len();
+
+        (0..len)
+            .map(|offset| {
+                if backward {
+                    (sel + len - offset) % len
+                } else {
+                    (sel + offset) % len
+                }
+            })
+            .find(|&pos| !self.disabled[visible[pos]])
+            .unwrap_or(sel)
+    }
+
+    /// Returns the number of lines taken by the header and the footer.
+    fn frame_height(&self) -> usize {
+        self.header
+            .iter()
+            .chain(self.footer.iter())
+            .map(|text| text.lines().count().max(1))
+            .sum()
+    }
+
+    /// Returns the indices of all items matching the fuzzy filter.
+    fn fuzzy_matches(&self, filter: &str) -> Vec<usize> {
+        self.display_order(
+            self.items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| fuzzy_match(item, filter))
+                .map(|(idx, _)| idx),
+        )
+    }
+
+    /// Returns the column the item descriptions start at when rendered inline.
+    ///
+    /// This leaves two spaces after the widest item.
+    fn description_column(&self) -> usize {
+        self.items
+            .iter()
+            .zip(&self.shortcuts)
+            .map(|(item, shortcut)| match *shortcut {
+                Some(key) => last_line_width(&shortcut_label(self.theme, key, item)),
+                None => last_line_width(item),
+            })
+            .max()
+            .unwrap_or(0)
+            + 2
+    }
+
+    /// Checks whether descriptions are rendered inline on a terminal `width` columns wide.
+    fn inline_descriptions(&self, width: usize) -> bool {
+        match self.description_layout {
+            DescriptionLayout::Inline => true,
+            DescriptionLayout::BelowActive => false,
+            DescriptionLayout::Auto => {
+                let description_width = self
+                    .descriptions
+                    .iter()
+                    .flatten()
+                    .map(|description| measure_text_width(description))
+                    .max()
+                    .unwrap_or(0);
+
+                // Two columns for the active item marker in front.
+                2 + self.description_column() + description_width <= width
+            }
+        }
+    }
+
+    /// Orders the item indices for display.
+    fn display_order<I: Iterator<Item = usize>>(&self, indices: I) -> Vec<usize> {
+        let mut order: Vec<usize> = indices.collect();
+
+        if self.sink_disabled {
+            order.sort_by_key(|&idx| self.disabled[idx]);
+        }
+
+        order
+    }
+
+    /// Returns the number of group header rows in front of each of the
+    /// items in `order`, counting trailing headers for the last item.
+    fn header_rows(&self, order: &[usize]) -> Vec<usize> {
+        let mut rows: Vec<usize> = order
+            .iter()
+            .map(|&idx| {
+                self.group_headers
+                    .iter()
+                    .filter(|&&(at, _)| at == idx)
+                    .count()
+            })
+            .collect();
+
+        if let Some(last) = rows.last_mut() {
+            *last += self.trailing_headers().count();
+        }
+
+        rows
+    }
+
+    /// Returns the group headers that were added after the last item.
+    fn trailing_headers(&self) -> impl Iterator<Item = &(usize, String)> {
+        let len = self.items.len();
+        self.group_headers.iter().filter(move |&&(at, _)| at >= len)
+    }
+}
+
+/// Items of a select prompt that are fetched a page at a time.
+///
+/// Only the most recently fetched page is kept around.
+struct PagedItems<'p> {
+    provider: &'p dyn Fn(usize, usize) -> Vec<String>,
+    page_size: usize,
+    cached: Option<(usize, Vec<String>)>,
+    /// The total number of items, once the last page has been fetched.
+    total: Option<usize>,
+}
+
+impl<'p> PagedItems<'p> {
+    fn new(provider: &'p dyn Fn(usize, usize) -> Vec<String>, page_size: usize) -> PagedItems<'p> {
+        PagedItems {
+            provider,
+            page_size,
+            cached: None,
+            total: None,
+        }
+    }
+
+    /// Returns the items on `page`, fetching them unless they are cached.
+    fn page(&mut self, page: usize) -> &[String] {
+        let cached = match self.cached {
+            Some((cached, _)) => cached == page,
+            None => false,
+        };
+
+        if !cached {
+            let offset = page * self.page_size;
+            let mut items = (self.provider)(offset, self.page_size);
+            items.truncate(self.page_size);
+
+            if items.len() < self.page_size {
+                self.total = Some(offset + items.len());
+            }
+
+            self.cached = Some((page, items));
+        }
+
+        match self.cached {
+            Some((_, ref items)) => items,
+            None => &[],
+        }
+    }
+
+    /// Checks whether `index` can be looked up without fetching a page.
+    fn is_loaded(&self, index: usize) -> bool {
+        match (self.total, &self.cached) {
+            (Some(_), _) => true,
+            (None, &Some((page, _))) => page == index / self.page_size,
+            (None, &None) => false,
+        }
+    }
+
+    /// Checks whether there is an item at `index`.
+    fn contains(&mut self, index: usize) -> bool {
+        match self.total {
+            Some(total) => index < total,
+            None => self.page(index / self.page_size).len() > index % self.page_size,
+        }
+    }
+}
+
+/// Returns how many items fit on a page of a list with group headers.
+///
+/// `header_rows` has the number of header rows in front of each item. The
+/// page takes at most `max` items, and those together with their headers
+/// take at most `rows` rows. Pages start at multiples of the capacity, or
+/// at any item for a `sliding` viewport.
+pub(crate) fn page_capacity(
+    header_rows: &[usize],
+    max: usize,
+    rows: usize,
+    sliding: bool,
+) -> usize {
+    let mut before = vec![0];
+    for count in header_rows {
+        before.push(before[before.len() - 1] + count);
+    }
+    let len = header_rows.len();
+
+    (1..=max.min(rows))
+        .rev()
+        .find(|&capacity| {
+            let step = if sliding { 1 } else { capacity };
+            (0..len)
+                .step_by(step)
+                .all(|start| capacity + before[(start + capacity).min(len)] - before[start] <= rows)
+        })
+        .unwrap_or(1)
+}
+
+/// Returns the width of the last line of `text`.
+fn last_line_width(text: &str) -> usize {
+    text.rsplit('\n').next().map_or(0, measure_text_width)
+}
+
+/// Checks if all characters of `filter` appear in `text` in the same order.
+///
+/// The comparison is case-insensitive.
+fn fuzzy_match(text: &str, filter: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+
+    filter
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|f| text.any(|t| t == f))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_keys_navigation() {
+        let mut select = Select::new();
+        select.items(&["Apple", "Banana", "Cherry"]).default(0);
+
+        let run = |keys: &[Key]| select.interact_with_keys(keys).unwrap();
+
+        assert_eq!(run(&[Key::Enter]), Some(0));
+        assert_eq!(run(&[Key::ArrowDown, Key::ArrowDown, Key::Enter]), Some(2));
+        assert_eq!(run(&[Key::Char('j'), Key::Char('k'), Key::Enter]), Some(0));
+        assert_eq!(run(&[Key::End, Key::Char(' ')]), Some(2));
+        assert_eq!(run(&[Key::Escape]), None);
+    }
+
+    #[test]
+    fn test_keys_without_default() {
+        let term = Term::buffered_stderr();
+        let mut select = Select::new();
+        select.items(&["Apple", "Banana", "Cherry"]);
+
+        let outcome = select
+            ._interact_on(&term, true, &ScriptedKeys::new(&[Key::Enter]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.index, 0);
+        assert!(!outcome.was_default);
+
+        let run = |select: &Select, keys: &[Key]| select.interact_with_keys(keys).unwrap();
+
+        assert_eq!(run(&select, &[Key::ArrowDown, Key::Enter]), Some(1));
+        assert_eq!(run(&select, &[Key::ArrowUp, Key::Enter]), Some(2));
+        assert_eq!(run(&select, &[Key::PageDown, Key::Enter]), Some(2));
+
+        let mut disabled_first = Select::new();
+        disabled_first.item_disabled("Apple", true).item("Banana");
+        assert_eq!(run(&disabled_first, &[Key::Enter]), Some(1));
+
+        let outcome = select
+            .default(2)
+            ._interact_on(&term, true, &ScriptedKeys::new(&[Key::Enter]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.index, 2);
+        assert!(outcome.was_default);
+    }
+
+    #[test]
+    fn test_keys_outcome() {
+        let term = Term::buffered_stderr();
+        let mut select = Select::new();
+        select.items(&["Apple", "Banana", "Cherry"]).default(1);
+
+        let run = |keys: &[Key]| {
+            select
+                ._interact_on(&term, true, &ScriptedKeys::new(keys))
+                .unwrap()
+                .unwrap()
+        };
+
+        let accepted = SelectionOutcome {
+            index: 1,
+            was_default: true,
+            used_key: Key::Enter,
+        };
+        assert_eq!(run(&[Key::Enter]), accepted);
+
+        let navigated = SelectionOutcome {
+            index: 1,
+            was_default: false,
+            used_key: Key::Char(' '),
+        };
+        assert_eq!(
+            run(&[Key::ArrowDown, Key::ArrowUp, Key::Char(' ')]),
+            navigated
+        );
+    }
+
+    #[test]
+    fn test_keys_provided_outcome() {
+        let term = Term::buffered_stderr();
+        let mut select = Select::new();
+        select.page_provider(|offset, count| {
+            (offset..(offset + count).min(3))
+                .map(|idx| format!("Item {}", idx))
+                .collect()
+        });
+
+        let run = |select: &Select, keys: &[Key]| {
+            select
+                ._interact_on(&term, true, &ScriptedKeys::new(keys))
+                .unwrap()
+                .unwrap()
+        };
+
+        let outcome = run(&select, &[Key::Enter]);
+        assert_eq!(outcome.index, 0);
+        assert!(!outcome.was_default);
+
+        select.default(1);
+        assert!(run(&select, &[Key::Enter]).was_default);
+
+        let outcome = run(&select, &[Key::ArrowDown, Key::ArrowUp, Key::Enter]);
+        assert_eq!(outcome.index, 1);
+        assert!(!outcome.was_default);
+    }
+
+    #[test]
+    fn test_keys_default_on_later_page() {
+        let mut select = Select::new();
+        select
+            .items(&["a", "b", "c", "d", "e"])
+            .default(3)
+            .paged(true)
+            .max_length(2);
+
+        let (selection, output) =
+            crate::capture_output(|| select.interact_with_keys(&[Key::Enter]));
+        assert_eq!(selection.unwrap(), Some(3));
+        assert!(output.starts_with("  c\n> d\n"));
+    }
+
+    #[test]
+    fn test_interact_reported() {
+        let mut select = Select::new();
+        select.items(&["Apple", "Banana", "Cherry"]).default(0);
+
+        let keys = ScriptedKeys::new(&[Key::ArrowDown, Key::ArrowDown, Key::Enter]);
+        let (selection, report) = select
+            .interact_reported(&Term::buffered_stderr(), &keys)
+            .unwrap();
+
+        assert_eq!(selection, 2);
+        assert_eq!(report.keystrokes, 3);
+    }
+
+    #[test]
+    fn test_keys_shortcuts() {
+        let mut select = Select::new();
+        select
+            .vim_keys(false)
+            .item_with_key('n', "New file")
+            .item_with_key('j', "Join lines")
+            .item_with_key('o', "Open file")
+            .default(0);
+
+        let (selection, output) =
+            crate::capture_output(|| select.interact_with_keys(&[Key::Char('o')]));
+        assert_eq!(selection.unwrap(), Some(2));
+        assert!(output.starts_with("> (n) New file\n  (j) Join lines\n  (o) Open file\n"));
+
+        let keys = [Key::Char('j')];
+        assert_eq!(select.interact_with_keys(&keys).unwrap(), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "shortcut 'n' is already used by another item")]
+    fn test_duplicate_shortcut() {
+        Select::new()
+            .item_with_key('n', "New file")
+            .item_with_key('n', "New folder");
+    }
+
+    #[test]
+    #[should_panic(expected = "shortcut 'q' is already bound to an action")]
+    fn test_bound_shortcut() {
+        Select::new().item_with_key('q', "Quit");
+    }
+
+    #[test]
+    #[should_panic(expected = "shortcut '?' is already bound to an action")]
+    fn test_help_toggle_shortcut() {
+        Select::new()
+            .help_toggle_key(Some('?'))
+            .item_with_key('?', "Help");
+    }
+
+    #[test]
+    fn test_keys_help_toggle() {
+        let mut select = Select::new();
+        select.items(&["Apple", "Banana"]).default(0);
+
+        let keys = [Key::Char('?'), Key::Enter];
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(0));
+        assert!(!output.contains("up/down"));
+
+        select.help_toggle_key(Some('?'));
+        let keys = [Key::Char('?'), Key::Char('?'), Key::Enter];
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(0));
+        assert_eq!(output.matches("up/down: move, enter: select").count(), 1);
+
+        select.help_toggle_key(Some('j'));
+        let keys = [Key::Char('j'), Key::Enter];
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(1));
+        assert!(!output.contains("up/down"));
+    }
+
+    #[test]
+    fn test_keys_viewport() {
+        let mut select = Select::new();
+        select
+            .items(&["a", "b", "c", "d", "e", "f"])
+            .default(2)
+            .viewport(3);
+
+        let keys = [Key::ArrowDown, Key::End, Key::Enter];
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(5));
+        assert_eq!(output, "  b\n> c\n  d\n  c\n> d\n  e\n  d\n  e\n> f\n");
+    }
+
+    #[test]
+    fn test_keys_fuzzy_without_matches() {
+        let mut select = Select::new();
+        select.items(&["Apple", "Banana"]).fuzzy(true);
 
-        let mut render = TermThemeRenderer::new(term, self.theme);
-        let mut sel = self.default;
+        let keys = [
+            Key::Char('x'),
+            Key::Char('y'),
+            Key::Backspace,
+            Key::Enter,
+            Key::ArrowDown,
+            Key::Backspace,
+            Key::ArrowDown,
+            Key::Enter,
+        ];
+        assert_eq!(select.interact_with_keys(&keys).unwrap(), Some(1));
+    }
 
-        if let Some(ref prompt) = self.prompt {
-            render.select_prompt(prompt)?;
-        }
+    #[test]
+    fn test_keys_fuzzy_backspace_without_matches() {
+        let mut select = Select::new();
+        select.items(&["Apple", "Banana"]).fuzzy(true);
 
-        let mut size_vec = Vec::new();
+        let keys = [
+            Key::Char('x'),
+            Key::Char('z'),
+            Key::Backspace,
+            Key::Enter,
+            Key::Escape,
+        ];
+        assert_eq!(select.interact_with_keys(&keys).unwrap(), None);
 
-        for items in self
-            .items
-            .iter()
-            .flat_map(|i| i.split('\n'))
-            .collect::<Vec<_>>()
-        {
-            let size = &items.len();
-            size_vec.push(*size);
-        }
+        let keys = [Key::Char('z'), Key::Backspace, Key::Enter];
+        assert_eq!(select.interact_with_keys(&keys).unwrap(), Some(0));
+    }
 
-        loop {
-            for (idx, item) in self
-                .items
-                .iter()
-                .enumerate()
-                .skip(page * capacity)
-                .take(capacity)
-            {
-                render.select_prompt_item(item, sel == idx)?;
-            }
+    #[test]
+    fn test_keys_custom_bindings() {
+        let mut bindings = KeyBindings::arrows_only();
+        bindings
+            .bind(KeyAction::Up, Key::Char('w'))
+            .bind(KeyAction::Down, Key::Char('s'))
+            .bind(KeyAction::Confirm, Key::Tab)
+            .unbind(&Key::Enter);
 
-            term.hide_cursor()?;
-            term.flush()?;
+        let mut select = Select::new();
+        select
+            .items(&["Apple", "Banana", "Cherry", "Date"])
+            .default(0)
+            .key_bindings(bindings);
 
-            match term.read_key()? {
-                Key::ArrowDown | Key::Char('j') => {
-                    if sel == !0 {
-                        sel = 0;
-                    } else {
-                        sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
-                    }
-                }
-                Key::Escape | Key::Char('q') => {
-                    if allow_quit {
-                        if self.clear {
-                            term.clear_last_lines(self.items.len())?;
-                            term.show_cursor()?;
-                            term.flush()?;
-                        }
+        let run = |keys: &[Key]| select.interact_with_keys(keys).unwrap();
 
-                        return Ok(None);
-                    }
-                }
-                Key::ArrowUp | Key::Char('k') => {
-                    if sel == !0 {
-                        sel = self.items.len() - 1;
-                    } else {
-                        sel = ((sel as i64 - 1 + self.items.len() as i64)
-                            % (self.items.len() as i64)) as usize;
-                    }
+        assert_eq!(run(&[Key::Char('s'), Key::Char('s'), Key::Tab]), Some(2));
+        assert_eq!(run(&[Key::Char('w'), Key::Tab]), Some(3));
+        // Unbound keys keep jumping, unbound actions do nothing.
+        assert_eq!(run(&[Key::Char('c'), Key::Enter, Key::Tab]), Some(2));
+        assert_eq!(run(&[Key::Char('j'), Key::Tab]), Some(0));
+        assert_eq!(run(&[Key::Escape]), None);
+    }
+
+    #[test]
+    fn test_keys_wrap_around() {
+        let mut select = Select::new();
+        select.items(&["Apple", "Banana", "Cherry"]).default(0);
+
+        let run = |keys: &[Key]| select.interact_with_keys(keys).unwrap();
+
+        assert_eq!(run(&[Key::ArrowUp, Key::Enter]), Some(2));
+        assert_eq!(run(&[Key::End, Key::ArrowDown, Key::Enter]), Some(0));
+    }
+
+    #[test]
+    fn test_items_borrowed() {
+        const FRUITS: &[&str] = &["Apple", "Banana"];
+
+        let mut select = Select::new();
+        select.items_borrowed(FRUITS).item("Cherry").default(0);
+
+        assert!(select.items.iter().take(2).all(|item| match *item {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        }));
+        assert_eq!(
+            select
+                .interact_with_keys(&[Key::ArrowDown, Key::Enter])
+                .unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_items_iter() {
+        let pulled = std::cell::Cell::new(0);
+        let mut select = Select::new();
+        select
+            .items_iter((1..=1000).inspect(|_| pulled.set(pulled.get() + 1)))
+            .max_length(2)
+            .default(0);
+
+        assert_eq!(pulled.get(), 0);
+
+        let keys = [Key::ArrowDown, Key::ArrowDown, Key::Enter];
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(2));
+        assert_eq!(pulled.get(), 4);
+        assert_eq!(output.matches("loading…").count(), 2);
+    }
+
+    #[test]
+    fn test_keys_bell() {
+        let mut select = Select::new();
+        select
+            .items(&["Apple", "Banana"])
+            .default(0)
+            .wrap(false)
+            .bell(true);
+
+        let keys = [Key::ArrowUp, Key::Char('x'), Key::Enter];
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(0));
+        assert_eq!(output.matches('\x07').count(), 2);
+
+        select.bell(false);
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(0));
+        assert!(!output.contains('\x07'));
+    }
+
+    #[test]
+    fn test_keys_no_wrap() {
+        let mut select = Select::new();
+        select
+            .items(&["Apple", "Banana", "Cherry"])
+            .default(0)
+            .wrap(false);
+
+        let run = |keys: &[Key]| select.interact_with_keys(keys).unwrap();
+
+        assert_eq!(run(&[Key::ArrowUp, Key::Enter]), Some(0));
+        assert_eq!(run(&[Key::End, Key::ArrowDown, Key::Enter]), Some(2));
+
+        select.item_disabled("Date", true);
+        assert_eq!(
+            select
+                .interact_with_keys(&[Key::End, Key::ArrowDown, Key::Enter])
+                .unwrap(),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_keys_paging() {
+        let mut select = Select::new();
+        select
+            .items(&["a", "b", "c", "d", "e"])
+            .default(0)
+            .paged(true)
+            .max_length(2);
+
+        let run = |keys: &[Key]| select.interact_with_keys(keys).unwrap();
+
+        assert_eq!(run(&[Key::ArrowRight, Key::Enter]), Some(2));
+        assert_eq!(run(&[Key::ArrowLeft, Key::Enter]), Some(4));
+        assert_eq!(run(&[Key::PageDown, Key::Enter]), Some(2));
+    }
+
+    #[test]
+    fn test_keys_fuzzy_and_disabled() {
+        let mut select = Select::new();
+        select
+            .item("Apple")
+            .item_disabled("Banana", true)
+            .item("Blueberry")
+            .default(0);
+
+        assert_eq!(
+            select
+                .interact_with_keys(&[Key::ArrowDown, Key::Enter])
+                .unwrap(),
+            Some(2)
+        );
+
+        select.fuzzy(true);
+        assert_eq!(
+            select
+                .interact_with_keys(&[Key::Char('b'), Key::Char('l'), Key::Enter])
+                .unwrap(),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_keys_run_out() {
+        let err = Select::new()
+            .items(&["Apple", "Banana"])
+            .interact_with_keys(&[Key::ArrowDown])
+            .unwrap_err();
+
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_keys_capture_output() {
+        let (selection, output) = crate::capture_output(|| {
+            Select::new()
+                .with_prompt("Fruit")
+                .items(&["Apple", "Banana"])
+                .default(0)
+                .interact_with_keys(&[Key::ArrowDown, Key::Enter])
+        });
+
+        assert_eq!(selection.unwrap(), Some(1));
+        assert_eq!(
+            output,
+            "Fruit:\n> Apple\n  Banana\n  Apple\n> Banana\nFruit: Banana\n"
+        );
+    }
+
+    #[test]
+    fn test_keys_header_footer() {
+        let (selection, output) = crate::capture_output(|| {
+            Select::new()
+                .with_prompt("Fruit")
+                .items(&["Apple", "Banana"])
+                .default(0)
+                .header("Pick one")
+                .footer("enter: select")
+                .interact_with_keys(&[Key::ArrowDown, Key::Enter])
+        });
+
+        assert_eq!(selection.unwrap(), Some(1));
+        assert_eq!(
+            output,
+            "Fruit:\nPick one\n> Apple\n  Banana\nenter: select\n\
+             Pick one\n  Apple\n> Banana\nenter: select\nFruit: Banana\n"
+        );
+    }
+
+    #[test]
+    fn test_keys_tall_header() {
+        let header = vec!["line"; 30].join("\n");
+        let (selection, _) = crate::capture_output(|| {
+            Select::new()
+                .items(&["Apple", "Banana", "Cherry"])
+                .default(0)
+                .paged(true)
+                .header(&header)
+                .footer("enter: select")
+                .interact_with_keys(&[Key::ArrowDown, Key::Enter])
+        });
+
+        assert_eq!(selection.unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_keys_provided_header_footer() {
+        let (selection, output) = crate::capture_output(|| {
+            Select::new()
+                .with_prompt("Page")
+                .page_provider(|offset, count| {
+                    (offset..(offset + count).min(2))
+                        .map(|idx| format!("Item {}", idx))
+                        .collect()
+                })
+                .header("Pick one")
+                .footer("enter: select")
+                .interact_with_keys(&[Key::ArrowDown, Key::Enter])
+        });
+
+        assert_eq!(selection.unwrap(), Some(1));
+        assert_eq!(
+            output,
+            "Page:\nloading…\nPick one\n> Item 0\n  Item 1\nenter: select\n\
+             Pick one\n  Item 0\n> Item 1\nenter: select\nPage: Item 1\n"
+        );
+    }
+
+    #[test]
+    fn test_keys_group_headers() {
+        let (selection, output) = crate::capture_output(|| {
+            Select::new()
+                .group_header("Fruits")
+                .item("Apple")
+                .group_header("Vegetables")
+                .item("Carrot")
+                .default(0)
+                .interact_with_keys(&[Key::ArrowDown, Key::Enter])
+        });
+
+        assert_eq!(selection.unwrap(), Some(1));
+        assert_eq!(
+            output,
+            "-- Fruits --\n> Apple\n-- Vegetables --\n  Carrot\n\
+             -- Fruits --\n  Apple\n-- Vegetables --\n> Carrot\n"
+        );
+    }
+
+    #[test]
+    fn test_keys_trailing_group_header() {
+        let (selection, output) = crate::capture_output(|| {
+            Select::new()
+                .item("Apple")
+                .group_header("More soon")
+                .default(0)
+                .interact_with_keys(&[Key::Enter])
+        });
+
+        assert_eq!(selection.unwrap(), Some(0));
+        assert_eq!(output, "> Apple\n-- More soon --\n");
+    }
+
+    #[test]
+    fn test_page_capacity() {
+        assert_eq!(page_capacity(&[0, 0, 0, 0], 10, 3, false), 3);
+        assert_eq!(page_capacity(&[1, 0, 1, 0], 10, 4, false), 2);
+        assert_eq!(page_capacity(&[1, 0, 1, 0], 10, 4, true), 2);
+        assert_eq!(page_capacity(&[0, 1, 0, 1], 10, 3, true), 2);
+        assert_eq!(page_capacity(&[0, 1, 0, 1], 10, 2, true), 1);
+        assert_eq!(page_capacity(&[1, 0, 0, 0], 2, 10, false), 2);
+        assert_eq!(page_capacity(&[3], 10, 2, false), 1);
+
+        let mut select = Select::new();
+        select
+            .group_header("Fruits")
+            .items(&["Apple", "Banana"])
+            .group_header("More soon");
+        assert_eq!(select.header_rows(&[0, 1]), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_keys_validator() {
+        let mut select = Select::new();
+        select
+            .items(&["Community", "Enterprise"])
+            .default(0)
+            .validate_with(|index: &usize| -> std::result::Result<(), &str> {
+                if *index == 1 {
+                    Err("This option requires a license")
+                } else {
+                    Ok(())
                 }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if self.paged {
-                        if page == 0 {
-                            page = pages - 1;
-                        } else {
-                            page -= 1;
-                        }
+            });
 
-                        sel = page * capacity;
-                    }
+        let (selection, output) = crate::capture_output(|| {
+            select.interact_with_keys(&[Key::ArrowDown, Key::Enter, Key::ArrowUp, Key::Enter])
+        });
+
+        assert_eq!(selection.unwrap(), Some(0));
+        assert!(output.contains("error: This option requires a license\n"));
+    }
+
+    #[test]
+    fn test_keys_validator_provided() {
+        let mut select = Select::new();
+        select
+            .page_provider(|offset, count| {
+                (offset..(offset + count).min(3))
+                    .map(|idx| format!("Item {}", idx))
+                    .collect()
+            })
+            .validate_with(|index: &usize| -> std::result::Result<(), &str> {
+                if *index == 0 {
+                    Err("Pick another item")
+                } else {
+                    Ok(())
                 }
-                Key::ArrowRight | Key::Char('l') => {
-                    if self.paged {
-                        if page == pages - 1 {
-                            page = 0;
-                        } else {
-                            page += 1;
-                        }
+            });
 
-                        sel = page * capacity;
-                    }
+        let (selection, output) = crate::capture_output(|| {
+            select.interact_with_keys(&[Key::Enter, Key::ArrowDown, Key::Enter])
+        });
+
+        assert_eq!(selection.unwrap(), Some(1));
+        assert!(output.contains("error: Pick another item\n"));
+    }
+
+    #[test]
+    fn test_keys_indent() {
+        let mut select = Select::new();
+        select
+            .with_prompt("Fruit")
+            .items(&["Apple", "Banana\nSplit"])
+            .validate_with(|index: &usize| -> std::result::Result<(), &str> {
+                if *index == 0 {
+                    Err("Out of stock")
+                } else {
+                    Ok(())
                 }
+            })
+            .indent(2);
 
-                Key::Enter | Key::Char(' ') if sel != !0 => {
-                    if self.clear {
-                        render.clear()?;
-                    }
+        let (selection, output) = crate::capture_output(|| {
+            select.interact_with_keys(&[Key::Enter, Key::ArrowDown, Key::Enter])
+        });
 
-                    if let Some(ref prompt) = self.prompt {
-                        render.select_prompt_selection(prompt, &self.items[sel])?;
-                    }
+        assert_eq!(selection.unwrap(), Some(1));
+        assert!(output.contains("  error: Out of stock\n"));
+        assert!(output.ends_with("  Fruit: Banana\n  Split\n"));
+        for line in output.lines().filter(|line| !line.is_empty()) {
+            assert!(line.starts_with("  "), "{:?}", line);
+        }
+    }
 
-                    term.show_cursor()?;
-                    term.flush()?;
+    #[test]
+    fn test_keys_filter_matches() {
+        let mut select = Select::new();
+        select
+            .items(&["Apple", "Apricot", "Banana"])
+            .default(0)
+            .fuzzy(true);
 
-                    return Ok(Some(sel));
-                }
-                _ => {}
-            }
+        let keys = [Key::Char('a'), Key::Char('p'), Key::Enter];
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(0));
+        assert!(output.starts_with("filter: \n"));
+        assert!(output.contains("filter: a  (3/3 matches)\n"));
+        assert!(output.contains("filter: ap  (2/3 matches)\n"));
+    }
 
-            if sel != !0 && (sel < page * capacity || sel >= (page + 1) * capacity) {
-                page = sel / capacity;
-            }
+    #[test]
+    fn test_keys_truncate_items() {
+        let columns = Term::buffered_stderr().size().1 as usize;
+        let long = "x".repeat(columns * 2);
 
-            render.clear_preserve_prompt(&size_vec)?;
+        let mut select = Select::new();
+        select
+            .items(&[long.as_str(), "Short"])
+            .default(0)
+            .truncate_items(true);
+
+        let keys = [Key::ArrowDown, Key::Enter];
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(1));
+        assert_eq!(output.lines().count(), 4);
+        for line in output.lines() {
+            assert!(measure_text_width(line) <= columns);
         }
+        assert!(output.contains("…\n> Short\n"));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_keys_pre_styled_redraw() {
+        let items: Vec<String> = ["fn", "let"]
+            .iter()
+            .map(|item| console::style(item).red().force_styling(true).to_string())
+            .collect();
+
+        let mut select = Select::new();
+        select.items(&items).pre_styled_items(true).default(0);
+
+        let (selection, output) =
+            crate::capture_output(|| select.interact_with_keys(&[Key::ArrowDown, Key::Enter]));
+
+        assert_eq!(selection.unwrap(), Some(1));
+        let first = output.find("> fn\n  let\n").unwrap();
+        let redraw = output.find("  fn\n> let\n").unwrap();
+        assert!(first < redraw);
+    }
+
+    #[test]
+    fn test_keys_page_indicator() {
+        let mut select = Select::new();
+        select
+            .items(&["a", "b", "c", "d", "e"])
+            .paged(true)
+            .max_length(2);
+
+        let (_, output) =
+            crate::capture_output(|| select.interact_with_keys(&[Key::ArrowRight, Key::Enter]));
+        assert!(output.contains("(page 1/3)\n"));
+        assert!(output.contains("(page 2/3)\n"));
+
+        select.max_length(5);
+        let (_, output) = crate::capture_output(|| select.interact_with_keys(&[Key::Enter]));
+        assert!(!output.contains("page"));
+    }
+
+    #[test]
+    fn test_keys_report() {
+        let mut select = Select::new();
+        select
+            .with_prompt("Fruit")
+            .items(&["Apple", "Banana"])
+            .default(0);
+
+        let (_, output) = crate::capture_output(|| select.interact_with_keys(&[Key::Enter]));
+        assert!(output.ends_with("Fruit: Apple\n"));
+
+        select.report(false);
+        let (_, output) = crate::capture_output(|| select.interact_with_keys(&[Key::Enter]));
+        assert!(!output.contains("Fruit: Apple"));
+    }
+
+    #[test]
+    fn test_piped() {
+        let term = Term::buffered_stderr();
+        let lines = piped::MockLines::new(&["2", "banana", "", "Apple", "4"]);
+
+        let mut select = Select::new();
+        select
+            .items(&["Apple", "Banana"])
+            .item_disabled("Cherry", true);
+        select.default(1);
+
+        let read = |select: &Select| select.interact_piped(&term, &lines);
+
+        assert_eq!(
+            io::Error::from(read(&select).unwrap_err()).kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(read(&select).unwrap().index, 1);
+
+        let outcome = read(&select).unwrap();
+        assert_eq!(outcome.index, 1);
+        assert!(outcome.was_default);
+
+        assert_eq!(read(&select).unwrap().index, 0);
+        assert_eq!(
+            io::Error::from(read(&select).unwrap_err()).kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn test_timeout_resolves_to_default() {
+        let outcome = Select::new()
+            .items(&["a", "b", "c"])
+            .default(1)
+            .timeout(Duration::from_millis(10))
+            .interact_detailed_on(&Term::buffered_stderr())
+            .unwrap();
+
+        assert_eq!(outcome.index, 1);
+        assert_eq!(outcome.used_key, Key::Unknown);
+    }
+
+    #[test]
+    fn test_timeout_rejected_default() {
+        let err = Select::new()
+            .items(&["a", "b", "c"])
+            .default(1)
+            .timeout(Duration::from_millis(10))
+            .validate_with(|index: &usize| if *index == 1 { Err("no") } else { Ok(()) })
+            .interact_on(&Term::buffered_stderr())
+            .unwrap_err();
+
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_timeout_without_default() {
+        let err = Select::new()
+            .items(&["a", "b", "c"])
+            .timeout(Duration::from_millis(10))
+            .interact_on(&Term::buffered_stderr())
+            .unwrap_err();
+
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::TimedOut);
+    }
 
     #[test]
     fn test_str() {
@@ -411,4 +2947,235 @@ mod tests {
             selections
         );
     }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("Chocolate Muffin", ""));
+        assert!(fuzzy_match("Chocolate Muffin", "cmuf"));
+        assert!(fuzzy_match("Chocolate Muffin", "CHOC"));
+        assert!(!fuzzy_match("Chocolate Muffin", "fc"));
+        assert!(!fuzzy_match("Chocolate Muffin", "chocolatey"));
+    }
+
+    #[test]
+    fn test_fuzzy_matches() {
+        let mut select = Select::new();
+        select.items(&["Ice Cream", "Vanilla Cupcake", "Chocolate Muffin"]);
+
+        assert_eq!(select.fuzzy_matches("c"), vec![0, 1, 2]);
+        assert_eq!(select.fuzzy_matches("cup"), vec![1]);
+        assert_eq!(select.fuzzy_matches("xyz"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_jump_to() {
+        let mut select = Select::new();
+        select.items(&["banana", "Apple", "cherry", "apricot"]);
+        let visible = vec![0, 1, 2, 3];
+
+        assert_eq!(select.jump_to(&visible, !0, 'a'), Some(1));
+        assert_eq!(select.jump_to(&visible, 1, 'a'), Some(3));
+        assert_eq!(select.jump_to(&visible, 3, 'A'), Some(1));
+        assert_eq!(select.jump_to(&visible, 2, 'c'), Some(2));
+        assert_eq!(select.jump_to(&visible, 0, 'x'), None);
+        assert_eq!(select.jump_to(&[3, 0], 0, 'a'), Some(0));
+    }
+
+    #[test]
+    fn test_confirmation_phrase() {
+        let mut select = Select::new();
+        select
+            .items(&["Keep", "\u{1b}[31mDelete everything\u{1b}[0m "])
+            .require_typed_confirmation(&[1]);
+
+        assert_eq!(select.typed_confirmation, vec![1]);
+        assert_eq!(select.confirmation_phrase(1), "Delete everything");
+    }
+
+    #[test]
+    fn test_keys_typed_confirmation() {
+        let mut select = Select::new();
+        select
+            .with_prompt("Action")
+            .items(&["Keep", "Delete"])
+            .require_typed_confirmation(&[1]);
+
+        let typed = |text: &str| -> Vec<Key> {
+            let mut keys = vec![Key::Enter];
+            keys.extend(text.chars().map(Key::Char));
+            keys.push(Key::Enter);
+            keys
+        };
+
+        let mut keys = vec![Key::ArrowDown];
+        keys.extend(typed("Delet"));
+        keys.extend(typed("Delete"));
+        let (selection, output) = crate::capture_output(|| select.interact_with_keys(&keys));
+        assert_eq!(selection.unwrap(), Some(1));
+        assert!(output.ends_with("Action: Delete\n"));
+
+        let mut keys = vec![Key::ArrowDown];
+        keys.extend(typed(""));
+        keys.push(Key::Escape);
+        assert_eq!(select.interact_with_keys(&keys).unwrap(), None);
+    }
+
+    #[test]
+    fn test_keys_disabled_default() {
+        let mut select = Select::new();
+        select
+            .item("Apple")
+            .item_disabled("Banana", true)
+            .item("Cherry")
+            .default(1);
+
+        let (outcome, output) = crate::capture_output(|| {
+            select._interact_on(
+                &Term::buffered_stderr(),
+                false,
+                &ScriptedKeys::new(&[Key::Enter]),
+            )
+        });
+        let outcome = outcome.unwrap().unwrap();
+        assert_eq!(outcome.index, 2);
+        assert!(!outcome.was_default);
+        assert!(output.contains("> Cherry\n"));
+    }
+
+    #[test]
+    fn test_skip_disabled() {
+        let mut select = Select::new();
+        select
+            .item_disabled("Fruit", true)
+            .item("Apple")
+            .item_disabled("Vegetables", true)
+            .item("Carrot");
+        let visible = vec![0, 1, 2, 3];
+
+        assert_eq!(select.skip_disabled(&visible, 0, false), 1);
+        assert_eq!(select.skip_disabled(&visible, 2, false), 3);
+        assert_eq!(select.skip_disabled(&visible, 2, true), 1);
+        assert_eq!(select.skip_disabled(&visible, 0, true), 3);
+        assert_eq!(select.skip_disabled(&[0, 2], 0, false), 0);
+        assert_eq!(select.jump_to(&visible, 1, 'v'), None);
+    }
+
+    #[test]
+    fn test_sink_disabled() {
+        let mut select = Select::new();
+        select
+            .item("Apple")
+            .item_disabled("Banana", true)
+            .item("Cherry")
+            .item_disabled("Date", true)
+            .item("Elderberry");
+
+        assert_eq!(select.display_order(0..5), vec![0, 1, 2, 3, 4]);
+
+        select.sink_disabled(true);
+        let visible = select.display_order(0..5);
+        assert_eq!(visible, vec![0, 2, 4, 1, 3]);
+        assert_eq!(visible[select.skip_disabled(&visible, 2, false)], 4);
+        assert_eq!(visible[select.skip_disabled(&visible, 3, false)], 0);
+        assert_eq!(select.fuzzy_matches("a"), vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_no_items_message() {
+        let term = Term::buffered_stderr();
+        let mut select = Select::new();
+        select.no_items_message("No branches to check out");
+
+        let err = select.interact_on(&term).unwrap_err();
+        assert_eq!(err.to_string(), "No branches to check out");
+        assert_eq!(select.interact_on_opt(&term).unwrap(), None);
+    }
+
+    #[test]
+    fn test_memory_preselects() {
+        let mut memory = HashMap::new();
+        memory.store("flavor", 1);
+
+        let mut select = Select::new();
+        select
+            .default(0)
+            .items(&["a", "b"])
+            .with_memory("flavor", &mut memory);
+
+        assert_eq!(select.initial_selection(), 1);
+    }
+
+    #[test]
+    fn test_memory_out_of_range() {
+        let mut memory = HashMap::new();
+        memory.store("flavor", 5);
+
+        let mut select = Select::new();
+        select
+            .default(0)
+            .items(&["a", "b"])
+            .with_memory("flavor", &mut memory);
+
+        assert_eq!(select.initial_selection(), 0);
+    }
+
+    #[test]
+    fn test_paged_items() {
+        let calls = RefCell::new(vec![]);
+        let provider = |offset: usize, count: usize| {
+            calls.borrow_mut().push((offset, count));
+            (offset..(offset + count).min(7))
+                .map(|n| format!("Item {}", n))
+                .collect()
+        };
+        let mut items = PagedItems::new(&provider, 3);
+
+        assert_eq!(items.page(1), ["Item 3", "Item 4", "Item 5"]);
+        assert!(items.contains(5));
+        assert_eq!(items.total, None);
+
+        assert!(items.contains(6));
+        assert!(!items.contains(7));
+        assert_eq!(items.total, Some(7));
+        assert_eq!(items.page(2), ["Item 6"]);
+
+        assert_eq!(*calls.borrow(), [(3, 3), (6, 3)]);
+    }
+
+    #[test]
+    fn test_paged_items_exact_pages() {
+        let provider = |offset: usize, count: usize| {
+            (offset..(offset + count).min(4))
+                .map(|n| n.to_string())
+                .collect()
+        };
+        let mut items = PagedItems::new(&provider, 2);
+
+        assert!(items.contains(3));
+        assert_eq!(items.total, None);
+        assert!(!items.contains(4));
+        assert_eq!(items.total, Some(4));
+    }
+
+    #[test]
+    fn test_description_layout() {
+        let mut select = Select::new();
+        select
+            .item_with_description("debug", "Fast builds with debug info")
+            .item("check")
+            .item_with_description("release", "Optimized builds");
+
+        assert_eq!(select.descriptions[1], None);
+        assert_eq!(select.description_column(), 9);
+
+        assert!(select.inline_descriptions(80));
+        assert!(select.inline_descriptions(38));
+        assert!(!select.inline_descriptions(37));
+
+        select.description_layout(DescriptionLayout::Inline);
+        assert!(select.inline_descriptions(20));
+
+        select.description_layout(DescriptionLayout::BelowActive);
+        assert!(!select.inline_descriptions(80));
+    }
 }
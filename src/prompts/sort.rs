@@ -1,9 +1,18 @@
-use std::{io, ops::Rem};
+use std::{io, mem, ops::Rem};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::{
+    cursor::HiddenCursor,
+    error::{Error, Result},
+    keys::{KeySource, ScriptedKeys, TermKeys},
+    piped::{self, LineSource},
+    prompts::multi_select::SelectionResult,
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
 
 use console::{Key, Term};
 
+type OrderValidatorCallback<'a> = Box<dyn Fn(&[usize]) -> std::result::Result<(), String> + 'a>;
+
 /// Renders a sort prompt.
 ///
 /// Returns list of indices in original items list sorted according to user input.
@@ -26,7 +35,17 @@ pub struct Sort<'a> {
     prompt: Option<String>,
     clear: bool,
     theme: &'a dyn Theme,
+    indent: usize,
+    prompt_icon: Option<String>,
+    wizard_step: Option<(usize, usize)>,
+    term: Option<Term>,
+    truncate_echo: bool,
     paged: bool,
+    wrap: bool,
+    show_positions: bool,
+    max_length: Option<usize>,
+    validator: Option<OrderValidatorCallback<'a>>,
+    initial_order: Option<Vec<usize>>,
 }
 
 impl<'a> Default for Sort<'a> {
@@ -48,7 +67,17 @@ impl<'a> Sort<'a> {
             clear: true,
             prompt: None,
             theme,
+            indent: 0,
+            prompt_icon: None,
+            wizard_step: None,
+            term: None,
+            truncate_echo: false,
             paged: false,
+            wrap: true,
+            show_positions: false,
+            max_length: None,
+            validator: None,
+            initial_order: None,
         }
     }
 
@@ -58,6 +87,35 @@ impl<'a> Sort<'a> {
         self
     }
 
+    /// Limits the number of items shown per page when paging is enabled.
+    ///
+    /// By default a page takes up the whole height of the terminal. With a
+    /// maximum length set a page holds at most `val` items, but never more
+    /// than fit into the terminal.
+    pub fn max_length(&mut self, val: usize) -> &mut Sort<'a> {
+        self.max_length = Some(val);
+        self
+    }
+
+    /// Indicates whether the selection wraps around at the ends
+    ///
+    /// With wrapping disabled moving up from the first item or down from
+    /// the last one keeps the selection where it is.
+    ///
+    /// Wrapping is enabled by default
+    pub fn wrap(&mut self, val: bool) -> &mut Sort<'a> {
+        self.wrap = val;
+        self
+    }
+
+    /// Indicates whether the items are numbered by their current position
+    ///
+    /// Positions are not shown by default
+    pub fn show_positions(&mut self, val: bool) -> &mut Sort<'a> {
+        self.show_positions = val;
+        self
+    }
+
     /// Sets the clear behavior of the menu.
     ///
     /// The default is to clear the menu after user interaction.
@@ -66,6 +124,57 @@ impl<'a> Sort<'a> {
         self
     }
 
+    /// Indents every rendered line by `val` spaces, see
+    /// [Select::indent](struct.Select.html#method.indent).
+    pub fn indent(&mut self, val: usize) -> &mut Sort<'a> {
+        self.indent = val;
+        self
+    }
+
+    /// Sets an icon rendered in front of the prompt, see
+    /// [Select::prompt_icon](struct.Select.html#method.prompt_icon).
+    pub fn prompt_icon<S: Into<String>>(&mut self, val: S) -> &mut Sort<'a> {
+        self.prompt_icon = Some(val.into());
+        self
+    }
+
+    /// Shows the position of the prompt in a sequence of prompts.
+    ///
+    /// The theme renders e.g. `Step 3/7` in front of the prompt and the
+    /// final selection, see
+    /// [Theme::format_step](../theme/trait.Theme.html#method.format_step).
+    pub fn wizard_step(&mut self, current: usize, total: usize) -> &mut Sort<'a> {
+        self.wizard_step = Some((current, total));
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on.
+    ///
+    /// The `interact` methods that don't take a terminal use this one
+    /// instead of stderr.
+    pub fn term(&mut self, term: Term) -> &mut Sort<'a> {
+        self.term = Some(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    ///
+    /// This is the terminal set with [term](#method.term), or stderr.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.target_term().size()
+    }
+
+    /// Truncates the echoed order to the width of the terminal.
+    ///
+    /// Long lists are cut off with an ellipsis instead of wrapping onto
+    /// the following lines.
+    ///
+    /// Disabled by default.
+    pub fn truncate_echo(&mut self, val: bool) -> &mut Sort<'a> {
+        self.truncate_echo = val;
+        self
+    }
+
     /// Add a single item to the selector.
     pub fn item<T: ToString>(&mut self, item: T) -> &mut Sort<'a> {
         self.items.push(item.to_string());
@@ -80,6 +189,17 @@ impl<'a> Sort<'a> {
         self
     }
 
+    /// Sets the order the items are initially shown in.
+    ///
+    /// This is useful to let the user revise a previously saved order. The
+    /// order must contain every index of the items exactly once, otherwise
+    /// interaction fails with an error. The returned indices still refer to
+    /// the positions the items were added at.
+    pub fn initial_order(&mut self, order: &[usize]) -> &mut Sort<'a> {
+        self.initial_order = Some(order.to_vec());
+        self
+    }
+
     /// Prefaces the menu with a prompt.
     ///
     /// When a prompt is set the system also prints out a confirmation after
@@ -89,27 +209,155 @@ impl<'a> Sort<'a> {
         self
     }
 
+    /// Registers a validator for the final order.
+    ///
+    /// The validator is invoked with the chosen order when the user hits
+    /// enter. If it returns an error the message is shown and the user can
+    /// keep reordering.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use dialoguer::Sort;
+    /// let order = Sort::new()
+    ///     .items(&["build", "test", "deploy"])
+    ///     .validate_with(|order: &[usize]| -> Result<(), String> {
+    ///         let build = order.iter().position(|&i| i == 0).unwrap();
+    ///         let deploy = order.iter().position(|&i| i == 2).unwrap();
+    ///
+    ///         if build < deploy {
+    ///             Ok(())
+    ///         } else {
+    ///             Err("build must come before deploy".into())
+    ///         }
+    ///     })
+    ///     .interact()
+    ///     .unwrap();
+    /// ```
+    pub fn validate_with<F>(&mut self, validator: F) -> &mut Sort<'a>
+    where
+        F: Fn(&[usize]) -> std::result::Result<(), String> + 'a,
+    {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// The user can order the items with the space bar and the arrows.
-    /// On enter the ordered list will be returned.
-    pub fn interact(&self) -> io::Result<Vec<usize>> {
-        self.interact_on(&Term::stderr())
+    /// Home and end, or `g` and `G`, move a picked item straight to the
+    /// first or last position. On enter the ordered list will be returned.
+    pub fn interact(&self) -> Result<Vec<usize>> {
+        self.interact_on(&self.target_term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// This method is similar to [interact_on_opt](#method.interact_on_opt) except for the fact that it does not allow selection of the terminal.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    /// Result contains `Some(order)` if the user confirmed with enter or `None` if user cancelled with 'Esc' or 'q'.
+    pub fn interact_opt(&self) -> Result<Option<Vec<usize>>> {
+        self.interact_on_opt(&self.target_term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<Vec<usize>> {
+    #[inline]
+    pub fn interact_on(&self, term: &Term) -> Result<Vec<usize>> {
+        self._interact_on(term, false, &TermKeys)?
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &Term) -> Result<Option<Vec<usize>>> {
+        self._interact_on(term, true, &TermKeys)
+    }
+
+    /// Enables user interaction and returns the indices and texts of the
+    /// items in their new order.
+    ///
+    /// Behaves like [interact](#method.interact) otherwise.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_detailed(&self) -> Result<SelectionResult> {
+        self.interact_detailed_on(&self.target_term())
+    }
+
+    /// Like [interact_detailed](#method.interact_detailed) but allows a specific terminal to be set.
+    pub fn interact_detailed_on(&self, term: &Term) -> Result<SelectionResult> {
+        let order = self.interact_on(term)?;
+        Ok(SelectionResult::new(order, &self.items))
+    }
+
+    /// Runs the prompt on a fixed sequence of keys instead of user input.
+    ///
+    /// This allows testing code that drives a sort prompt without a
+    /// terminal. The prompt is rendered to a buffered stderr. Like
+    /// [interact_opt](#method.interact_opt) the result is `None` if the keys
+    /// cancel the prompt. Running out of keys before the prompt is finished
+    /// is an error of kind `UnexpectedEof`.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use dialoguer::{console::Key, Sort};
+    ///
+    /// let order = Sort::new()
+    ///     .items(&["Build", "Test", "Deploy"])
+    ///     .interact_with_keys(&[Key::Char(' '), Key::ArrowDown, Key::Enter])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(order, Some(vec![1, 0, 2]));
+    /// ```
+    pub fn interact_with_keys(&self, keys: &[Key]) -> Result<Option<Vec<usize>>> {
+        self.interact_with_keys_on(&Term::buffered_stderr(), keys)
+    }
+
+    /// Like [interact_with_keys](#method.interact_with_keys) but allows a specific terminal to be set.
+    pub fn interact_with_keys_on(&self, term: &Term, keys: &[Key]) -> Result<Option<Vec<usize>>> {
+        self._interact_on(term, true, &ScriptedKeys::new(keys))
+    }
+
+    /// Returns the terminal set with [term](#method.term), or stderr.
+    fn target_term(&self) -> Term {
+        self.term.clone().unwrap_or_else(Term::stderr)
+    }
+
+    /// Like `interact` but allows a specific terminal to be set.
+    fn _interact_on(
+        &self,
+        term: &Term,
+        allow_quit: bool,
+        keys: &dyn KeySource,
+    ) -> Result<Option<Vec<usize>>> {
         let mut page = 0;
 
         if self.items.is_empty() {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
                 "Empty list of items given to `Sort`",
-            ));
+            )
+            .into());
+        }
+
+        let mut order = match self.initial_order {
+            Some(ref order) if is_permutation(order, self.items.len()) => order.clone(),
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Initial order given to `Sort` is not a permutation of its items",
+                )
+                .into());
+            }
+            None => (0..self.items.len()).collect(),
+        };
+
+        if !keys.is_scripted() && piped::is_piped(term) {
+            return self.interact_piped(term, &piped::Stdin, order).map(Some);
         }
 
         let capacity = if self.paged {
-            term.size().0 as usize - 1
+            let capacity = term.size().0 as usize - 1;
+            self.max_length
+                .map_or(capacity, |max| max.max(1).min(capacity))
         } else {
             self.items.len()
         };
@@ -117,6 +365,11 @@ impl<'a> Sort<'a> {
         let pages = (self.items.len() as f64 / capacity as f64).ceil() as usize;
 
         let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_truncate_echo(self.truncate_echo);
+        let _cursor = HiddenCursor::new(term)?;
         let mut sel = 0;
 
         if let Some(ref prompt) = self.prompt {
@@ -130,8 +383,8 @@ impl<'a> Sort<'a> {
             size_vec.push(*size);
         }
 
-        let mut order: Vec<_> = (0..self.items.len()).collect();
         let mut checked: bool = false;
+        let mut error: Option<String> = None;
 
         loop {
             for (idx, item) in order
@@ -140,19 +393,35 @@ impl<'a> Sort<'a> {
                 .skip(page * capacity)
                 .take(capacity)
             {
-                render.sort_prompt_item(&self.items[*item], checked, sel == idx)?;
+                if self.show_positions {
+                    render.sort_prompt_positioned_item(
+                        &self.items[*item],
+                        checked,
+                        sel == idx,
+                        idx + 1,
+                        order.len(),
+                    )?;
+                } else {
+                    render.sort_prompt_item(&self.items[*item], checked, sel == idx)?;
+                }
+            }
+
+            if let Some(ref err) = error {
+                render.validation_error(err)?;
             }
 
-            term.hide_cursor()?;
             term.flush()?;
 
-            match term.read_key()? {
+            error = None;
+
+            // Without a timeout a key is always read.
+            match keys.read_key(term, None)?.unwrap_or(Key::Unknown) {
                 Key::ArrowDown | Key::Char('j') => {
                     let old_sel = sel;
 
                     if sel == !0 {
                         sel = 0;
-                    } else {
+                    } else if self.wrap || sel + 1 < self.items.len() {
                         sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
                     }
 
@@ -165,7 +434,7 @@ impl<'a> Sort<'a> {
 
                     if sel == !0 {
                         sel = self.items.len() - 1;
-                    } else {
+                    } else if self.wrap || sel > 0 {
                         sel = ((sel as i64 - 1 + self.items.len() as i64)
                             % (self.items.len() as i64)) as usize;
                     }
@@ -174,67 +443,92 @@ impl<'a> Sort<'a> {
                         order.swap(old_sel, sel);
                     }
                 }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if self.paged {
-                        let old_sel = sel;
-                        let old_page = page;
+                Key::ArrowLeft | Key::Char('h') if self.paged => {
+                    let old_sel = sel;
+                    let old_page = page;
 
-                        if page == 0 {
-                            page = pages - 1;
-                        } else {
-                            page -= 1;
-                        }
+                    if page == 0 {
+                        page = pages - 1;
+                    } else {
+                        page -= 1;
+                    }
 
-                        sel = page * capacity;
+                    sel = page * capacity;
 
-                        if checked {
-                            let indexes: Vec<_> = if old_page == 0 {
-                                let indexes1: Vec<_> = (0..=old_sel).rev().collect();
-                                let indexes2: Vec<_> = (sel..self.items.len()).rev().collect();
-                                [indexes1, indexes2].concat()
-                            } else {
-                                (sel..=old_sel).rev().collect()
-                            };
+                    if checked {
+                        let indexes: Vec<_> = if old_page == 0 {
+                            let indexes1: Vec<_> = (0..=old_sel).rev().collect();
+                            let indexes2: Vec<_> = (sel..self.items.len()).rev().collect();
+                            [indexes1, indexes2].concat()
+                        } else {
+                            (sel..=old_sel).rev().collect()
+                        };
 
-                            for index in 0..(indexes.len() - 1) {
-                                order.swap(indexes[index], indexes[index + 1]);
-                            }
+                        for index in 0..(indexes.len() - 1) {
+                            order.swap(indexes[index], indexes[index + 1]);
                         }
                     }
                 }
-                Key::ArrowRight | Key::Char('l') => {
-                    if self.paged {
-                        let old_sel = sel;
-                        let old_page = page;
+                Key::ArrowRight | Key::Char('l') if self.paged => {
+                    let old_sel = sel;
+                    let old_page = page;
 
-                        if page == pages - 1 {
-                            page = 0;
-                        } else {
-                            page += 1;
-                        }
+                    if page == pages - 1 {
+                        page = 0;
+                    } else {
+                        page += 1;
+                    }
 
-                        sel = page * capacity;
+                    sel = page * capacity;
 
-                        if checked {
-                            let indexes: Vec<_> = if old_page == pages - 1 {
-                                let indexes1: Vec<_> = (old_sel..self.items.len()).collect();
-                                let indexes2: Vec<_> = vec![0];
-                                [indexes1, indexes2].concat()
-                            } else {
-                                (old_sel..=sel).collect()
-                            };
+                    if checked {
+                        let indexes: Vec<_> = if old_page == pages - 1 {
+                            let indexes1: Vec<_> = (old_sel..self.items.len()).collect();
+                            let indexes2: Vec<_> = vec![0];
+                            [indexes1, indexes2].concat()
+                        } else {
+                            (old_sel..=sel).collect()
+                        };
 
-                            for index in 0..(indexes.len() - 1) {
-                                order.swap(indexes[index], indexes[index + 1]);
-                            }
+                        for index in 0..(indexes.len() - 1) {
+                            order.swap(indexes[index], indexes[index + 1]);
                         }
                     }
                 }
+                Key::Home | Key::Char('g') => {
+                    if checked {
+                        move_item(&mut order, sel, 0);
+                    }
+
+                    sel = 0;
+                }
+                Key::End | Key::Char('G') => {
+                    if checked {
+                        move_item(&mut order, sel, self.items.len() - 1);
+                    }
+
+                    sel = self.items.len() - 1;
+                }
                 Key::Char(' ') => {
                     checked = !checked;
                 }
-                // TODO: Key::Escape
+                Key::Escape | Key::Char('q') if allow_quit => {
+                    if self.clear {
+                        render.clear()?;
+                        term.flush()?;
+                    }
+
+                    return Ok(None);
+                }
                 Key::Enter => {
+                    if let Some(ref validator) = self.validator {
+                        if let Err(err) = validator(&order) {
+                            error = Some(err);
+                            render.clear_preserve_prompt(&size_vec)?;
+                            continue;
+                        }
+                    }
+
                     if self.clear {
                         render.clear()?;
                     }
@@ -242,16 +536,14 @@ impl<'a> Sort<'a> {
                     if let Some(ref prompt) = self.prompt {
                         let list: Vec<_> = order
                             .iter()
-                            .enumerate()
-                            .map(|(_, item)| self.items[*item].as_str())
+                            .map(|item| self.items[*item].as_str())
                             .collect();
                         render.sort_prompt_selection(prompt, &list[..])?;
                     }
 
-                    term.show_cursor()?;
                     term.flush()?;
 
-                    return Ok(order);
+                    return Ok(Some(order));
                 }
                 _ => {}
             }
@@ -263,4 +555,150 @@ impl<'a> Sort<'a> {
             render.clear_preserve_prompt(&size_vec)?;
         }
     }
+
+    /// Reads the new order as a comma separated list of all item indices or
+    /// texts from `source`.
+    ///
+    /// A blank answer keeps the `order` the prompt starts with.
+    fn interact_piped(
+        &self,
+        term: &Term,
+        source: &dyn LineSource,
+        order: Vec<usize>,
+    ) -> Result<Vec<usize>> {
+        let order = match piped::read_answer(source)? {
+            Some(answer) => match piped::parse_indices(&answer, &self.items) {
+                Some(order) if is_permutation(&order, self.items.len()) => order,
+                _ => {
+                    return Err(piped::invalid_answer_error(&format!(
+                        "Invalid order: {}",
+                        answer
+                    )))
+                }
+            },
+            None => order,
+        };
+
+        if let Some(ref validator) = self.validator {
+            if let Err(err) = validator(&order) {
+                return Err(piped::invalid_answer_error(&err));
+            }
+        }
+
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        render.set_indent(self.indent);
+        render.set_prompt_icon(self.prompt_icon.as_deref());
+        render.set_step(self.wizard_step);
+        render.set_truncate_echo(self.truncate_echo);
+
+        if let Some(ref prompt) = self.prompt {
+            let list: Vec<_> = order
+                .iter()
+                .map(|item| self.items[*item].as_str())
+                .collect();
+            render.sort_prompt_selection(prompt, &list[..])?;
+        }
+
+        term.flush()?;
+
+        Ok(order)
+    }
+}
+
+/// Moves the entry of `order` at `from` to `to`, shifting the ones between.
+fn move_item(order: &mut Vec<usize>, from: usize, to: usize) {
+    let item = order.remove(from);
+    order.insert(to, item);
+}
+
+/// Checks if `order` contains every index below `len` exactly once.
+fn is_permutation(order: &[usize], len: usize) -> bool {
+    let mut seen = vec![false; len];
+
+    order.len() == len
+        && order
+            .iter()
+            .all(|&idx| idx < len && !mem::replace(&mut seen[idx], true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_item() {
+        let mut order = vec![0, 1, 2, 3];
+
+        move_item(&mut order, 2, 0);
+        assert_eq!(order, vec![2, 0, 1, 3]);
+
+        move_item(&mut order, 1, 3);
+        assert_eq!(order, vec![2, 1, 3, 0]);
+    }
+
+    #[test]
+    fn test_validate_with() {
+        let mut sort = Sort::new();
+        sort.items(&["build", "deploy"]).validate_with(
+            |order: &[usize]| -> std::result::Result<(), String> {
+                if order[0] == 0 {
+                    Ok(())
+                } else {
+                    Err("build must come before deploy".into())
+                }
+            },
+        );
+
+        let keys = [Key::Char(' '), Key::ArrowDown, Key::Enter];
+        let (order, output) = crate::capture_output(|| sort.interact_with_keys(&keys));
+        assert_eq!(
+            io::Error::from(order.unwrap_err()).kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert!(output.contains("build must come before deploy"));
+
+        let keys = [
+            Key::Char(' '),
+            Key::ArrowDown,
+            Key::Enter,
+            Key::ArrowUp,
+            Key::Enter,
+        ];
+        assert_eq!(sort.interact_with_keys(&keys).unwrap(), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_piped() {
+        let term = Term::buffered_stderr();
+        let lines = piped::MockLines::new(&["deploy, 0, test", "", "0, 1", "2, 0, 1"]);
+        let mut sort = Sort::new();
+        sort.items(&["build", "test", "deploy"]);
+
+        assert_eq!(
+            sort.interact_piped(&term, &lines, vec![0, 1, 2]).unwrap(),
+            vec![2, 0, 1]
+        );
+        assert_eq!(
+            sort.interact_piped(&term, &lines, vec![1, 0, 2]).unwrap(),
+            vec![1, 0, 2]
+        );
+        assert!(sort.interact_piped(&term, &lines, vec![0, 1, 2]).is_err());
+
+        sort.validate_with(|order: &[usize]| -> std::result::Result<(), String> {
+            if order[0] == 0 {
+                Ok(())
+            } else {
+                Err("build must come first".into())
+            }
+        });
+        assert!(sort.interact_piped(&term, &lines, vec![0, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_is_permutation() {
+        assert!(is_permutation(&[2, 0, 1], 3));
+        assert!(!is_permutation(&[0, 1], 3));
+        assert!(!is_permutation(&[0, 0, 1], 3));
+        assert!(!is_permutation(&[0, 1, 3], 3));
+    }
 }
@@ -0,0 +1,253 @@
+use crate::{
+    error::{Error, Result},
+    keys::{KeySource, TermKeys},
+    prompts::select::Select,
+    theme::Theme,
+};
+
+use console::Term;
+
+/// Renders a select prompt over typed items.
+///
+/// This works like [Select](struct.Select.html) but keeps the items
+/// themselves and returns a clone of the chosen one instead of its index.
+/// Each item is rendered through its `ToString` implementation.
+///
+/// ## Example usage
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::fmt;
+/// use dialoguer::TypedSelect;
+///
+/// #[derive(Clone)]
+/// enum Profile {
+///     Debug,
+///     Release,
+/// }
+///
+/// impl fmt::Display for Profile {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         match self {
+///             Profile::Debug => write!(f, "debug"),
+///             Profile::Release => write!(f, "release"),
+///         }
+///     }
+/// }
+///
+/// let profile = TypedSelect::new()
+///     .with_prompt("Build profile")
+///     .items(&[Profile::Debug, Profile::Release])
+///     .interact_item()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TypedSelect<'a, T> {
+    select: Select<'a>,
+    values: Vec<T>,
+}
+
+impl<'a, T> Default for TypedSelect<'a, T>
+where
+    T: Clone + ToString,
+{
+    fn default() -> TypedSelect<'a, T> {
+        TypedSelect::new()
+    }
+}
+
+impl<'a, T> TypedSelect<'a, T>
+where
+    T: Clone + ToString,
+{
+    /// Creates a typed select prompt builder with default theme.
+    pub fn new() -> TypedSelect<'a, T> {
+        TypedSelect {
+            select: Select::new(),
+            values: vec![],
+        }
+    }
+
+    /// Creates a typed select prompt builder with a specific theme.
+    pub fn with_theme(theme: &'a dyn Theme) -> TypedSelect<'a, T> {
+        TypedSelect {
+            select: Select::with_theme(theme),
+            values: vec![],
+        }
+    }
+
+    /// Enables or disables paging, see [Select::paged](struct.Select.html#method.paged).
+    pub fn paged(&mut self, val: bool) -> &mut TypedSelect<'a, T> {
+        self.select.paged(val);
+        self
+    }
+
+    /// Limits the page size, see [Select::max_length](struct.Select.html#method.max_length).
+    pub fn max_length(&mut self, val: usize) -> &mut TypedSelect<'a, T> {
+        self.select.max_length(val);
+        self
+    }
+
+    /// Enables or disables fuzzy filtering, see [Select::fuzzy](struct.Select.html#method.fuzzy).
+    pub fn fuzzy(&mut self, val: bool) -> &mut TypedSelect<'a, T> {
+        self.select.fuzzy(val);
+        self
+    }
+
+    /// Indicates whether the menu should be erased from the screen after interaction.
+    pub fn clear(&mut self, val: bool) -> &mut TypedSelect<'a, T> {
+        self.select.clear(val);
+        self
+    }
+
+    /// Indents every rendered line by `val` spaces, see
+    /// [Select::indent](struct.Select.html#method.indent).
+    pub fn indent(&mut self, val: usize) -> &mut TypedSelect<'a, T> {
+        self.select.indent(val);
+        self
+    }
+
+    /// Sets an icon rendered in front of the prompt, see
+    /// [Select::prompt_icon](struct.Select.html#method.prompt_icon).
+    pub fn prompt_icon<S: Into<String>>(&mut self, val: S) -> &mut TypedSelect<'a, T> {
+        self.select.prompt_icon(val);
+        self
+    }
+
+    /// Shows the position of the prompt in a sequence of prompts, see
+    /// [Select::wizard_step](struct.Select.html#method.wizard_step).
+    pub fn wizard_step(&mut self, current: usize, total: usize) -> &mut TypedSelect<'a, T> {
+        self.select.wizard_step(current, total);
+        self
+    }
+
+    /// Sets the terminal the prompt is rendered on, see
+    /// [Select::term](struct.Select.html#method.term).
+    pub fn term(&mut self, term: Term) -> &mut TypedSelect<'a, T> {
+        self.select.term(term);
+        self
+    }
+
+    /// Returns the size of the terminal the prompt is rendered on.
+    pub fn term_size(&self) -> (u16, u16) {
+        self.select.term_size()
+    }
+
+    /// Sets the initially selected item by its index.
+    pub fn default(&mut self, val: usize) -> &mut TypedSelect<'a, T> {
+        self.select.default(val);
+        self
+    }
+
+    /// Add a single item to the selector.
+    pub fn item(&mut self, item: T) -> &mut TypedSelect<'a, T> {
+        self.item_disabled(item, false)
+    }
+
+    /// Add a single item that can be disabled, see
+    /// [Select::item_disabled](struct.Select.html#method.item_disabled).
+    pub fn item_disabled(&mut self, item: T, disabled: bool) -> &mut TypedSelect<'a, T> {
+        self.select.item_disabled(item.to_string(), disabled);
+        self.values.push(item);
+        self
+    }
+
+    /// Adds multiple items to the selector.
+    pub fn items(&mut self, items: &[T]) -> &mut TypedSelect<'a, T> {
+        for item in items {
+            self.item(item.clone());
+        }
+        self
+    }
+
+    /// Sets the select prompt.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut TypedSelect<'a, T> {
+        self.select.with_prompt(prompt);
+        self
+    }
+
+    /// Enables user interaction and returns the chosen item.
+    ///
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_item(&self) -> Result<T> {
+        self.interact_item_on(&self.select.target_term())
+    }
+
+    /// Enables user interaction and returns the chosen item.
+    ///
+    /// Result contains `None` if the user cancelled with 'Esc' or 'q'.
+    /// The dialog is rendered on the [terminal](#method.term) if one is set, or on stderr.
+    pub fn interact_item_opt(&self) -> Result<Option<T>> {
+        self.interact_item_on_opt(&self.select.target_term())
+    }
+
+    /// Like [interact_item](#method.interact_item) but allows a specific terminal to be set.
+    pub fn interact_item_on(&self, term: &Term) -> Result<T> {
+        self.interact_item_with(term, false, &TermKeys)?
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Like [interact_item_opt](#method.interact_item_opt) but allows a specific terminal to be set.
+    pub fn interact_item_on_opt(&self, term: &Term) -> Result<Option<T>> {
+        self.interact_item_with(term, true, &TermKeys)
+    }
+
+    fn interact_item_with(
+        &self,
+        term: &Term,
+        allow_quit: bool,
+        keys: &dyn KeySource,
+    ) -> Result<Option<T>> {
+        Ok(self
+            .select
+            ._interact_on(term, allow_quit, keys)?
+            .map(|outcome| self.values[outcome.index].clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::keys::ScriptedKeys;
+    use console::Key;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Profile {
+        Debug,
+        Release,
+        Bench,
+    }
+
+    impl std::fmt::Display for Profile {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    #[test]
+    fn test_keys_item() {
+        let term = Term::buffered_stderr();
+        let mut select = TypedSelect::new();
+        select
+            .with_prompt("Profile")
+            .items(&[Profile::Debug, Profile::Release])
+            .item_disabled(Profile::Bench, true)
+            .default(0);
+
+        let run = |keys: &[Key]| {
+            select
+                .interact_item_with(&term, true, &ScriptedKeys::new(keys))
+                .unwrap()
+        };
+
+        let keys = [Key::ArrowDown, Key::Enter];
+        let (item, output) = crate::capture_output(|| run(&keys));
+        assert_eq!(item, Some(Profile::Release));
+        assert!(output.contains("  Bench\n"));
+        assert!(output.ends_with("Profile: Release\n"));
+
+        // The disabled item is skipped.
+        assert_eq!(run(&[Key::ArrowUp, Key::Enter]), Some(Profile::Release));
+        assert_eq!(run(&[Key::Escape]), None);
+    }
+}
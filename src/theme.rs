@@ -1,7 +1,12 @@
 //! Customizes the rendering of the elements.
-use std::{fmt, io};
+use std::{borrow::Cow, fmt, io, time::Duration};
 
-use console::{style, Style, StyledObject, Term};
+use crate::capture;
+
+use console::{
+    measure_text_width, strip_ansi_codes, style, truncate_str, Style, StyledObject, Term,
+    TermTarget,
+};
 
 /// Implements a theme for dialoguer.
 pub trait Theme {
@@ -17,24 +22,162 @@ pub trait Theme {
         write!(f, "error: {}", err)
     }
 
+    /// Formats an answer rejected by a validator.
+    #[inline]
+    fn format_validation_error(&self, f: &mut dyn fmt::Write, err: &str) -> fmt::Result {
+        self.format_error(f, err)
+    }
+
+    /// Formats an answer that can't be parsed into the requested type.
+    #[inline]
+    fn format_parse_error(&self, f: &mut dyn fmt::Write, err: &str) -> fmt::Result {
+        self.format_error(f, err)
+    }
+
+    /// Formats the validation status shown while typing an input.
+    ///
+    /// `err` is the validation error of the current input, or `None` if
+    /// the input is valid.
+    fn format_input_live_status(&self, f: &mut dyn fmt::Write, err: Option<&str>) -> fmt::Result {
+        match err {
+            Some(err) => write!(f, "  ({})", err),
+            None => write!(f, "  (ok)"),
+        }
+    }
+
+    /// Formats the counter of typed characters shown while typing an input.
+    fn format_input_length(
+        &self,
+        f: &mut dyn fmt::Write,
+        length: usize,
+        max: usize,
+    ) -> fmt::Result {
+        write!(f, "  ({}/{})", length, max)
+    }
+
     /// Formats a confirm prompt.
     fn format_confirm_prompt(
         &self,
         f: &mut dyn fmt::Write,
         prompt: &str,
         default: Option<bool>,
+    ) -> fmt::Result {
+        self.format_confirm_prompt_labels(f, prompt, default, "y", "n")
+    }
+
+    /// Formats a confirm prompt with custom labels for the answers.
+    ///
+    /// The label of the default answer is rendered in uppercase.
+    fn format_confirm_prompt_labels(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        default: Option<bool>,
+        yes: &str,
+        no: &str,
     ) -> fmt::Result {
         if !prompt.is_empty() {
             write!(f, "{} ", &prompt)?;
         }
         match default {
-            None => write!(f, "[y/n] ")?,
-            Some(true) => write!(f, "[Y/n] ")?,
-            Some(false) => write!(f, "[y/N] ")?,
+            None => write!(f, "[{}/{}] ", yes, no)?,
+            Some(true) => write!(f, "[{}/{}] ", yes.to_uppercase(), no)?,
+            Some(false) => write!(f, "[{}/{}] ", yes, no.to_uppercase())?,
         }
         Ok(())
     }
 
+    /// Formats a confirm prompt with custom labels and a custom separator
+    /// before the answer.
+    ///
+    /// `suffix` replaces the theme's own separator, including any spacing.
+    fn format_confirm_prompt_with_suffix(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        default: Option<bool>,
+        yes: &str,
+        no: &str,
+        suffix: &str,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(f, "{} ", &prompt)?;
+        }
+        match default {
+            None => write!(f, "[{}/{}]{}", yes, no, suffix),
+            Some(true) => write!(f, "[{}/{}]{}", yes.to_uppercase(), no, suffix),
+            Some(false) => write!(f, "[{}/{}]{}", yes, no.to_uppercase(), suffix),
+        }
+    }
+
+    /// Formats a confirm prompt with the answers as buttons.
+    ///
+    /// The highlighted answer is the one chosen on enter.
+    fn format_confirm_prompt_buttons(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        highlighted: Option<bool>,
+    ) -> fmt::Result {
+        self.format_confirm_prompt_buttons_labels(f, prompt, highlighted, "yes", "no")
+    }
+
+    /// Formats a confirm prompt with custom labels on the buttons.
+    fn format_confirm_prompt_buttons_labels(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        highlighted: Option<bool>,
+        yes: &str,
+        no: &str,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(f, "{} ", &prompt)?;
+        }
+        match highlighted {
+            None => write!(f, " {}   {} ", yes, no),
+            Some(true) => write!(f, "[{}]  {} ", yes, no),
+            Some(false) => write!(f, " {}  [{}]", yes, no),
+        }
+    }
+
+    /// Formats a confirm prompt that requires typing a text.
+    #[inline]
+    fn format_confirm_prompt_text(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        expected: &str,
+    ) -> fmt::Result {
+        self.format_input_prompt(
+            f,
+            &format!("{} (type \"{}\" to confirm)", prompt, expected),
+            None,
+        )
+    }
+
+    /// Formats the separator between a prompt and its answer.
+    ///
+    /// The default formatters of input prompts and of the answers of all
+    /// prompts but confirm prompts render it.
+    fn format_prompt_separator(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, ": ")
+    }
+
+    /// Formats the separator between a confirm prompt and its answer.
+    ///
+    /// Confirm prompts usually end with a question mark, so this is a space
+    /// by default.
+    fn format_confirm_prompt_separator(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, " ")
+    }
+
+    /// Formats the separator between the answers of a multi select or sort
+    /// prompt after selection.
+    fn format_selection_separator(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, ", ")
+    }
+
     /// Formats a confirm prompt after selection.
     fn format_confirm_prompt_selection(
         &self,
@@ -42,24 +185,75 @@ pub trait Theme {
         prompt: &str,
         selection: bool,
     ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(f, "{}", &prompt)?;
+            self.format_confirm_prompt_separator(f)?;
+        }
+        write!(f, "{}", if selection { "yes" } else { "no" })
+    }
+
+    /// Formats a confirm prompt that was cancelled.
+    fn format_confirm_prompt_cancelled(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
         if prompt.is_empty() {
-            write!(f, "{}", if selection { "yes" } else { "no" })
+            write!(f, "cancelled")
         } else {
-            write!(f, "{} {}", &prompt, if selection { "yes" } else { "no" })
+            write!(f, "{} cancelled", &prompt)
         }
     }
 
+    /// Formats the remaining time of a prompt with a timeout.
+    ///
+    /// The countdown is written right after the confirm prompt. It should be
+    /// separated from the prompt by a single space and end with a space, so
+    /// that the cursor rests next to it.
+    ///
+    /// `fraction` is the share of the timeout that is left, going from `1.0`
+    /// down to `0.0`.
+    fn format_countdown(
+        &self,
+        f: &mut dyn fmt::Write,
+        remaining: Duration,
+        fraction: f32,
+    ) -> fmt::Result {
+        let _ = fraction;
+        // The confirm prompt already ends with a space.
+        write!(f, "({}s) ", countdown_secs(remaining))
+    }
+
+    /// Formats the position of a prompt in a sequence of prompts.
+    ///
+    /// This is rendered ahead of the prompt icon and the prompt.
+    fn format_step(&self, f: &mut dyn fmt::Write, current: usize, total: usize) -> fmt::Result {
+        write!(f, "Step {}/{} ", current, total)
+    }
+
     /// Formats an input prompt.
     fn format_input_prompt(
         &self,
         f: &mut dyn fmt::Write,
         prompt: &str,
         default: Option<&str>,
+    ) -> fmt::Result {
+        let mut separator = String::new();
+        self.format_prompt_separator(&mut separator)?;
+        self.format_input_prompt_with_suffix(f, prompt, default, &separator)
+    }
+
+    /// Formats an input prompt with a custom separator before the input.
+    ///
+    /// `suffix` replaces the theme's own separator, e.g. `": "`, including
+    /// any spacing.
+    fn format_input_prompt_with_suffix(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        default: Option<&str>,
+        suffix: &str,
     ) -> fmt::Result {
         match default {
-            Some(default) if prompt.is_empty() => write!(f, "[{}]: ", default),
-            Some(default) => write!(f, "{} [{}]: ", prompt, default),
-            None => write!(f, "{}: ", prompt),
+            Some(default) if prompt.is_empty() => write!(f, "[{}]{}", default, suffix),
+            Some(default) => write!(f, "{} [{}]{}", prompt, default, suffix),
+            None => write!(f, "{}{}", prompt, suffix),
         }
     }
 
@@ -71,7 +265,32 @@ pub trait Theme {
         prompt: &str,
         sel: &str,
     ) -> fmt::Result {
-        write!(f, "{}: {}", prompt, sel)
+        write!(f, "{}", prompt)?;
+        self.format_prompt_separator(f)?;
+        write!(f, "{}", sel)
+    }
+
+    /// Formats a number stepper prompt with its current value.
+    #[inline]
+    fn format_number_stepper_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        value: &str,
+    ) -> fmt::Result {
+        self.format_input_prompt(f, prompt, None)?;
+        write!(f, "{}", value)
+    }
+
+    /// Formats a number stepper prompt after selection.
+    #[inline]
+    fn format_number_stepper_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        sel: &str,
+    ) -> fmt::Result {
+        self.format_input_prompt_selection(f, prompt, sel)
     }
 
     /// Formats a password prompt.
@@ -90,6 +309,20 @@ pub trait Theme {
         self.format_input_prompt_selection(f, prompt, "[hidden]")
     }
 
+    /// Formats the remaining attempts after a failed password confirmation.
+    fn format_password_attempts_left(
+        &self,
+        f: &mut dyn fmt::Write,
+        attempts_left: usize,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "({} attempt{} left)",
+            attempts_left,
+            if attempts_left == 1 { "" } else { "s" }
+        )
+    }
+
     /// Formats a select prompt.
     #[inline]
     fn format_select_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
@@ -107,6 +340,96 @@ pub trait Theme {
         self.format_input_prompt_selection(f, prompt, sel)
     }
 
+    /// Formats the filter line of a fuzzy select prompt.
+    fn format_select_prompt_filter(&self, f: &mut dyn fmt::Write, filter: &str) -> fmt::Result {
+        write!(f, "filter: {}", filter)
+    }
+
+    /// Formats the filter of a fuzzy select prompt with the number of items
+    /// matching it.
+    ///
+    /// The count is only shown while there is a filter.
+    fn format_select_prompt_filter_matches(
+        &self,
+        f: &mut dyn fmt::Write,
+        filter: &str,
+        matches: usize,
+        total: usize,
+    ) -> fmt::Result {
+        self.format_select_prompt_filter(f, filter)?;
+
+        if !filter.is_empty() {
+            write!(f, "  ({}/{} matches)", matches, total)?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats the key binding help of a select prompt.
+    fn format_select_prompt_help(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "up/down: move, enter: select")
+    }
+
+    /// Formats the page indicator of a paged select prompt.
+    ///
+    /// `page` counts from 1. Only shown if there is more than one page.
+    fn format_select_prompt_page(
+        &self,
+        f: &mut dyn fmt::Write,
+        page: usize,
+        pages: usize,
+    ) -> fmt::Result {
+        write!(f, "(page {}/{})", page, pages)
+    }
+
+    /// Formats the key binding help of a multi select prompt.
+    fn format_multi_select_prompt_help(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "up/down: move, space: toggle, enter: confirm")
+    }
+
+    /// Formats a section header between the items of a select or multi
+    /// select prompt.
+    fn format_select_prompt_group_header(
+        &self,
+        f: &mut dyn fmt::Write,
+        header: &str,
+    ) -> fmt::Result {
+        write!(f, "-- {} --", header)
+    }
+
+    /// Formats the shortcut key in front of a select prompt item.
+    ///
+    /// The item is styled together with the shortcut, so this should not
+    /// apply styles of its own.
+    fn format_select_prompt_shortcut(&self, f: &mut dyn fmt::Write, key: char) -> fmt::Result {
+        write!(f, "({}) ", key)
+    }
+
+    /// Formats the header above the items of a select or multi select prompt.
+    fn format_select_prompt_header(&self, f: &mut dyn fmt::Write, header: &str) -> fmt::Result {
+        write!(f, "{}", header)
+    }
+
+    /// Formats the footer below the items of a select or multi select prompt.
+    fn format_select_prompt_footer(&self, f: &mut dyn fmt::Write, footer: &str) -> fmt::Result {
+        write!(f, "{}", footer)
+    }
+
+    /// Formats the line shown while a select prompt fetches more items.
+    fn format_select_prompt_loading(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "loading…")
+    }
+
+    /// Formats the prompt asking to type an item's name to confirm it.
+    #[inline]
+    fn format_select_prompt_confirm_phrase(
+        &self,
+        f: &mut dyn fmt::Write,
+        phrase: &str,
+    ) -> fmt::Result {
+        self.format_input_prompt(f, &format!("Type \"{}\" to confirm", phrase), None)
+    }
+
     /// Formats a multi select prompt.
     #[inline]
     fn format_multi_select_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
@@ -126,13 +449,31 @@ pub trait Theme {
         prompt: &str,
         selections: &[&str],
     ) -> fmt::Result {
-        write!(f, "{}: ", prompt)?;
+        write!(f, "{}", prompt)?;
+        self.format_prompt_separator(f)?;
         for (idx, sel) in selections.iter().enumerate() {
-            write!(f, "{}{}", if idx == 0 { "" } else { ", " }, sel)?;
+            if idx > 0 {
+                self.format_selection_separator(f)?;
+            }
+            write!(f, "{}", sel)?;
         }
         Ok(())
     }
 
+    /// Formats a multi select prompt after nothing was selected.
+    ///
+    /// Only used if the prompt has a text for empty selections set.
+    fn format_multi_select_prompt_empty_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        text: &str,
+    ) -> fmt::Result {
+        write!(f, "{}", prompt)?;
+        self.format_prompt_separator(f)?;
+        write!(f, "{}", text)
+    }
+
     /// Formats a sort prompt after selection.
     #[inline]
     fn format_sort_prompt_selection(
@@ -154,6 +495,38 @@ pub trait Theme {
         write!(f, "{} {}", if active { ">" } else { " " }, text)
     }
 
+    /// Formats a select prompt item that already contains styling.
+    #[inline]
+    fn format_select_prompt_styled_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        self.format_select_prompt_item(f, text, active)
+    }
+
+    /// Formats a select prompt item that cannot be selected.
+    #[inline]
+    fn format_select_prompt_disabled_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        self.format_select_prompt_item(f, text, active)
+    }
+
+    /// Formats the description of a select prompt item.
+    #[inline]
+    fn format_select_prompt_description(
+        &self,
+        f: &mut dyn fmt::Write,
+        description: &str,
+    ) -> fmt::Result {
+        write!(f, "{}", description)
+    }
+
     /// Formats a multi select prompt item.
     fn format_multi_select_prompt_item(
         &self,
@@ -175,6 +548,45 @@ pub trait Theme {
         )
     }
 
+    /// Formats a quantity select prompt.
+    #[inline]
+    fn format_quantity_select_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        self.format_prompt(f, prompt)
+    }
+
+    /// Formats a quantity select prompt after selection.
+    fn format_quantity_select_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        selections: &[(&str, u32)],
+    ) -> fmt::Result {
+        let selections: Vec<_> = selections
+            .iter()
+            .map(|&(text, quantity)| format!("{} x{}", text, quantity))
+            .collect();
+        let selections: Vec<_> = selections.iter().map(|s| s.as_str()).collect();
+
+        self.format_multi_select_prompt_selection(f, prompt, &selections)
+    }
+
+    /// Formats a quantity select prompt item.
+    fn format_quantity_select_prompt_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        quantity: u32,
+        checked: bool,
+        active: bool,
+    ) -> fmt::Result {
+        self.format_multi_select_prompt_item(
+            f,
+            &format!("{} [{}]", text, quantity),
+            checked,
+            active,
+        )
+    }
+
     /// Formats a sort prompt item.
     fn format_sort_prompt_item(
         &self,
@@ -194,6 +606,37 @@ pub trait Theme {
             text
         )
     }
+
+    /// Formats a sort prompt item along with its current position.
+    ///
+    /// `position` counts from 1 and is padded to the width of `total`.
+    fn format_sort_prompt_positioned_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        picked: bool,
+        active: bool,
+        position: usize,
+        total: usize,
+    ) -> fmt::Result {
+        let width = total.to_string().len();
+        let text = format!("{:>width$}. {}", position, text, width = width);
+        self.format_sort_prompt_item(f, &text, picked, active)
+    }
+
+    /// Formats an answer of a recap.
+    ///
+    /// Labels are padded to `label_width` so the values line up.
+    fn format_recap_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        label: &str,
+        value: &str,
+        label_width: usize,
+    ) -> fmt::Result {
+        let padding = label_width.saturating_sub(measure_text_width(label));
+        write!(f, "{}:{} {}", label, " ".repeat(padding), value)
+    }
 }
 
 /// The default theme.
@@ -201,6 +644,30 @@ pub struct SimpleTheme;
 
 impl Theme for SimpleTheme {}
 
+/// A theme without any styling.
+///
+/// It formats like [SimpleTheme](struct.SimpleTheme.html), but also
+/// removes the styling from pre-styled items so the output is plain text,
+/// e.g. for logging prompts to a file.
+///
+/// Prompts fall back to this theme on terminals that colors are disabled
+/// for, see [console::colors_enabled_stderr](https://docs.rs/console/0.13/console/fn.colors_enabled_stderr.html).
+/// Everything they render there is stripped of styling, including the
+/// texts passed to the prompts.
+pub struct PlainTheme;
+
+impl Theme for PlainTheme {
+    /// Formats a select prompt item that already contains styling.
+    fn format_select_prompt_styled_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        self.format_select_prompt_item(f, &strip_ansi_codes(text), active)
+    }
+}
+
 /// A colorful theme
 pub struct ColorfulTheme {
     /// The style for default values
@@ -219,6 +686,10 @@ pub struct ColorfulTheme {
     pub error_prefix: StyledObject<String>,
     /// The style for error message
     pub error_style: Style,
+    /// Validation error prefix value and style
+    pub validation_error_prefix: StyledObject<String>,
+    /// The style for validation error messages
+    pub validation_error_style: Style,
     /// The style for hints
     pub hint_style: Style,
     /// The style for values on prompt success
@@ -241,6 +712,8 @@ pub struct ColorfulTheme {
     pub unpicked_item_prefix: StyledObject<String>,
     /// Show the selections from certain prompts inline
     pub inline_selections: bool,
+    /// Separator between inline selections
+    pub selection_separator: String,
 }
 
 impl Default for ColorfulTheme {
@@ -254,6 +727,8 @@ impl Default for ColorfulTheme {
             success_suffix: style("·".to_string()).for_stderr().black().bright(),
             error_prefix: style("✘".to_string()).for_stderr().red(),
             error_style: Style::new().for_stderr().red(),
+            validation_error_prefix: style("✘".to_string()).for_stderr().red(),
+            validation_error_style: Style::new().for_stderr().red(),
             hint_style: Style::new().for_stderr().black().bright(),
             values_style: Style::new().for_stderr().green(),
             active_item_style: Style::new().for_stderr().cyan(),
@@ -265,10 +740,117 @@ impl Default for ColorfulTheme {
             picked_item_prefix: style("❯".to_string()).for_stderr().green(),
             unpicked_item_prefix: style(" ".to_string()).for_stderr(),
             inline_selections: true,
+            selection_separator: ", ".to_string(),
         }
     }
 }
 
+/// Setters for the symbols of the theme.
+///
+/// The styles are applied for stderr, where the prompts are rendered.
+/// The other styles can be changed through the public fields.
+///
+/// ## Example
+///
+/// ```rust
+/// use dialoguer::{console::Style, theme::ColorfulTheme};
+///
+/// let mut theme = ColorfulTheme::default();
+/// theme
+///     .active_item_prefix(">", Style::new().cyan())
+///     .checked_item_prefix("[x]", Style::new().green())
+///     .unchecked_item_prefix("[ ]", Style::new());
+/// ```
+impl ColorfulTheme {
+    /// Sets the prompt prefix and its style.
+    ///
+    /// Defaults to `?`.
+    pub fn prompt_prefix<S: Into<String>>(
+        &mut self,
+        symbol: S,
+        style: Style,
+    ) -> &mut ColorfulTheme {
+        self.prompt_prefix = style.for_stderr().apply_to(symbol.into());
+        self
+    }
+
+    /// Sets the prefix of answered prompts and its style.
+    ///
+    /// Defaults to `✔`.
+    pub fn success_prefix<S: Into<String>>(
+        &mut self,
+        symbol: S,
+        style: Style,
+    ) -> &mut ColorfulTheme {
+        self.success_prefix = style.for_stderr().apply_to(symbol.into());
+        self
+    }
+
+    /// Sets the prefix of errors and its style.
+    ///
+    /// Defaults to `✘`.
+    pub fn error_prefix<S: Into<String>>(&mut self, symbol: S, style: Style) -> &mut ColorfulTheme {
+        self.error_prefix = style.for_stderr().apply_to(symbol.into());
+        self
+    }
+
+    /// Sets the marker of the active item and its style.
+    ///
+    /// Defaults to `❯`.
+    pub fn active_item_prefix<S: Into<String>>(
+        &mut self,
+        symbol: S,
+        style: Style,
+    ) -> &mut ColorfulTheme {
+        self.active_item_prefix = style.for_stderr().apply_to(symbol.into());
+        self
+    }
+
+    /// Sets the marker of inactive items and its style.
+    ///
+    /// Defaults to a space.
+    pub fn inactive_item_prefix<S: Into<String>>(
+        &mut self,
+        symbol: S,
+        style: Style,
+    ) -> &mut ColorfulTheme {
+        self.inactive_item_prefix = style.for_stderr().apply_to(symbol.into());
+        self
+    }
+
+    /// Sets the checkbox of checked items and its style.
+    ///
+    /// Defaults to `✔`.
+    pub fn checked_item_prefix<S: Into<String>>(
+        &mut self,
+        symbol: S,
+        style: Style,
+    ) -> &mut ColorfulTheme {
+        self.checked_item_prefix = style.for_stderr().apply_to(symbol.into());
+        self
+    }
+
+    /// Sets the checkbox of unchecked items and its style.
+    ///
+    /// Defaults to a dim `✔`.
+    pub fn unchecked_item_prefix<S: Into<String>>(
+        &mut self,
+        symbol: S,
+        style: Style,
+    ) -> &mut ColorfulTheme {
+        self.unchecked_item_prefix = style.for_stderr().apply_to(symbol.into());
+        self
+    }
+
+    /// Sets the separator between inline selections.
+    ///
+    /// Defaults to `, `.
+    pub fn selection_separator<S: Into<String>>(&mut self, val: S) -> &mut ColorfulTheme {
+        self.selection_separator = val.into();
+        self
+    }
+}
+
 impl Theme for ColorfulTheme {
     /// Formats a prompt.
     fn format_prompt(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
@@ -294,15 +876,47 @@ impl Theme for ColorfulTheme {
         )
     }
 
-    /// Formats an input prompt.
-    fn format_input_prompt(
+    /// Formats an answer rejected by a validator.
+    fn format_validation_error(&self, f: &mut dyn fmt::Write, err: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            &self.validation_error_prefix,
+            self.validation_error_style.apply_to(err)
+        )
+    }
+
+    /// Formats the validation status shown while typing an input.
+    fn format_input_live_status(&self, f: &mut dyn fmt::Write, err: Option<&str>) -> fmt::Result {
+        match err {
+            Some(err) => write!(f, " {}", self.hint_style.apply_to(format!("✘ {}", err))),
+            None => write!(f, " {}", self.hint_style.apply_to("✔")),
+        }
+    }
+
+    /// Formats the counter of typed characters shown while typing an input.
+    fn format_input_length(
         &self,
         f: &mut dyn fmt::Write,
-        prompt: &str,
-        default: Option<&str>,
+        length: usize,
+        max: usize,
     ) -> fmt::Result {
-        if !prompt.is_empty() {
-            write!(
+        write!(
+            f,
+            " {}",
+            self.hint_style.apply_to(format!("{}/{}", length, max))
+        )
+    }
+
+    /// Formats an input prompt.
+    fn format_input_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        default: Option<&str>,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(
                 f,
                 "{} {} ",
                 &self.prompt_prefix,
@@ -321,6 +935,34 @@ impl Theme for ColorfulTheme {
         }
     }
 
+    /// Formats an input prompt with a custom separator.
+    fn format_input_prompt_with_suffix(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        default: Option<&str>,
+        suffix: &str,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(
+                f,
+                "{} {}",
+                &self.prompt_prefix,
+                self.prompt_style.apply_to(prompt)
+            )?;
+        }
+
+        match default {
+            Some(default) => write!(
+                f,
+                " {}{}",
+                self.hint_style.apply_to(&format!("({})", default)),
+                suffix
+            ),
+            None => write!(f, "{}", suffix),
+        }
+    }
+
     /// Formats a confirm prompt.
     fn format_confirm_prompt(
         &self,
@@ -361,6 +1003,115 @@ impl Theme for ColorfulTheme {
         }
     }
 
+    /// Formats a confirm prompt with custom labels for the answers.
+    fn format_confirm_prompt_labels(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        default: Option<bool>,
+        yes: &str,
+        no: &str,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(
+                f,
+                "{} {} ",
+                &self.prompt_prefix,
+                self.prompt_style.apply_to(prompt)
+            )?;
+        }
+
+        let hint = match default {
+            None => format!("({}/{})", yes, no),
+            Some(true) => format!("({}/{})", yes.to_uppercase(), no),
+            Some(false) => format!("({}/{})", yes, no.to_uppercase()),
+        };
+
+        write!(
+            f,
+            "{} {}",
+            self.hint_style.apply_to(hint),
+            &self.prompt_suffix
+        )
+    }
+
+    /// Formats a confirm prompt with custom labels and a custom separator.
+    fn format_confirm_prompt_with_suffix(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        default: Option<bool>,
+        yes: &str,
+        no: &str,
+        suffix: &str,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(
+                f,
+                "{} {}",
+                &self.prompt_prefix,
+                self.prompt_style.apply_to(prompt)
+            )?;
+        }
+
+        let hint = match default {
+            None => format!("({}/{})", yes, no),
+            Some(true) => format!("({}/{})", yes.to_uppercase(), no),
+            Some(false) => format!("({}/{})", yes, no.to_uppercase()),
+        };
+
+        write!(f, " {}{}", self.hint_style.apply_to(hint), suffix)
+    }
+
+    /// Formats a confirm prompt with custom labels on the buttons.
+    fn format_confirm_prompt_buttons_labels(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        highlighted: Option<bool>,
+        yes: &str,
+        no: &str,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(
+                f,
+                "{} {} ",
+                &self.prompt_prefix,
+                self.prompt_style.apply_to(prompt)
+            )?;
+        }
+
+        let button = |label: &str, active: bool| {
+            if active {
+                self.active_item_style.apply_to(format!(" {} ", label))
+            } else {
+                self.inactive_item_style.apply_to(format!(" {} ", label))
+            }
+        };
+
+        write!(
+            f,
+            "{} {} {}",
+            &self.prompt_suffix,
+            button(yes, highlighted == Some(true)),
+            button(no, highlighted == Some(false))
+        )
+    }
+
+    /// Formats a confirm prompt that was cancelled.
+    fn format_confirm_prompt_cancelled(&self, f: &mut dyn fmt::Write, prompt: &str) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(
+                f,
+                "{} {} ",
+                &self.error_prefix,
+                self.prompt_style.apply_to(prompt)
+            )?;
+        }
+
+        write!(f, "{}", self.hint_style.apply_to("cancelled"))
+    }
+
     /// Formats a confirm prompt after selection.
     fn format_confirm_prompt_selection(
         &self,
@@ -386,6 +1137,43 @@ impl Theme for ColorfulTheme {
         )
     }
 
+    /// Formats the remaining time of a prompt with a timeout.
+    ///
+    /// The countdown turns from green over yellow to a blinking red as the
+    /// time runs out.
+    fn format_countdown(
+        &self,
+        f: &mut dyn fmt::Write,
+        remaining: Duration,
+        fraction: f32,
+    ) -> fmt::Result {
+        let style = if fraction > 0.5 {
+            Style::new().for_stderr().green()
+        } else if fraction > 0.25 {
+            Style::new().for_stderr().yellow()
+        } else {
+            Style::new().for_stderr().red().bold().blink()
+        };
+
+        // Unlike with the simple theme the confirm prompt doesn't end with
+        // a space.
+        write!(
+            f,
+            " {} ",
+            style.apply_to(format!("({}s)", countdown_secs(remaining)))
+        )
+    }
+
+    /// Formats the position of a prompt in a sequence of prompts.
+    fn format_step(&self, f: &mut dyn fmt::Write, current: usize, total: usize) -> fmt::Result {
+        write!(
+            f,
+            "{} ",
+            self.hint_style
+                .apply_to(format!("Step {}/{}", current, total))
+        )
+    }
+
     /// Formats an input prompt after selection.
     fn format_input_prompt_selection(
         &self,
@@ -419,6 +1207,28 @@ impl Theme for ColorfulTheme {
         self.format_input_prompt_selection(f, prompt, "********")
     }
 
+    /// Formats the remaining attempts after a failed password confirmation.
+    fn format_password_attempts_left(
+        &self,
+        f: &mut dyn fmt::Write,
+        attempts_left: usize,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.hint_style.apply_to(format!(
+                "({} attempt{} left)",
+                attempts_left,
+                if attempts_left == 1 { "" } else { "s" }
+            ))
+        )
+    }
+
+    /// Formats the separator between inline selections.
+    fn format_selection_separator(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "{}", &self.selection_separator)
+    }
+
     /// Formats a multi select prompt after selection.
     fn format_multi_select_prompt_selection(
         &self,
@@ -439,18 +1249,40 @@ impl Theme for ColorfulTheme {
 
         if self.inline_selections {
             for (idx, sel) in selections.iter().enumerate() {
-                write!(
-                    f,
-                    "{}{}",
-                    if idx == 0 { "" } else { ", " },
-                    self.values_style.apply_to(sel)
-                )?;
+                if idx > 0 {
+                    self.format_selection_separator(f)?;
+                }
+                write!(f, "{}", self.values_style.apply_to(sel))?;
             }
         }
 
         Ok(())
     }
 
+    /// Formats a multi select prompt after nothing was selected.
+    fn format_multi_select_prompt_empty_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        text: &str,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(
+                f,
+                "{} {} ",
+                &self.success_prefix,
+                self.prompt_style.apply_to(prompt)
+            )?;
+        }
+
+        write!(
+            f,
+            "{} {}",
+            &self.success_suffix,
+            self.hint_style.apply_to(text)
+        )
+    }
+
     /// Formats a select prompt item.
     fn format_select_prompt_item(
         &self,
@@ -472,6 +1304,133 @@ impl Theme for ColorfulTheme {
         write!(f, "{} {}", details.0, details.1)
     }
 
+    /// Formats a select prompt item that already contains styling.
+    fn format_select_prompt_styled_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        let prefix = match active {
+            true => &self.active_item_prefix,
+            false => &self.inactive_item_prefix,
+        };
+
+        write!(f, "{} {}", prefix, text)
+    }
+
+    /// Formats a select prompt item that cannot be selected.
+    fn format_select_prompt_disabled_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        let prefix = match active {
+            true => &self.active_item_prefix,
+            false => &self.inactive_item_prefix,
+        };
+
+        write!(f, "{} {}", prefix, self.hint_style.apply_to(text))
+    }
+
+    /// Formats the description of a select prompt item.
+    fn format_select_prompt_description(
+        &self,
+        f: &mut dyn fmt::Write,
+        description: &str,
+    ) -> fmt::Result {
+        write!(f, "{}", self.hint_style.apply_to(description))
+    }
+
+    /// Formats the filter line of a fuzzy select prompt.
+    fn format_select_prompt_filter(&self, f: &mut dyn fmt::Write, filter: &str) -> fmt::Result {
+        write!(f, "{} {}", &self.prompt_suffix, filter)
+    }
+
+    /// Formats the filter of a fuzzy select prompt with the number of matches.
+    fn format_select_prompt_filter_matches(
+        &self,
+        f: &mut dyn fmt::Write,
+        filter: &str,
+        matches: usize,
+        total: usize,
+    ) -> fmt::Result {
+        self.format_select_prompt_filter(f, filter)?;
+
+        if !filter.is_empty() {
+            write!(
+                f,
+                " {}",
+                self.hint_style
+                    .apply_to(format!("{}/{} matches", matches, total))
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats the key binding help of a select prompt.
+    fn format_select_prompt_help(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "{}", self.hint_style.apply_to("↑↓ move · ⏎ select"))
+    }
+
+    /// Formats the page indicator of a paged select prompt.
+    fn format_select_prompt_page(
+        &self,
+        f: &mut dyn fmt::Write,
+        page: usize,
+        pages: usize,
+    ) -> fmt::Result {
+        let more = match (page > 1, page < pages) {
+            (true, true) => "↑↓ more",
+            (true, false) => "↑ more",
+            _ => "↓ more",
+        };
+
+        write!(
+            f,
+            "{}",
+            self.hint_style
+                .apply_to(format!("{} · page {}/{}", more, page, pages))
+        )
+    }
+
+    /// Formats the key binding help of a multi select prompt.
+    fn format_multi_select_prompt_help(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.hint_style
+                .apply_to("↑↓ move · space toggle · ⏎ confirm")
+        )
+    }
+
+    /// Formats a section header between the items of a select or multi
+    /// select prompt.
+    fn format_select_prompt_group_header(
+        &self,
+        f: &mut dyn fmt::Write,
+        header: &str,
+    ) -> fmt::Result {
+        write!(f, "{}", self.prompt_style.apply_to(header))
+    }
+
+    /// Formats the header above the items of a select or multi select prompt.
+    fn format_select_prompt_header(&self, f: &mut dyn fmt::Write, header: &str) -> fmt::Result {
+        write!(f, "{}", self.prompt_style.apply_to(header))
+    }
+
+    /// Formats the footer below the items of a select or multi select prompt.
+    fn format_select_prompt_footer(&self, f: &mut dyn fmt::Write, footer: &str) -> fmt::Result {
+        write!(f, "{}", self.hint_style.apply_to(footer))
+    }
+
+    /// Formats the line shown while a select prompt fetches more items.
+    fn format_select_prompt_loading(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "{}", self.hint_style.apply_to("loading…"))
+    }
+
     /// Formats a multi select prompt item.
     fn format_multi_select_prompt_item(
         &self,
@@ -527,25 +1486,135 @@ impl Theme for ColorfulTheme {
 
         write!(f, "{} {}", details.0, details.1)
     }
+
+    /// Formats an answer of a recap.
+    fn format_recap_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        label: &str,
+        value: &str,
+        label_width: usize,
+    ) -> fmt::Result {
+        let padding = label_width.saturating_sub(measure_text_width(label));
+
+        write!(
+            f,
+            "{}{} {}",
+            self.prompt_style.apply_to(label),
+            " ".repeat(padding),
+            self.values_style.apply_to(value)
+        )
+    }
+}
+
+/// Returns `item` labeled with its shortcut `key` by `theme`.
+pub(crate) fn shortcut_label(theme: &dyn Theme, key: char, item: &str) -> String {
+    let mut label = String::new();
+    // Formatting into a string can't fail.
+    let _ = theme.format_select_prompt_shortcut(&mut label, key);
+    label.push_str(item);
+    label
+}
+
+/// Returns the remaining seconds of a countdown, rounded up.
+fn countdown_secs(remaining: Duration) -> u64 {
+    remaining.as_secs() + if remaining.subsec_nanos() > 0 { 1 } else { 0 }
+}
+
+/// Pads the start of every line in `s` with `indent` spaces.
+///
+/// Carriage returns are kept in front of the padding so that rewriting a
+/// line in place stays indented.
+fn indent_lines(s: &str, indent: usize) -> String {
+    if indent == 0 {
+        return s.to_string();
+    }
+
+    let pad = " ".repeat(indent);
+    let mut rv = String::with_capacity(s.len());
+    let mut line_start = true;
+
+    for c in s.chars() {
+        match c {
+            '\r' => {}
+            '\n' => line_start = true,
+            _ if line_start => {
+                rv.push_str(&pad);
+                line_start = false;
+            }
+            _ => {}
+        }
+
+        rv.push(c);
+    }
+
+    rv
+}
+
+/// Cuts every line of `s` that is wider than `width` off with an ellipsis.
+fn truncate_lines(s: &str, width: usize) -> String {
+    let width = width.max(1);
+
+    s.split('\n')
+        .map(|line| {
+            if measure_text_width(line) > width {
+                truncate_str(line, width, "…").into_owned()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lines buffered while rendering a scrollable region.
+struct ScrollRegion {
+    lines: Vec<String>,
+    focus: (usize, usize),
+    footer: Option<usize>,
 }
 
 /// Helper struct to conveniently render a theme ot a term.
 pub(crate) struct TermThemeRenderer<'a> {
     term: &'a Term,
     theme: &'a dyn Theme,
+    plain: bool,
     height: usize,
+    line_widths: Vec<usize>,
     prompt_height: usize,
     prompts_reset_height: bool,
+    indent: usize,
+    truncate_echo: bool,
+    truncate_items: bool,
+    prompt_icon: Option<String>,
+    step: Option<(usize, usize)>,
+    prompt_suffix: Option<String>,
+    max_height: Option<usize>,
+    scroll_offset: usize,
+    region: Option<ScrollRegion>,
 }
 
 impl<'a> TermThemeRenderer<'a> {
     pub fn new(term: &'a Term, theme: &'a dyn Theme) -> TermThemeRenderer<'a> {
+        let plain = !colors_enabled(term);
+
         TermThemeRenderer {
             term,
-            theme,
+            theme: if plain { &PlainTheme } else { theme },
+            plain,
             height: 0,
+            line_widths: vec![],
             prompt_height: 0,
             prompts_reset_height: true,
+            indent: 0,
+            truncate_echo: false,
+            truncate_items: false,
+            prompt_icon: None,
+            step: None,
+            prompt_suffix: None,
+            max_height: None,
+            scroll_offset: 0,
+            region: None,
         }
     }
 
@@ -553,24 +1622,93 @@ impl<'a> TermThemeRenderer<'a> {
         self.prompts_reset_height = val;
     }
 
-    pub fn term(&self) -> &Term {
-        self.term
+    pub fn set_indent(&mut self, val: usize) {
+        self.indent = val;
     }
 
-    pub fn add_line(&mut self) {
-        self.height += 1;
+    pub fn set_truncate_echo(&mut self, val: bool) {
+        self.truncate_echo = val;
     }
 
-    fn write_formatted_str<
-        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
-    >(
-        &mut self,
+    pub fn set_truncate_items(&mut self, val: bool) {
+        self.truncate_items = val;
+    }
+
+    pub fn set_prompt_icon(&mut self, val: Option<&str>) {
+        self.prompt_icon = val.map(String::from);
+    }
+
+    pub fn set_step(&mut self, val: Option<(usize, usize)>) {
+        self.step = val;
+    }
+
+    pub fn set_prompt_suffix(&mut self, val: Option<&str>) {
+        self.prompt_suffix = val.map(String::from);
+    }
+
+    /// Caps the height of the prompt when lines are written through a
+    /// scroll region.
+    ///
+    /// Only Select and MultiSelect render their items in a region, other
+    /// prompts are not capped.
+    pub fn set_max_height(&mut self, val: Option<usize>) {
+        self.max_height = val;
+    }
+
+    /// Rings the terminal bell.
+    pub fn bell(&mut self) -> io::Result<()> {
+        self.write_str("\x07")
+    }
+
+    pub fn term(&self) -> &'a Term {
+        self.term
+    }
+
+    pub fn add_line(&mut self) {
+        self.height += 1;
+    }
+
+    /// Returns the widths of the lines written since the last clear.
+    ///
+    /// Every line of a multi-line item counts separately and the widths
+    /// include the item prefixes, but not the indent.
+    pub fn line_widths(&self) -> &[usize] {
+        &self.line_widths
+    }
+
+    fn write_str(&self, text: &str) -> io::Result<()> {
+        let text = self.plain_text(text);
+        capture::record(&text);
+        self.term.write_str(&text)
+    }
+
+    fn write_line(&self, text: &str) -> io::Result<()> {
+        let text = self.plain_text(text);
+        capture::record(&text);
+        capture::record("\n");
+        self.term.write_line(&text)
+    }
+
+    /// Removes the styling from `text` if colors are disabled.
+    fn plain_text<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if self.plain {
+            strip_ansi_codes(text)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    fn write_formatted_str<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
         f: F,
     ) -> io::Result<()> {
         let mut buf = String::new();
         f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let buf = indent_lines(&buf, self.indent);
         self.height += buf.chars().filter(|&x| x == '\n').count();
-        self.term.write_str(&buf)
+        self.write_str(&buf)
     }
 
     fn write_formatted_line<
@@ -581,8 +1719,20 @@ impl<'a> TermThemeRenderer<'a> {
     ) -> io::Result<()> {
         let mut buf = String::new();
         f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let buf = indent_lines(&buf, self.indent);
+
+        if let Some(ref mut region) = self.region {
+            region.lines.extend(buf.split('\n').map(String::from));
+            return Ok(());
+        }
+
         self.height += buf.chars().filter(|&x| x == '\n').count() + 1;
-        self.term.write_line(&buf)
+        let indent = self.indent;
+        self.line_widths.extend(
+            buf.split('\n')
+                .map(|line| measure_text_width(line).saturating_sub(indent)),
+        );
+        self.write_line(&buf)
     }
 
     fn write_formatted_prompt<
@@ -595,92 +1745,452 @@ impl<'a> TermThemeRenderer<'a> {
         if self.prompts_reset_height {
             self.prompt_height = self.height;
             self.height = 0;
+            self.line_widths.clear();
         }
         Ok(())
     }
 
+    fn write_formatted_selection<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        f: F,
+    ) -> io::Result<()> {
+        if !self.truncate_echo {
+            return self.write_formatted_prompt(f);
+        }
+
+        let width = (self.term.size().1 as usize).saturating_sub(self.indent);
+
+        self.write_formatted_prompt(|this, buf| {
+            let mut echo = String::new();
+            f(this, &mut echo)?;
+            write!(buf, "{}", truncate_lines(&echo, width))
+        })
+    }
+
+    /// Writes a line of an item, cut off at the terminal width if enabled.
+    fn write_formatted_item<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        f: F,
+    ) -> io::Result<()> {
+        if !self.truncate_items {
+            return self.write_formatted_line(f);
+        }
+
+        let width = (self.term.size().1 as usize).saturating_sub(self.indent);
+
+        self.write_formatted_line(|this, buf| {
+            let mut item = String::new();
+            f(this, &mut item)?;
+            write!(buf, "{}", truncate_lines(&item, width))
+        })
+    }
+
+    /// Writes the step and the prompt icon, if there are any, ahead of a prompt.
+    fn write_prompt_prefix(&self, buf: &mut dyn fmt::Write) -> fmt::Result {
+        if let Some((current, total)) = self.step {
+            self.theme.format_step(buf, current, total)?;
+        }
+
+        match self.prompt_icon {
+            Some(ref icon) => write!(buf, "{} ", icon),
+            None => Ok(()),
+        }
+    }
+
     pub fn error(&mut self, err: &str) -> io::Result<()> {
         self.write_formatted_line(|this, buf| this.theme.format_error(buf, err))
     }
 
+    pub fn validation_error(&mut self, err: &str) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_validation_error(buf, err))
+    }
+
+    pub fn parse_error(&mut self, err: &str) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_parse_error(buf, err))
+    }
+
+    /// Writes the live validation status after the input and returns its width.
+    ///
+    /// The status continues the current line, so it is not indented.
+    pub fn input_live_status(&mut self, err: Option<&str>) -> io::Result<usize> {
+        let mut buf = String::new();
+        self.theme
+            .format_input_live_status(&mut buf, err)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        self.write_str(&buf)?;
+        Ok(measure_text_width(&buf))
+    }
+
+    /// Writes the counter of typed characters after the input and returns its width.
+    pub fn input_length(&mut self, length: usize, max: usize) -> io::Result<usize> {
+        let mut buf = String::new();
+        self.theme
+            .format_input_length(&mut buf, length, max)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        self.write_str(&buf)?;
+        Ok(measure_text_width(&buf))
+    }
+
     pub fn confirm_prompt(&mut self, prompt: &str, default: Option<bool>) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| this.theme.format_confirm_prompt(buf, prompt, default))
+        self.write_formatted_str(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            match this.prompt_suffix {
+                Some(ref suffix) => this
+                    .theme
+                    .format_confirm_prompt_with_suffix(buf, prompt, default, "y", "n", suffix),
+                None => this.theme.format_confirm_prompt(buf, prompt, default),
+            }
+        })
     }
 
-    pub fn confirm_prompt_selection(&mut self, prompt: &str, sel: bool) -> io::Result<()> {
+    pub fn confirm_prompt_labels(
+        &mut self,
+        prompt: &str,
+        default: Option<bool>,
+        yes: &str,
+        no: &str,
+    ) -> io::Result<()> {
+        self.write_formatted_str(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            match this.prompt_suffix {
+                Some(ref suffix) => this
+                    .theme
+                    .format_confirm_prompt_with_suffix(buf, prompt, default, yes, no, suffix),
+                None => this
+                    .theme
+                    .format_confirm_prompt_labels(buf, prompt, default, yes, no),
+            }
+        })
+    }
+
+    pub fn confirm_prompt_buttons(
+        &mut self,
+        prompt: &str,
+        highlighted: Option<bool>,
+        labels: Option<(&str, &str)>,
+    ) -> io::Result<()> {
+        self.write_formatted_str(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            match labels {
+                Some((yes, no)) => this.theme.format_confirm_prompt_buttons_labels(
+                    buf,
+                    prompt,
+                    highlighted,
+                    yes,
+                    no,
+                ),
+                None => this
+                    .theme
+                    .format_confirm_prompt_buttons(buf, prompt, highlighted),
+            }
+        })
+    }
+
+    pub fn confirm_prompt_text(&mut self, prompt: &str, expected: &str) -> io::Result<()> {
+        self.write_formatted_str(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme.format_confirm_prompt_text(buf, prompt, expected)
+        })
+    }
+
+    pub fn confirm_prompt_cancelled(&mut self, prompt: &str) -> io::Result<()> {
         self.write_formatted_prompt(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme.format_confirm_prompt_cancelled(buf, prompt)
+        })
+    }
+
+    pub fn confirm_prompt_selection(&mut self, prompt: &str, sel: bool) -> io::Result<()> {
+        self.write_formatted_selection(|this, buf| {
+            this.write_prompt_prefix(buf)?;
             this.theme.format_confirm_prompt_selection(buf, prompt, sel)
         })
     }
 
+    pub fn countdown(&mut self, remaining: Duration, fraction: f32) -> io::Result<()> {
+        self.write_formatted_str(|this, buf| this.theme.format_countdown(buf, remaining, fraction))
+    }
+
     pub fn input_prompt(&mut self, prompt: &str, default: Option<&str>) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| this.theme.format_input_prompt(buf, prompt, default))
+        self.write_formatted_str(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            match this.prompt_suffix {
+                Some(ref suffix) => this
+                    .theme
+                    .format_input_prompt_with_suffix(buf, prompt, default, suffix),
+                None => this.theme.format_input_prompt(buf, prompt, default),
+            }
+        })
     }
 
     pub fn input_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
+        self.write_formatted_selection(|this, buf| {
+            this.write_prompt_prefix(buf)?;
             this.theme.format_input_prompt_selection(buf, prompt, sel)
         })
     }
 
+    pub fn number_stepper_prompt(&mut self, prompt: &str, value: &str) -> io::Result<()> {
+        self.write_formatted_str(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme.format_number_stepper_prompt(buf, prompt, value)
+        })
+    }
+
+    pub fn number_stepper_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
+        self.write_formatted_selection(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme
+                .format_number_stepper_prompt_selection(buf, prompt, sel)
+        })
+    }
+
     pub fn password_prompt(&mut self, prompt: &str) -> io::Result<()> {
         self.write_formatted_str(|this, buf| {
             write!(buf, "\r")?;
+            this.write_prompt_prefix(buf)?;
             this.theme.format_password_prompt(buf, prompt)
         })
     }
 
     pub fn password_prompt_selection(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
+        self.write_formatted_selection(|this, buf| {
+            this.write_prompt_prefix(buf)?;
             this.theme.format_password_prompt_selection(buf, prompt)
         })
     }
 
+    pub fn password_mismatch(&mut self, err: &str, attempts_left: Option<usize>) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            this.theme.format_error(buf, err)?;
+
+            if let Some(attempts_left) = attempts_left {
+                write!(buf, " ")?;
+                this.theme
+                    .format_password_attempts_left(buf, attempts_left)?;
+            }
+
+            Ok(())
+        })
+    }
+
     pub fn select_prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| this.theme.format_select_prompt(buf, prompt))
+        self.write_formatted_prompt(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme.format_select_prompt(buf, prompt)
+        })
     }
 
     pub fn select_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
+        self.write_formatted_selection(|this, buf| {
+            this.write_prompt_prefix(buf)?;
             this.theme.format_select_prompt_selection(buf, prompt, sel)
         })
     }
 
-    pub fn select_prompt_item(&mut self, text: &str, active: bool) -> io::Result<()> {
+    /// Writes an item's description after it, `padding` spaces apart.
+    fn write_inline_description(
+        &self,
+        buf: &mut dyn fmt::Write,
+        description: Option<(&str, usize)>,
+    ) -> fmt::Result {
+        match description {
+            Some((description, padding)) => {
+                write!(buf, "{}", " ".repeat(padding))?;
+                self.theme
+                    .format_select_prompt_description(buf, description)
+            }
+            None => Ok(()),
+        }
+    }
+
+    pub fn select_prompt_item(
+        &mut self,
+        text: &str,
+        active: bool,
+        description: Option<(&str, usize)>,
+    ) -> io::Result<()> {
+        self.write_formatted_item(|this, buf| {
+            this.theme.format_select_prompt_item(buf, text, active)?;
+            this.write_inline_description(buf, description)
+        })
+    }
+
+    pub fn select_prompt_styled_item(
+        &mut self,
+        text: &str,
+        active: bool,
+        description: Option<(&str, usize)>,
+    ) -> io::Result<()> {
+        self.write_formatted_item(|this, buf| {
+            this.theme
+                .format_select_prompt_styled_item(buf, text, active)?;
+            this.write_inline_description(buf, description)
+        })
+    }
+
+    pub fn select_prompt_disabled_item(
+        &mut self,
+        text: &str,
+        active: bool,
+        description: Option<(&str, usize)>,
+    ) -> io::Result<()> {
+        self.write_formatted_item(|this, buf| {
+            this.theme
+                .format_select_prompt_disabled_item(buf, text, active)?;
+            this.write_inline_description(buf, description)
+        })
+    }
+
+    /// Writes an item's description on its own line below it.
+    pub fn select_prompt_description(&mut self, description: &str) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            write!(buf, "  ")?;
+            this.theme
+                .format_select_prompt_description(buf, description)
+        })
+    }
+
+    pub fn select_prompt_filter(
+        &mut self,
+        filter: &str,
+        matches: usize,
+        total: usize,
+    ) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            this.theme
+                .format_select_prompt_filter_matches(buf, filter, matches, total)
+        })
+    }
+
+    pub fn select_prompt_page(&mut self, page: usize, pages: usize) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            this.theme.format_select_prompt_page(buf, page, pages)
+        })
+    }
+
+    pub fn select_prompt_help(&mut self) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_select_prompt_help(buf))
+    }
+
+    pub fn multi_select_prompt_help(&mut self) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_multi_select_prompt_help(buf))
+    }
+
+    /// Returns `item` labeled with its shortcut `key`.
+    pub fn shortcut_label(&self, key: char, item: &str) -> String {
+        shortcut_label(self.theme, key, item)
+    }
+
+    pub fn select_prompt_group_header(&mut self, header: &str) -> io::Result<()> {
         self.write_formatted_line(|this, buf| {
-            this.theme.format_select_prompt_item(buf, text, active)
+            this.theme.format_select_prompt_group_header(buf, header)
+        })
+    }
+
+    pub fn select_prompt_header(&mut self, header: &str) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_select_prompt_header(buf, header))
+    }
+
+    pub fn select_prompt_footer(&mut self, footer: &str) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_select_prompt_footer(buf, footer))
+    }
+
+    pub fn select_prompt_loading(&mut self) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme.format_select_prompt_loading(buf))
+    }
+
+    pub fn select_prompt_confirm_phrase(&mut self, phrase: &str) -> io::Result<()> {
+        self.write_formatted_str(|this, buf| {
+            this.theme.format_select_prompt_confirm_phrase(buf, phrase)
         })
     }
 
     pub fn multi_select_prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| this.theme.format_multi_select_prompt(buf, prompt))
+        self.write_formatted_prompt(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme.format_multi_select_prompt(buf, prompt)
+        })
     }
 
     pub fn multi_select_prompt_selection(&mut self, prompt: &str, sel: &[&str]) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
+        self.write_formatted_selection(|this, buf| {
+            this.write_prompt_prefix(buf)?;
             this.theme
                 .format_multi_select_prompt_selection(buf, prompt, sel)
         })
     }
 
+    pub fn multi_select_prompt_empty_selection(
+        &mut self,
+        prompt: &str,
+        text: &str,
+    ) -> io::Result<()> {
+        self.write_formatted_selection(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme
+                .format_multi_select_prompt_empty_selection(buf, prompt, text)
+        })
+    }
+
     pub fn multi_select_prompt_item(
         &mut self,
         text: &str,
         checked: bool,
         active: bool,
     ) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| {
+        self.write_formatted_item(|this, buf| {
             this.theme
                 .format_multi_select_prompt_item(buf, text, checked, active)
         })
     }
 
+    pub fn quantity_select_prompt(&mut self, prompt: &str) -> io::Result<()> {
+        self.write_formatted_prompt(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme.format_quantity_select_prompt(buf, prompt)
+        })
+    }
+
+    pub fn quantity_select_prompt_selection(
+        &mut self,
+        prompt: &str,
+        sel: &[(&str, u32)],
+    ) -> io::Result<()> {
+        self.write_formatted_selection(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme
+                .format_quantity_select_prompt_selection(buf, prompt, sel)
+        })
+    }
+
+    pub fn quantity_select_prompt_item(
+        &mut self,
+        text: &str,
+        quantity: u32,
+        checked: bool,
+        active: bool,
+    ) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            this.theme
+                .format_quantity_select_prompt_item(buf, text, quantity, checked, active)
+        })
+    }
+
     pub fn sort_prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| this.theme.format_sort_prompt(buf, prompt))
+        self.write_formatted_prompt(|this, buf| {
+            this.write_prompt_prefix(buf)?;
+            this.theme.format_sort_prompt(buf, prompt)
+        })
     }
 
     pub fn sort_prompt_selection(&mut self, prompt: &str, sel: &[&str]) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
+        self.write_formatted_selection(|this, buf| {
+            this.write_prompt_prefix(buf)?;
             this.theme.format_sort_prompt_selection(buf, prompt, sel)
         })
     }
@@ -692,23 +2202,710 @@ impl<'a> TermThemeRenderer<'a> {
         })
     }
 
+    pub fn sort_prompt_positioned_item(
+        &mut self,
+        text: &str,
+        picked: bool,
+        active: bool,
+        position: usize,
+        total: usize,
+    ) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            this.theme
+                .format_sort_prompt_positioned_item(buf, text, picked, active, position, total)
+        })
+    }
+
+    pub fn recap_item(&mut self, label: &str, value: &str, label_width: usize) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| {
+            this.theme.format_recap_item(buf, label, value, label_width)
+        })
+    }
+
+    /// Starts buffering lines into a scrollable region.
+    ///
+    /// Without a maximum height this does nothing and lines are written
+    /// right away.
+    pub fn begin_scroll_region(&mut self) {
+        if self.max_height.is_some() {
+            self.region = Some(ScrollRegion {
+                lines: vec![],
+                focus: (0, 0),
+                footer: None,
+            });
+        }
+    }
+
+    /// Marks the following lines as the ones to keep visible.
+    pub fn begin_scroll_focus(&mut self) {
+        if let Some(ref mut region) = self.region {
+            region.focus = (region.lines.len(), region.lines.len());
+        }
+    }
+
+    /// Ends the lines to keep visible.
+    pub fn end_scroll_focus(&mut self) {
+        if let Some(ref mut region) = self.region {
+            region.focus.1 = region.lines.len();
+        }
+    }
+
+    /// Marks the following lines as a footer that is always shown.
+    pub fn begin_scroll_footer(&mut self) {
+        if let Some(ref mut region) = self.region {
+            region.footer = Some(region.lines.len());
+        }
+    }
+
+    /// Writes the part of the scrollable region that fits the maximum height.
+    ///
+    /// The lines rendered above the region stay in place, the body scrolls
+    /// to keep the focused lines visible and the footer is written below.
+    /// If the footer doesn't leave room for a line of the body, it is cut
+    /// off at the bottom. Returns the widths of the written lines, or `None` if no region was
+    /// started.
+    pub fn end_scroll_region(&mut self) -> io::Result<Option<Vec<usize>>> {
+        let region = match self.region.take() {
+            Some(region) => region,
+            None => return Ok(None),
+        };
+
+        let footer_start = region.footer.unwrap_or(region.lines.len());
+        let (body, footer) = region.lines.split_at(footer_start);
+        // The focused line is always shown, even if it doesn't fit. The
+        // footer gives up its last lines to make room for it.
+        let rows = self
+            .max_height
+            .unwrap_or(!0)
+            .saturating_sub(self.prompt_height + self.height)
+            .max(1);
+        let footer = &footer[..footer.len().min(rows - 1)];
+        let available = rows - footer.len();
+
+        self.scroll_offset = scroll_offset(self.scroll_offset, body.len(), available, region.focus);
+
+        let mut sizes = vec![];
+
+        for line in body
+            .iter()
+            .skip(self.scroll_offset)
+            .take(available)
+            .chain(footer)
+        {
+            self.write_line(line)?;
+            self.height += 1;
+            sizes.push(measure_text_width(line).saturating_sub(self.indent));
+        }
+
+        self.line_widths.extend_from_slice(&sizes);
+
+        Ok(Some(sizes))
+    }
+
     pub fn clear(&mut self) -> io::Result<()> {
         self.term
             .clear_last_lines(self.height + self.prompt_height)?;
         self.height = 0;
+        self.line_widths.clear();
         Ok(())
     }
 
     pub fn clear_preserve_prompt(&mut self, size_vec: &[usize]) -> io::Result<()> {
-        let mut new_height = self.height;
-        //Check each item size, increment on finding an overflow
-        for size in size_vec {
-            if *size > self.term.size().1 as usize {
-                new_height += 1;
-            }
-        }
+        let new_height = self.preserve_prompt_height(size_vec);
         self.term.clear_last_lines(new_height)?;
         self.height = 0;
+        self.line_widths.clear();
         Ok(())
     }
+
+    /// Returns the number of terminal rows taken up below the prompt.
+    ///
+    /// Lines wider than the terminal wrap onto as many extra rows as needed.
+    fn preserve_prompt_height(&self, size_vec: &[usize]) -> usize {
+        let columns = self.term.size().1 as usize;
+
+        self.height
+            + size_vec
+                .iter()
+                .map(|size| wrapped_rows(size + self.indent, columns) - 1)
+                .sum::<usize>()
+    }
+}
+
+/// Returns whether colors are enabled for where `term` writes to.
+fn colors_enabled(term: &Term) -> bool {
+    match term.target() {
+        TermTarget::Stdout => console::colors_enabled(),
+        TermTarget::Stderr => console::colors_enabled_stderr(),
+    }
+}
+
+/// Returns the number of rows a line of `width` takes up in `columns`.
+fn wrapped_rows(width: usize, columns: usize) -> usize {
+    if columns == 0 || width == 0 {
+        1
+    } else {
+        (width + columns - 1) / columns
+    }
+}
+
+/// Returns the first of `available` lines out of `len` to show.
+///
+/// Scrolls as little as possible from `offset` to show the `focus` range.
+fn scroll_offset(offset: usize, len: usize, available: usize, focus: (usize, usize)) -> usize {
+    let mut offset = offset;
+
+    if focus.1 > offset + available {
+        offset = focus.1 - available;
+    }
+
+    if focus.0 < offset {
+        offset = focus.0;
+    }
+
+    offset.min(len.saturating_sub(available))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help_line_accounting() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+
+        for &show_help in &[true, false, true] {
+            render.select_prompt_item("Apple", true, None).unwrap();
+            render.select_prompt_item("Banana", false, None).unwrap();
+
+            if show_help {
+                render.select_prompt_help().unwrap();
+            }
+
+            assert_eq!(render.height, if show_help { 3 } else { 2 });
+            render.clear_preserve_prompt(&[5, 6]).unwrap();
+            assert_eq!(render.height, 0);
+        }
+    }
+
+    #[test]
+    fn test_multi_line_item_accounting() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+
+        render.select_prompt_item("Apple\nPie", true, None).unwrap();
+        render.select_prompt_item("Banana", false, None).unwrap();
+        assert_eq!(render.line_widths(), &[7, 3, 8][..]);
+        assert_eq!(render.height, 3);
+        render.clear_preserve_prompt(&[]).unwrap();
+        assert!(render.line_widths().is_empty());
+
+        // The first line only overflows because of the item prefix.
+        let columns = term.size().1 as usize;
+        let item = format!("{}\nPie", "a".repeat(columns - 1));
+        render.select_prompt_item(&item, true, None).unwrap();
+
+        let widths = render.line_widths().to_vec();
+        assert_eq!(render.preserve_prompt_height(&widths), 3);
+    }
+
+    #[test]
+    fn test_styled_item_accounting() {
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+        let mut render = TermThemeRenderer::new(&term, &theme);
+
+        // Wider than the terminal in bytes, but not on screen.
+        let columns = term.size().1 as usize;
+        let item: String = (0..columns / 2)
+            .map(|_| style("x").red().force_styling(true).to_string())
+            .collect();
+        assert!(item.len() > columns);
+
+        for active in &[true, false] {
+            render
+                .select_prompt_styled_item(&item, *active, None)
+                .unwrap();
+            render
+                .select_prompt_styled_item(&item, !*active, None)
+                .unwrap();
+
+            let widths = render.line_widths().to_vec();
+            assert_eq!(widths, vec![columns / 2 + 2; 2]);
+            assert_eq!(render.preserve_prompt_height(&widths), 2);
+
+            render.clear_preserve_prompt(&widths).unwrap();
+            assert_eq!(render.height, 0);
+        }
+    }
+
+    #[test]
+    fn test_wrapped_rows() {
+        assert_eq!(wrapped_rows(0, 80), 1);
+        assert_eq!(wrapped_rows(80, 80), 1);
+        assert_eq!(wrapped_rows(81, 80), 2);
+        assert_eq!(wrapped_rows(200, 80), 3);
+        assert_eq!(wrapped_rows(10, 0), 1);
+    }
+
+    #[test]
+    fn test_scroll_offset() {
+        // Stays in place while the focus is visible.
+        assert_eq!(scroll_offset(2, 10, 4, (3, 4)), 2);
+        // Scrolls down just enough.
+        assert_eq!(scroll_offset(2, 10, 4, (6, 8)), 4);
+        // Scrolls up just enough.
+        assert_eq!(scroll_offset(4, 10, 4, (1, 2)), 1);
+        // Never leaves empty lines at the end.
+        assert_eq!(scroll_offset(8, 10, 4, (9, 10)), 6);
+        assert_eq!(scroll_offset(3, 2, 4, (0, 1)), 0);
+    }
+
+    #[test]
+    fn test_scroll_region() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        render.set_max_height(Some(5));
+        render.select_prompt("Fruit").unwrap();
+
+        let items = ["Apple", "Banana", "Cherry", "Date", "Elderberry", "Fig"];
+
+        for &(sel, visible) in &[(0, &items[..3]), (4, &items[2..5]), (3, &items[2..5])] {
+            render.begin_scroll_region();
+
+            for (idx, item) in items.iter().enumerate() {
+                if idx == sel {
+                    render.begin_scroll_focus();
+                }
+                render.select_prompt_item(item, idx == sel, None).unwrap();
+                if idx == sel {
+                    render.end_scroll_focus();
+                }
+            }
+
+            render.begin_scroll_footer();
+            render.select_prompt_help().unwrap();
+
+            // Nothing is written until the region ends.
+            assert_eq!(render.height, 0);
+
+            let sizes = render.end_scroll_region().unwrap().unwrap();
+
+            // The prompt, three items and the help fill the five lines.
+            let mut expected: Vec<usize> = visible.iter().map(|item| item.len() + 2).collect();
+            expected.push(sizes[3]);
+            assert_eq!(sizes, expected);
+            assert_eq!(render.prompt_height + render.height, 5);
+
+            render.clear_preserve_prompt(&sizes).unwrap();
+            assert_eq!(render.height, 0);
+        }
+    }
+
+    #[test]
+    fn test_scroll_region_clamped() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        render.select_prompt("Fruit").unwrap();
+
+        for &(max_height, rows) in &[(4, 3), (3, 2), (2, 1), (1, 1)] {
+            render.set_max_height(Some(max_height));
+            render.begin_scroll_region();
+
+            for (idx, item) in ["Apple", "Banana", "Cherry"].iter().enumerate() {
+                if idx == 2 {
+                    render.begin_scroll_focus();
+                }
+                render.select_prompt_item(item, idx == 2, None).unwrap();
+                if idx == 2 {
+                    render.end_scroll_focus();
+                }
+            }
+
+            render.begin_scroll_footer();
+            render.select_prompt_help().unwrap();
+            render.select_prompt_help().unwrap();
+
+            // The focused item stays while the footer gives up lines.
+            let sizes = render.end_scroll_region().unwrap().unwrap();
+            assert_eq!(sizes.len(), rows);
+            assert_eq!(sizes[0], "Cherry".len() + 2);
+
+            render.clear_preserve_prompt(&sizes).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_countdown_spacing() {
+        let themes: [&dyn Theme; 2] = [&SimpleTheme, &ColorfulTheme::default()];
+
+        for theme in themes.iter() {
+            let mut buf = String::new();
+            theme
+                .format_confirm_prompt(&mut buf, "Continue?", Some(true))
+                .unwrap();
+            theme
+                .format_countdown(&mut buf, Duration::from_millis(2500), 0.5)
+                .unwrap();
+
+            let buf = strip_ansi_codes(&buf);
+            assert!(buf.ends_with(" (3s) "), "{:?}", buf);
+            assert!(!buf.contains("  ("), "{:?}", buf);
+        }
+    }
+
+    #[test]
+    fn test_confirm_prompt_labels() {
+        let mut buf = String::new();
+        SimpleTheme
+            .format_confirm_prompt_labels(&mut buf, "Continuer ?", Some(true), "o", "n")
+            .unwrap();
+        assert_eq!(buf, "Continuer ? [O/n] ");
+
+        buf.clear();
+        SimpleTheme
+            .format_confirm_prompt(&mut buf, "Continue?", Some(false))
+            .unwrap();
+        assert_eq!(buf, "Continue? [y/N] ");
+    }
+
+    #[test]
+    fn test_confirm_prompt_buttons_labels() {
+        let mut buf = String::new();
+        SimpleTheme
+            .format_confirm_prompt_buttons_labels(
+                &mut buf,
+                "Continuer ?",
+                Some(false),
+                "oui",
+                "non",
+            )
+            .unwrap();
+        assert_eq!(buf, "Continuer ?  oui  [non]");
+
+        buf.clear();
+        SimpleTheme
+            .format_confirm_prompt_buttons(&mut buf, "Continue?", Some(true))
+            .unwrap();
+        assert_eq!(buf, "Continue? [yes]  no ");
+    }
+
+    #[test]
+    fn test_prompt_icon() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        render.set_prompt_icon(Some("🔑"));
+        render.set_step(Some((1, 2)));
+
+        let (_, output) = crate::capture_output(|| render.password_prompt("Password").unwrap());
+        assert_eq!(output, "\rStep 1/2 🔑 Password: ");
+
+        let buf = "🔑 Password: ";
+        assert_eq!(measure_text_width(buf), 13);
+        assert_eq!(truncate_lines(buf, 6), "🔑 Pa…");
+    }
+
+    #[test]
+    fn test_step() {
+        let term = Term::buffered_stderr();
+        let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+        render.set_prompt_icon(Some("🔑"));
+        render.set_step(Some((3, 7)));
+
+        let mut buf = String::new();
+        render.write_prompt_prefix(&mut buf).unwrap();
+        assert_eq!(buf, "Step 3/7 🔑 ");
+    }
+
+    #[test]
+    fn test_recap_item_alignment() {
+        let mut name = String::new();
+        let mut email = String::new();
+        SimpleTheme
+            .format_recap_item(&mut name, "Name", "Ferris", 5)
+            .unwrap();
+        SimpleTheme
+            .format_recap_item(&mut email, "Email", "ferris@example.com", 5)
+            .unwrap();
+
+        assert_eq!(name, "Name:  Ferris");
+        assert_eq!(email, "Email: ferris@example.com");
+    }
+
+    #[test]
+    fn test_multi_select_empty_selection() {
+        let mut buf = String::new();
+        SimpleTheme
+            .format_multi_select_prompt_empty_selection(&mut buf, "Toppings", "(none selected)")
+            .unwrap();
+
+        assert_eq!(buf, "Toppings: (none selected)");
+    }
+
+    #[test]
+    fn test_truncate_lines() {
+        let echo = "Pick one: a very long selected value";
+
+        assert_eq!(truncate_lines(echo, 16), "Pick one: a ver…");
+        assert_eq!(truncate_lines(echo, 80), echo);
+        assert_eq!(
+            truncate_lines("short\nsomewhat longer", 8),
+            "short\nsomewha…"
+        );
+        assert_eq!(
+            measure_text_width(&truncate_lines(
+                &style(echo).red().force_styling(true).to_string(),
+                16
+            )),
+            16
+        );
+    }
+
+    #[test]
+    fn test_indent_lines() {
+        assert_eq!(indent_lines("a\nb", 0), "a\nb");
+        assert_eq!(indent_lines("a\nb", 2), "  a\n  b");
+        assert_eq!(indent_lines("a\n\nb", 2), "  a\n\n  b");
+        assert_eq!(indent_lines("\rpassword: ", 2), "\r  password: ");
+    }
+
+    #[test]
+    fn test_password_attempts_left() {
+        let mut buf = String::new();
+
+        for attempts_left in (1..=3).rev() {
+            SimpleTheme
+                .format_password_attempts_left(&mut buf, attempts_left)
+                .unwrap();
+        }
+
+        assert_eq!(buf, "(3 attempts left)(2 attempts left)(1 attempt left)");
+    }
+
+    #[test]
+    fn test_styled_item_verbatim() {
+        let item = format!("{}", style("fn").red().force_styling(true));
+        let mut buf = String::new();
+
+        ColorfulTheme::default()
+            .format_select_prompt_styled_item(&mut buf, &item, true)
+            .unwrap();
+
+        assert!(buf.ends_with(&format!(" {}", item)));
+    }
+
+    #[test]
+    fn test_colorful_symbols() {
+        let mut theme = ColorfulTheme::default();
+        theme
+            .active_item_prefix("->", Style::new())
+            .checked_item_prefix("[x]", Style::new())
+            .error_prefix("!", Style::new());
+
+        let mut buf = String::new();
+        theme
+            .format_select_prompt_item(&mut buf, "Apple", true)
+            .unwrap();
+        assert!(console::strip_ansi_codes(&buf).starts_with("-> "));
+
+        buf.clear();
+        theme
+            .format_multi_select_prompt_item(&mut buf, "Apple", true, false)
+            .unwrap();
+        assert!(console::strip_ansi_codes(&buf).starts_with("[x] "));
+
+        buf.clear();
+        theme.format_error(&mut buf, "Oops").unwrap();
+        assert_eq!(console::strip_ansi_codes(&buf), "! Oops");
+    }
+
+    #[test]
+    fn test_plain_theme() {
+        let mut buf = String::new();
+        PlainTheme
+            .format_select_prompt_styled_item(&mut buf, "\u{1b}[1mApple\u{1b}[0m", true)
+            .unwrap();
+        assert_eq!(buf, "> Apple");
+
+        // Falls back to the plain theme without colors.
+        console::set_colors_enabled_stderr(false);
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+
+        let (_, output) = crate::capture_output(|| {
+            let mut render = TermThemeRenderer::new(&term, &theme);
+            render.select_prompt("Fruit").unwrap();
+            render
+                .select_prompt_item("\u{1b}[1mApple\u{1b}[0m", true, None)
+                .unwrap();
+        });
+        assert_eq!(output, "Fruit:\n> Apple\n");
+    }
+
+    #[test]
+    fn test_validation_error() {
+        let mut buf = String::new();
+        SimpleTheme
+            .format_validation_error(&mut buf, "Too short")
+            .unwrap();
+        assert_eq!(buf, "error: Too short");
+
+        let theme = ColorfulTheme {
+            validation_error_prefix: style("!".to_string()).for_stderr().yellow(),
+            ..ColorfulTheme::default()
+        };
+
+        buf.clear();
+        theme
+            .format_validation_error(&mut buf, "Too short")
+            .unwrap();
+        assert_eq!(console::strip_ansi_codes(&buf), "! Too short");
+
+        buf.clear();
+        theme.format_parse_error(&mut buf, "Not a number").unwrap();
+        assert_eq!(console::strip_ansi_codes(&buf), "✘ Not a number");
+    }
+
+    #[test]
+    fn test_sort_prompt_positioned_item() {
+        let mut buf = String::new();
+        SimpleTheme
+            .format_sort_prompt_positioned_item(&mut buf, "Deploy", true, true, 3, 12)
+            .unwrap();
+        assert_eq!(buf, "> [x]  3. Deploy");
+    }
+
+    #[test]
+    fn test_selection_separator() {
+        let mut theme = ColorfulTheme::default();
+        theme.selection_separator(" | ");
+        let mut buf = String::new();
+
+        theme
+            .format_multi_select_prompt_selection(&mut buf, "", &["a", "b", "c"])
+            .unwrap();
+
+        assert!(console::strip_ansi_codes(&buf).ends_with("a | b | c"));
+
+        struct SlashTheme;
+
+        impl Theme for SlashTheme {
+            fn format_selection_separator(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+                write!(f, " / ")
+            }
+        }
+
+        let mut buf = String::new();
+        SlashTheme
+            .format_multi_select_prompt_selection(&mut buf, "Picked", &["a", "b"])
+            .unwrap();
+        assert_eq!(buf, "Picked: a / b");
+    }
+
+    #[test]
+    fn test_prompt_suffix() {
+        let mut buf = String::new();
+        SimpleTheme
+            .format_input_prompt(&mut buf, "Name", Some("Bob"))
+            .unwrap();
+        assert_eq!(buf, "Name [Bob]: ");
+
+        let mut buf = String::new();
+        SimpleTheme
+            .format_input_prompt_with_suffix(&mut buf, "Name", None, " › ")
+            .unwrap();
+        assert_eq!(buf, "Name › ");
+
+        let mut buf = String::new();
+        SimpleTheme
+            .format_confirm_prompt_with_suffix(&mut buf, "Continue", Some(true), "y", "n", ": ")
+            .unwrap();
+        assert_eq!(buf, "Continue [Y/n]: ");
+
+        let term = Term::buffered_stderr();
+        let (_, output) = crate::capture_output(|| {
+            let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+            render.set_prompt_suffix(Some(" > "));
+            render.input_prompt("Name", None).unwrap();
+        });
+        assert_eq!(output, "Name > ");
+    }
+
+    #[test]
+    fn test_prompt_separator() {
+        struct ArrowTheme;
+
+        impl Theme for ArrowTheme {
+            fn format_prompt_separator(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+                write!(f, " → ")
+            }
+        }
+
+        let mut buf = String::new();
+        ArrowTheme
+            .format_input_prompt(&mut buf, "Name", None)
+            .unwrap();
+        assert_eq!(buf, "Name → ");
+
+        let mut buf = String::new();
+        ArrowTheme
+            .format_input_prompt_selection(&mut buf, "Name", "Bob")
+            .unwrap();
+        assert_eq!(buf, "Name → Bob");
+
+        let mut buf = String::new();
+        ArrowTheme
+            .format_multi_select_prompt_selection(&mut buf, "Fruits", &["Apple", "Pear"])
+            .unwrap();
+        assert_eq!(buf, "Fruits → Apple, Pear");
+
+        let mut buf = String::new();
+        ArrowTheme
+            .format_multi_select_prompt_empty_selection(&mut buf, "Fruits", "none")
+            .unwrap();
+        assert_eq!(buf, "Fruits → none");
+
+        let mut buf = String::new();
+        ArrowTheme
+            .format_confirm_prompt_selection(&mut buf, "Continue?", true)
+            .unwrap();
+        assert_eq!(buf, "Continue? yes");
+
+        let mut buf = String::new();
+        SimpleTheme
+            .format_confirm_prompt_selection(&mut buf, "", false)
+            .unwrap();
+        assert_eq!(buf, "no");
+    }
+
+    #[test]
+    fn test_select_prompt_filter_matches() {
+        let mut buf = String::new();
+        SimpleTheme
+            .format_select_prompt_filter_matches(&mut buf, "ap", 12, 340)
+            .unwrap();
+        assert_eq!(buf, "filter: ap  (12/340 matches)");
+
+        let mut buf = String::new();
+        SimpleTheme
+            .format_select_prompt_filter_matches(&mut buf, "", 340, 340)
+            .unwrap();
+        assert_eq!(buf, "filter: ");
+    }
+
+    #[test]
+    fn test_truncate_items() {
+        let term = Term::buffered_stderr();
+        let columns = term.size().1 as usize;
+        let item = "x".repeat(columns * 2);
+
+        let (_, output) = crate::capture_output(|| {
+            let mut render = TermThemeRenderer::new(&term, &SimpleTheme);
+            render.set_truncate_items(true);
+            render.select_prompt_item(&item, true, None).unwrap();
+        });
+        assert_eq!(measure_text_width(output.trim_end()), columns);
+        assert!(output.trim_end().ends_with('…'));
+    }
 }
@@ -0,0 +1,103 @@
+//! Provides reading keys with a timeout
+use std::{io, thread, time::Duration};
+
+use console::{Key, Term};
+
+use crate::error::Error;
+
+/// Reads a single key from the terminal, giving up after `timeout` if set.
+pub(crate) fn read_key_within(term: &Term, timeout: Option<Duration>) -> io::Result<Option<Key>> {
+    match timeout {
+        Some(timeout) => read_key_timeout(term, timeout),
+        None => term.read_key().map(Some),
+    }
+}
+
+/// How soon after a newline more input has to follow for it to be pasted.
+const PASTE_GAP: Duration = Duration::from_millis(5);
+
+/// Returns whether more input is already waiting, as it is while the
+/// terminal delivers pasted text.
+///
+/// A terminal that is not user attended never has input waiting, and
+/// neither has one on a platform without polling support.
+pub(crate) fn key_pending(term: &Term) -> io::Result<bool> {
+    if !cfg!(unix) || !term.features().is_attended() {
+        return Ok(false);
+    }
+
+    poll_input(PASTE_GAP)
+}
+
+/// Returns the error for a prompt whose timeout ran out without a default.
+pub(crate) fn timed_out_error() -> Error {
+    io::Error::new(io::ErrorKind::TimedOut, "No answer was given in time").into()
+}
+
+/// Reads a single key from the terminal, giving up after `timeout`.
+///
+/// Returns `None` if no key was pressed in time. A terminal that is not
+/// user attended never delivers a key, so this only waits out the timeout.
+/// On platforms without polling support this blocks like `read_key`.
+pub(crate) fn read_key_timeout(term: &Term, timeout: Duration) -> io::Result<Option<Key>> {
+    if !term.features().is_attended() {
+        thread::sleep(timeout);
+        return Ok(None);
+    }
+
+    if poll_input(timeout)? {
+        term.read_key().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(unix)]
+fn poll_input(timeout: Duration) -> io::Result<bool> {
+    use std::{fs, os::unix::io::AsRawFd};
+
+    let tty_f;
+    let fd = unsafe {
+        if libc::isatty(libc::STDIN_FILENO) == 1 {
+            libc::STDIN_FILENO
+        } else {
+            tty_f = fs::File::open("/dev/tty")?;
+            tty_f.as_raw_fd()
+        }
+    };
+
+    // The terminal has to be in raw mode while polling, otherwise input
+    // only becomes readable once the user hits enter.
+    let mut termios = std::mem::MaybeUninit::uninit();
+    if unsafe { libc::tcgetattr(fd, termios.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let original = unsafe { termios.assume_init() };
+    let mut raw = original;
+    unsafe { libc::cfmakeraw(&mut raw) };
+    if unsafe { libc::tcsetattr(fd, libc::TCSADRAIN, &raw) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut pollfd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let millis = timeout.as_millis().min(std::i32::MAX as u128) as i32;
+    let rv = unsafe { libc::poll(&mut pollfd, 1, millis) };
+    let err = io::Error::last_os_error();
+
+    unsafe { libc::tcsetattr(fd, libc::TCSADRAIN, &original) };
+
+    if rv < 0 {
+        Err(err)
+    } else {
+        Ok(rv > 0)
+    }
+}
+
+#[cfg(not(unix))]
+fn poll_input(_timeout: Duration) -> io::Result<bool> {
+    Ok(true)
+}
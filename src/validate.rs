@@ -3,9 +3,11 @@ use std::fmt::{Debug, Display};
 
 /// Trait for input validators.
 ///
-/// A generic implementation for `Fn(&str) -> Result<(), E>` is provided
-/// to facilitate development.
-pub trait Validator<T> {
+/// A generic implementation for `Fn(&T) -> Result<(), E>` is provided
+/// to facilitate development. Besides inputs this validates the index
+/// picked in a [Select](struct.Select.html) and the indices checked in a
+/// [MultiSelect](struct.MultiSelect.html).
+pub trait Validator<T: ?Sized> {
     type Err: Debug + Display;
 
     /// Invoked with the value to validate.
@@ -15,10 +17,98 @@ pub trait Validator<T> {
     fn validate(&self, input: &T) -> Result<(), Self::Err>;
 }
 
-impl<T, F: Fn(&T) -> Result<(), E>, E: Debug + Display> Validator<T> for F {
+impl<T: ?Sized, F: Fn(&T) -> Result<(), E>, E: Debug + Display> Validator<T> for F {
     type Err = E;
 
     fn validate(&self, input: &T) -> Result<(), Self::Err> {
         self(input)
     }
 }
+
+/// Validator rejecting values that are already in use.
+///
+/// Values are compared by their string representation, case-sensitively
+/// unless [ignore_case](#method.ignore_case) is enabled. This is commonly
+/// used when naming new resources, see
+/// [Input::not_in](struct.Input.html#method.not_in).
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Input, NotIn};
+///
+/// let branches = ["main", "develop"];
+/// let name: String = Input::new()
+///     .with_prompt("Branch name")
+///     .validate_with(NotIn::new(&branches).ignore_case(true))
+///     .interact_text()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct NotIn {
+    existing: Vec<String>,
+    ignore_case: bool,
+}
+
+impl NotIn {
+    /// Creates a validator rejecting any of the `existing` values.
+    pub fn new<S: AsRef<str>>(existing: &[S]) -> NotIn {
+        NotIn {
+            existing: existing.iter().map(|s| s.as_ref().to_string()).collect(),
+            ignore_case: false,
+        }
+    }
+
+    /// Compares values ignoring case.
+    ///
+    /// Values are compared case-sensitively by default.
+    pub fn ignore_case(mut self, val: bool) -> NotIn {
+        self.ignore_case = val;
+        self
+    }
+}
+
+impl<T: Display> Validator<T> for NotIn {
+    type Err = &'static str;
+
+    fn validate(&self, input: &T) -> Result<(), Self::Err> {
+        let input = input.to_string();
+
+        let taken = self.existing.iter().any(|existing| {
+            if self.ignore_case {
+                existing.to_lowercase() == input.to_lowercase()
+            } else {
+                *existing == input
+            }
+        });
+
+        if taken {
+            Err("name already in use")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_in() {
+        let validator = NotIn::new(&["main", "develop"]);
+
+        assert!(validator.validate(&"main".to_string()).is_err());
+        assert!(validator.validate(&"feature".to_string()).is_ok());
+        assert!(validator.validate(&"Main".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_not_in_ignore_case() {
+        let validator = NotIn::new(&["main"]).ignore_case(true);
+
+        assert!(validator.validate(&"MAIN".to_string()).is_err());
+        assert!(validator.validate(&"mainline".to_string()).is_ok());
+    }
+}